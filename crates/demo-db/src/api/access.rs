@@ -0,0 +1,112 @@
+use pg_tables::{
+    pg_core::DbContext,
+    table::{
+        subject::{dto::SubjectId, service::SubjectService},
+        subject_access::{
+            dto::{AccessLevel, GrantSubjectAccess},
+            service::SubjectAccessService,
+        },
+    },
+};
+
+use crate::{
+    Error, Result,
+    dto::access::{Actor, GrantAccessRequest, SubjectAccessResponse},
+};
+
+/// 校验 actor 对 subject 是否具有指定级别的访问权限
+///
+/// 无权限时返回 `Error::permission_denied`（web 层映射为 403）
+pub(crate) async fn authorize(
+    access: &SubjectAccessService,
+    actor: &Actor,
+    subject_id: SubjectId,
+    level: AccessLevel,
+) -> Result<()> {
+    if actor.is_admin {
+        return Ok(());
+    }
+
+    match access.get_role(subject_id, actor.user_id).await? {
+        Some(role) if role.allows(level) => Ok(()),
+        _ => Err(Error::permission_denied(format!(
+            "user {} has no {} access to subject {}",
+            actor.user_id,
+            level.as_str(),
+            subject_id.0
+        ))),
+    }
+}
+
+/// 校验 actor 为管理员（用于全局配置类操作，例如指标管理）
+pub(crate) fn require_admin(actor: &Actor) -> Result<()> {
+    if actor.is_admin {
+        Ok(())
+    } else {
+        Err(Error::permission_denied(format!(
+            "user {} is not an administrator",
+            actor.user_id
+        )))
+    }
+}
+
+pub struct AccessApi {
+    subject: SubjectService,
+    access: SubjectAccessService,
+}
+
+impl AccessApi {
+    pub fn new(db: DbContext) -> Self {
+        Self {
+            subject: SubjectService::new(db.clone()),
+            access: SubjectAccessService::new(db),
+        }
+    }
+
+    /// 授予访问权限（仅 owner / 管理员）
+    pub async fn grant(
+        &self,
+        actor: &Actor,
+        req: GrantAccessRequest,
+    ) -> Result<SubjectAccessResponse> {
+        self.subject
+            .exists(req.subject_id)
+            .await?
+            .then_some(())
+            .ok_or_else(|| Error::not_found("subject", req.subject_id.0))?;
+
+        authorize(&self.access, actor, req.subject_id, AccessLevel::Manage).await?;
+
+        self.access
+            .grant(GrantSubjectAccess {
+                subject_id: req.subject_id,
+                user_id: req.user_id,
+                role: req.role,
+                granted_by: Some(actor.user_id),
+            })
+            .await
+    }
+
+    /// 撤销访问权限（仅 owner / 管理员）
+    pub async fn revoke(&self, actor: &Actor, subject_id: SubjectId, user_id: i64) -> Result<()> {
+        authorize(&self.access, actor, subject_id, AccessLevel::Manage).await?;
+
+        if !self.access.revoke(subject_id, user_id).await? {
+            return Err(Error::not_found(
+                "subject_access",
+                format!("{}:{}", subject_id.0, user_id),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 列出 Subject 的全部授权（仅 owner / 管理员）
+    pub async fn list(
+        &self,
+        actor: &Actor,
+        subject_id: SubjectId,
+    ) -> Result<Vec<SubjectAccessResponse>> {
+        authorize(&self.access, actor, subject_id, AccessLevel::Manage).await?;
+        self.access.list_by_subject(subject_id).await
+    }
+}
@@ -0,0 +1,191 @@
+use std::sync::LazyLock;
+
+use argon2::{
+    Argon2,
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+};
+use pg_tables::{
+    pg_core::DbContext,
+    table::{
+        account::{
+            dto::{AccountId, RegisterAccount},
+            service::AccountService,
+        },
+        revoked_token::service::RevokedTokenService,
+    },
+};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+use crate::{
+    Error, Result,
+    api::access::require_admin,
+    dto::{
+        access::Actor,
+        account::{AccountResponse, LoginRequest, RegisterAccountRequest},
+    },
+};
+
+/// 密码最短长度
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// 登录失败时统一的错误信息（不区分用户不存在 / 密码错误）
+const INVALID_CREDENTIALS: &str = "invalid username or password";
+
+pub struct AccountApi {
+    account: AccountService,
+    revoked: RevokedTokenService,
+}
+
+impl AccountApi {
+    pub fn new(db: DbContext) -> Self {
+        Self {
+            account: AccountService::new(db.clone()),
+            revoked: RevokedTokenService::new(db),
+        }
+    }
+
+    /// 注册账号（仅管理员）：
+    /// 1. 创建账号本人的 Subject
+    /// 2. 创建 Account（argon2 哈希密码）
+    /// 3. 授予账号对该 Subject 的 owner 权限
+    ///
+    /// 三步在同一事务内完成
+    pub async fn register(
+        &self,
+        actor: &Actor,
+        req: RegisterAccountRequest,
+    ) -> Result<AccountResponse> {
+        require_admin(actor)?;
+        self.create_account(req, Vec::new()).await
+    }
+
+    /// 初始化管理员：还没有可用的 `admin_role` 账号时，按给定的用户名和密码创建一个
+    ///
+    /// register 只对管理员开放，新部署的第一个管理员只能由此创建（服务启动时调用）。
+    /// 已有管理员时什么都不做，返回 None；多个实例同时启动时只有一个能创建成功
+    pub async fn bootstrap_admin(
+        &self,
+        req: RegisterAccountRequest,
+        admin_role: &str,
+    ) -> Result<Option<AccountResponse>> {
+        if self.account.has_active_role(admin_role).await? {
+            return Ok(None);
+        }
+
+        match self.create_account(req, vec![admin_role.to_string()]).await {
+            Ok(account) => Ok(Some(account)),
+            // 其它实例先一步创建了管理员
+            Err(_) if self.account.has_active_role(admin_role).await? => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// 账号密码登录
+    ///
+    /// 失败时返回 `Error::permission_denied`（不泄露用户是否存在）；
+    /// 账号不存在时也校验一次密码，响应时间不随用户名是否存在而变化
+    pub async fn login(&self, req: LoginRequest) -> Result<AccountResponse> {
+        let account = self
+            .account
+            .get_by_username(req.username.trim())
+            .await?
+            .filter(|a| a.is_active());
+
+        let Some(account) = account else {
+            verify_password(&req.password, &DUMMY_PASSWORD_HASH);
+            return Err(Error::permission_denied(INVALID_CREDENTIALS));
+        };
+
+        if !verify_password(&req.password, &account.password_hash) {
+            return Err(Error::permission_denied(INVALID_CREDENTIALS));
+        }
+
+        Ok(account)
+    }
+
+    /// 获取可用的账号（刷新 token 时重新读取角色）
+    pub async fn get_active(&self, id: AccountId) -> Result<AccountResponse> {
+        self.account
+            .get(id)
+            .await?
+            .filter(|a| a.is_active())
+            .ok_or_else(|| Error::permission_denied("account is disabled or does not exist"))
+    }
+
+    /// 注销 token（在过期前失效）
+    pub async fn revoke_token(&self, token: &str, expires_at: OffsetDateTime) -> Result<()> {
+        self.revoked.revoke(token_hash(token), expires_at).await
+    }
+
+    /// token 是否已被注销
+    pub async fn is_token_revoked(&self, token: &str) -> Result<bool> {
+        self.revoked.is_revoked(&token_hash(token)).await
+    }
+
+    /// 清理已过期的注销记录
+    pub async fn purge_revoked_tokens(&self) -> Result<u64> {
+        self.revoked.purge_expired(OffsetDateTime::now_utc()).await
+    }
+
+    /// 校验用户名和密码后创建账号（连同本人的 Subject 与 owner 授权）
+    async fn create_account(
+        &self,
+        req: RegisterAccountRequest,
+        roles: Vec<String>,
+    ) -> Result<AccountResponse> {
+        let username = req.username.trim().to_string();
+        if username.is_empty() {
+            return Err(Error::validation("username must not be empty"));
+        }
+        if req.password.chars().count() < MIN_PASSWORD_LEN {
+            return Err(Error::validation(format!(
+                "password must be at least {} characters",
+                MIN_PASSWORD_LEN
+            )));
+        }
+        if self.account.get_by_username(&username).await?.is_some() {
+            return Err(Error::already_exists("Account", "username", username));
+        }
+
+        let password_hash = hash_password(&req.password)?;
+
+        self.account
+            .register(RegisterAccount {
+                username,
+                password_hash,
+                roles,
+            })
+            .await
+    }
+}
+
+/// 账号不存在时用于校验的哈希（与真实账号使用相同的 argon2 参数）
+static DUMMY_PASSWORD_HASH: LazyLock<String> =
+    LazyLock::new(|| hash_password("dummy-password").unwrap_or_default());
+
+fn hash_password(password: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| Error::internal(format!("failed to hash password: {e}")))
+}
+
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// token 的 SHA-256（hex），注销列表只保存哈希
+fn token_hash(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
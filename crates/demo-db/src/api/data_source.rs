@@ -0,0 +1,136 @@
+use pg_tables::{
+    PaginatedResponse,
+    pg_core::DbContext,
+    table::{
+        data_source::{dto::DataSourceId, service::DataSourceService},
+        dto::PaginationInput,
+        observation::{dto::Observation, service::ObservationService},
+        subject_access::{dto::AccessLevel, service::SubjectAccessService},
+    },
+};
+
+use crate::{
+    Error, Result,
+    api::access::authorize,
+    dto::{
+        access::Actor,
+        data_source::{DataSourceResponse, UpdateDataSourceRequest, VoidDataSourceResponse},
+    },
+};
+
+/// 数据来源管理（例如一次上传的报告）
+///
+/// DataSource 本身不归属 subject，权限按其 Observation 涉及的全部 subject 校验
+pub struct DataSourceApi {
+    data_source: DataSourceService,
+    observation: ObservationService,
+    access: SubjectAccessService,
+}
+
+impl DataSourceApi {
+    pub fn new(db: DbContext) -> Self {
+        Self {
+            data_source: DataSourceService::new(db.clone()),
+            observation: ObservationService::new(db.clone()),
+            access: SubjectAccessService::new(db),
+        }
+    }
+
+    /// 获取 DataSource（需要读权限）
+    pub async fn get(&self, actor: &Actor, id: DataSourceId) -> Result<DataSourceResponse> {
+        let source = self.get_source(id).await?;
+        self.authorize_source(actor, id, AccessLevel::Read).await?;
+        Ok(source)
+    }
+
+    /// 修改名称 / 元信息（需要写权限，已作废的来源不可修改）
+    pub async fn update(
+        &self,
+        actor: &Actor,
+        id: DataSourceId,
+        req: UpdateDataSourceRequest,
+    ) -> Result<DataSourceResponse> {
+        let source = self.get_source(id).await?;
+        self.authorize_source(actor, id, AccessLevel::Write).await?;
+
+        if source.is_voided() {
+            return Err(Error::validation(format!("data source {} is voided", id.0)));
+        }
+        if req
+            .name
+            .as_deref()
+            .is_some_and(|name| name.trim().is_empty())
+        {
+            return Err(Error::validation("name must not be empty"));
+        }
+
+        self.data_source
+            .update(id, req)
+            .await?
+            .ok_or_else(|| Error::not_found("data source", id.0))
+    }
+
+    /// 分页查询该来源写入的全部 Observation（含已作废的）
+    pub async fn list_observations(
+        &self,
+        actor: &Actor,
+        id: DataSourceId,
+        pagination: Option<PaginationInput>,
+    ) -> Result<PaginatedResponse<Observation>> {
+        self.get_source(id).await?;
+        self.authorize_source(actor, id, AccessLevel::Read).await?;
+
+        self.observation.list_by_source(id, pagination).await
+    }
+
+    /// 作废来源：来源及其全部 Observation 在同一事务内标记为撤回（需要写权限）
+    ///
+    /// 用于上传了错误报告的场景，数据保留以便审计，但不再参与查询
+    pub async fn void(
+        &self,
+        actor: &Actor,
+        id: DataSourceId,
+        reason: Option<String>,
+    ) -> Result<VoidDataSourceResponse> {
+        self.get_source(id).await?;
+        self.authorize_source(actor, id, AccessLevel::Write).await?;
+
+        self.data_source
+            .void(id, reason)
+            .await?
+            .ok_or_else(|| Error::not_found("data source", id.0))
+    }
+
+    async fn get_source(&self, id: DataSourceId) -> Result<DataSourceResponse> {
+        self.data_source
+            .get(id)
+            .await?
+            .ok_or_else(|| Error::not_found("data source", id.0))
+    }
+
+    /// 校验 actor 对来源涉及的每个 subject 都有指定级别的权限
+    ///
+    /// 还没有任何 Observation 的来源无法确定归属，仅管理员可访问
+    async fn authorize_source(
+        &self,
+        actor: &Actor,
+        id: DataSourceId,
+        level: AccessLevel,
+    ) -> Result<()> {
+        if actor.is_admin {
+            return Ok(());
+        }
+
+        let subject_ids = self.observation.list_subject_ids_by_source(id).await?;
+        if subject_ids.is_empty() {
+            return Err(Error::permission_denied(format!(
+                "data source {} has no observations, only administrators can access it",
+                id.0
+            )));
+        }
+        for subject_id in subject_ids {
+            authorize(&self.access, actor, subject_id, level).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use pg_tables::{
+    PaginatedResponse,
+    pg_core::DbContext,
+    table::{
+        dto::PaginationInput,
+        job::{dto::JobId, service::JobService},
+    },
+};
+use serde_json::Value as JsonValue;
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+    Error, Result,
+    dto::job::{ClaimJobRequest, EnqueueJobRequest, JobResponse, ListJobsRequest},
+};
+
+pub struct JobApi {
+    job: JobService,
+}
+
+impl JobApi {
+    pub fn new(db: DbContext) -> Self {
+        Self {
+            job: JobService::new(db),
+        }
+    }
+
+    pub async fn enqueue(&self, req: EnqueueJobRequest) -> Result<JobResponse> {
+        self.job.enqueue(req).await
+    }
+
+    pub async fn get(&self, id: JobId) -> Result<JobResponse> {
+        self.job
+            .get(id)
+            .await?
+            .ok_or_else(|| Error::not_found("job", id.0))
+    }
+
+    pub async fn list(
+        &self,
+        req: ListJobsRequest,
+        pagination: Option<PaginationInput>,
+    ) -> Result<PaginatedResponse<JobResponse>> {
+        self.job.list(req, pagination).await
+    }
+
+    /// 取消任务并返回最新状态
+    ///
+    /// 已结束的任务保持原状态（幂等）
+    pub async fn cancel(&self, id: JobId) -> Result<JobResponse> {
+        self.job.cancel(id).await?;
+        self.get(id).await
+    }
+
+    pub async fn claim(&self, req: ClaimJobRequest) -> Result<Option<JobResponse>> {
+        self.job.claim(req).await
+    }
+
+    pub async fn heartbeat(
+        &self,
+        id: JobId,
+        worker_id: &str,
+        visibility_timeout: Duration,
+    ) -> Result<bool> {
+        self.job.heartbeat(id, worker_id, visibility_timeout).await
+    }
+
+    pub async fn update_progress(
+        &self,
+        id: JobId,
+        worker_id: &str,
+        progress: JsonValue,
+    ) -> Result<bool> {
+        self.job.update_progress(id, worker_id, progress).await
+    }
+
+    pub async fn complete(&self, id: JobId, worker_id: &str, result: JsonValue) -> Result<bool> {
+        self.job.complete(id, worker_id, result).await
+    }
+
+    pub async fn fail(
+        &self,
+        id: JobId,
+        worker_id: &str,
+        error: String,
+        retry_delay: Option<Duration>,
+    ) -> Result<Option<JobResponse>> {
+        self.job.fail(id, worker_id, error, retry_delay).await
+    }
+
+    /// 清理过期任务：
+    /// 1. 超时且用完重试次数的 running 任务 → failed
+    /// 2. 结束超过 ttl 的任务 → 删除
+    pub async fn cleanup(&self, ttl: Duration) -> Result<(u64, u64)> {
+        let reaped = self.job.reap_expired().await?;
+        let purged = self
+            .job
+            .purge_finished(OffsetDateTime::now_utc() - ttl)
+            .await?;
+        Ok((reaped, purged))
+    }
+}
@@ -1,29 +1,52 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use pg_tables::{
     pg_core::DbContext,
     table::{
-        data_source::{dto::DataSourceId, service::DataSourceService},
+        data_source::{
+            dto::{DataSourceId, ResolveDataSource},
+            service::DataSourceService,
+        },
         metric::{
-            dto::{Metric, MetricKind, MetricSummary},
+            dto::{Metric, MetricId, MetricKind, MetricSummary, MetricValueType},
             service::MetricService,
         },
+        metric_reference_range::{dto::select_range, service::ReferenceRangeService},
+        metric_unit::{
+            dto::UnitConversion, registry::resolve_conversion, service::MetricUnitService,
+        },
         observation::{
-            dto::{ObservationPoint, ObservationQueryKey, ObservationValue, RecordObservation},
+            dto::{
+                BucketSpec, Observation, ObservationId, ObservationPoint, ObservationQueryKey,
+                ObservationValue, RecordObservation,
+            },
             service::ObservationService,
+            value::{NormalizedValue, normalize_value},
+        },
+        recipe::{
+            dto::{QueryRecipe, Recipe},
+            service::RecipeService,
         },
-        recipe::{dto::Recipe, service::RecipeService},
         subject::{dto::SubjectId, service::SubjectService},
+        subject_access::{dto::AccessLevel, service::SubjectAccessService},
+        subject_profile::service::SubjectProfileService,
     },
 };
 use time::OffsetDateTime;
 
 use crate::{
     Error, Result,
-    calc::{get_calc, parse_inputs},
+    api::access::authorize,
+    calc::{bind_args, get_calc, parse_arg_map, parse_inputs},
+    demographics::Demographics,
     dto::{
+        access::Actor,
         base::Range,
         medical::{
-            QueryObservationRequest, QueryObservationResponse, RecordObservationRequest,
-            RecordObservationResult, RecordObservationWithSourceRequest,
+            AggregateObservationResponse, LatestMetricValue, LatestSnapshotResponse,
+            ObservationStatsResponse, QueryObservationRequest, QueryObservationResponse,
+            RecordObservationRequest, RecordObservationResult, RecordObservationWithSourceRequest,
+            RecordReportRequest, RecordReportResult, SkippedReportValue,
         },
     },
 };
@@ -34,6 +57,10 @@ pub struct HealthApi {
     observation: ObservationService,
     data_source: DataSourceService,
     recipe: RecipeService,
+    reference_range: ReferenceRangeService,
+    unit: MetricUnitService,
+    profile: SubjectProfileService,
+    access: SubjectAccessService,
 }
 
 impl HealthApi {
@@ -43,39 +70,63 @@ impl HealthApi {
             metric: MetricService::new(db.clone()),
             observation: ObservationService::new(db.clone()),
             data_source: DataSourceService::new(db.clone()),
-            recipe: RecipeService::new(db),
+            recipe: RecipeService::new(db.clone()),
+            reference_range: ReferenceRangeService::new(db.clone()),
+            unit: MetricUnitService::new(db.clone()),
+            profile: SubjectProfileService::new(db.clone()),
+            access: SubjectAccessService::new(db),
         }
     }
 }
 
 impl HealthApi {
-    pub async fn record_observation(&self, req: RecordObservationRequest) -> Result<()> {
-        // 1. subject 必须存在
-        self.subject
-            .exists(req.subject_id)
-            .await?
-            .then(|| ())
-            .ok_or_else(|| Error::not_found("subject", req.subject_id.0))?;
+    /// 记录观测数据
+    ///
+    /// 指定来源时按 (kind, name) 查找共享来源，不存在则创建，返回实际关联的来源 ID；
+    /// 指定单位时先换算为指标的规范单位
+    pub async fn record_observation(
+        &self,
+        actor: &Actor,
+        req: RecordObservationRequest,
+    ) -> Result<RecordObservationResult> {
+        // 1. subject 必须存在且未停用，且 actor 有写权限
+        self.ensure_writable(actor, req.subject_id).await?;
 
-        // 2. metric 必须存在
-        self.metric
+        // 2. metric 必须存在，值换算为规范单位后按 metric 的值类型校验并规范化
+        let metric = self
+            .metric
             .get(req.metric_id)
             .await?
             .ok_or_else(|| Error::not_found("metric", req.metric_id.0))?;
+        let value = self
+            .to_canonical_unit(&metric, req.value, req.unit.as_deref())
+            .await?;
+        let value = Self::normalize(&metric, &value)?;
+
+        // 3. 解析来源
+        let source_id = match req.source {
+            Some(source) => Some(self.resolve_source(source).await?),
+            None => None,
+        };
 
-        // 3. 组装 pg-tables 的 RecordObservation DTO
+        // 4. 组装 pg-tables 的 RecordObservation DTO
         let input = RecordObservation {
             subject_id: req.subject_id,
             metric_id: req.metric_id,
-            value: req.value,
+            value: value.value,
+            value_num: value.value_num,
+            value_bool: value.value_bool,
             observed_at: req.observed_at,
-            source_id: req.source.map(|_| DataSourceId(0)), // demo：真实系统这里应做 source 映射
+            source_id,
         };
 
-        // 4. 调用真实的单表 service
-        self.observation.record(input).await?;
+        // 5. 调用真实的单表 service
+        let observation = self.observation.record(input).await?;
 
-        Ok(())
+        Ok(RecordObservationResult {
+            observation_id: observation.id,
+            source_id,
+        })
     }
 
     /// 记录观测数据（带 source 创建）
@@ -83,20 +134,22 @@ impl HealthApi {
     /// 2. 再插入 observation
     pub async fn record_observation_with_source(
         &self,
+        actor: &Actor,
         req: RecordObservationWithSourceRequest,
     ) -> Result<RecordObservationResult> {
-        // 1. subject 必须存在
-        self.subject
-            .exists(req.subject_id)
-            .await?
-            .then(|| ())
-            .ok_or_else(|| Error::not_found("subject", req.subject_id.0))?;
+        // 1. subject 必须存在且未停用，且 actor 有写权限
+        self.ensure_writable(actor, req.subject_id).await?;
 
-        // 2. metric 必须存在
-        self.metric
+        // 2. metric 必须存在，值换算为规范单位后按 metric 的值类型校验并规范化
+        let metric = self
+            .metric
             .get(req.metric_id)
             .await?
             .ok_or_else(|| Error::not_found("metric", req.metric_id.0))?;
+        let value = self
+            .to_canonical_unit(&metric, req.value, req.unit.as_deref())
+            .await?;
+        let value = Self::normalize(&metric, &value)?;
 
         // 3. 创建 data_source
         let data_source = self.data_source.create(req.source).await?;
@@ -105,7 +158,9 @@ impl HealthApi {
         let input = RecordObservation {
             subject_id: req.subject_id,
             metric_id: req.metric_id,
-            value: req.value,
+            value: value.value,
+            value_num: value.value_num,
+            value_bool: value.value_bool,
             observed_at: req.observed_at,
             source_id: Some(data_source.id),
         };
@@ -113,40 +168,98 @@ impl HealthApi {
 
         Ok(RecordObservationResult {
             observation_id: observation.id,
-            source_id: data_source.id,
+            source_id: Some(data_source.id),
         })
     }
 
-    pub async fn record_observation_with_source_id(
+    /// 记录一份报告：新建来源并写入其下全部观测
+    ///
+    /// 先把全部值换算为规范单位并校验，任意一项不合法时整体拒绝；
+    /// 单位无法换算（包括指标没有规范单位）的值不写入，在结果中列出；
+    /// 来源与观测在同一事务内写入，失败后重试不会产生重复数据
+    pub async fn record_report(
         &self,
-        subject_id: SubjectId,
-        metric_id: pg_tables::table::metric::dto::MetricId,
-        value: ObservationValue,
-        observed_at: OffsetDateTime,
-        source_id: DataSourceId,
-    ) -> Result<()> {
-        let input = RecordObservation {
-            subject_id,
-            metric_id,
-            value,
-            observed_at,
-            source_id: Some(source_id),
-        };
+        actor: &Actor,
+        req: RecordReportRequest,
+    ) -> Result<RecordReportResult> {
+        self.ensure_writable(actor, req.subject_id).await?;
 
-        self.observation.record(input).await?;
+        let mut observations = Vec::with_capacity(req.values.len());
+        let mut skipped = Vec::new();
+        for item in req.values {
+            let metric = self
+                .metric
+                .get(item.metric_id)
+                .await?
+                .ok_or_else(|| Error::not_found("metric", item.metric_id.0))?;
+            let value = match Self::given_unit(item.unit.as_deref()) {
+                None => item.value,
+                Some(unit) => {
+                    let conversion = self
+                        .resolve_conversion(metric.id, metric.unit.as_deref(), unit)
+                        .await?;
+                    match conversion {
+                        Some(conversion) => {
+                            Self::apply_conversion(&metric, item.value, conversion)?
+                        }
+                        None => {
+                            skipped.push(SkippedReportValue {
+                                metric_id: item.metric_id,
+                                unit: unit.to_string(),
+                            });
+                            continue;
+                        }
+                    }
+                }
+            };
+            let value = Self::normalize(&metric, &value)?;
 
-        Ok(())
+            observations.push(RecordObservation {
+                subject_id: req.subject_id,
+                metric_id: item.metric_id,
+                value: value.value,
+                value_num: value.value_num,
+                value_bool: value.value_bool,
+                observed_at: req.observed_at,
+                source_id: None,
+            });
+        }
+
+        let records_inserted = observations.len();
+        let source = self
+            .data_source
+            .create_with_observations(req.source, observations)
+            .await?;
+
+        Ok(RecordReportResult {
+            source,
+            records_inserted,
+            skipped,
+        })
     }
 
     pub async fn list_selectable_metrics(&self) -> Result<Vec<Metric>> {
         self.metric.list_selectable().await
     }
 
+    /// 校验 actor 对 subject 的访问权限（供上层在执行耗时操作前提前校验）
+    pub async fn authorize(
+        &self,
+        actor: &Actor,
+        subject_id: SubjectId,
+        level: AccessLevel,
+    ) -> Result<()> {
+        authorize(&self.access, actor, subject_id, level).await
+    }
+
     pub async fn query_observation(
         &self,
+        actor: &Actor,
         req: QueryObservationRequest,
         range: Range<OffsetDateTime>,
     ) -> Result<QueryObservationResponse> {
+        authorize(&self.access, actor, req.subject_id, AccessLevel::Read).await?;
+
         let metric = self
             .metric
             .get(req.metric_id.0.into())
@@ -154,54 +267,569 @@ impl HealthApi {
             .ok_or(Error::db_not_found("metric"))?;
         let metric_summary = metric.clone().into();
 
-        match metric.kind {
+        let mut resp = match metric.kind {
             MetricKind::Primitive => {
                 self.query_observation_metric(req.subject_id, metric.id, metric_summary, range)
-                    .await
+                    .await?
             }
             MetricKind::Derived => {
-                let recipe = self
-                    .recipe
-                    .get_by_metric_id(metric.id)
+                let recipes = self.recipe.list_by_metric_id(metric.id).await?;
+                if recipes.is_empty() {
+                    return Err(Error::db_not_found("recipe"));
+                }
+                self.eval_composite_recipe(req.subject_id, recipes, metric_summary, range)
                     .await?
-                    .ok_or(Error::db_not_found("recipe"))?;
-                self.eval_composite_recipe(req.subject_id, recipe, metric_summary, range)
-                    .await
+            }
+        };
+
+        self.flag_points(req.subject_id, &metric, &mut resp.points)
+            .await?;
+
+        Ok(resp)
+    }
+
+    /// 按时间桶聚合观测数据（在数据库内完成）
+    ///
+    /// 只支持数值类型的 Primitive 指标
+    pub async fn aggregate_observation(
+        &self,
+        actor: &Actor,
+        req: QueryObservationRequest,
+        range: Range<OffsetDateTime>,
+        spec: BucketSpec,
+    ) -> Result<AggregateObservationResponse> {
+        authorize(&self.access, actor, req.subject_id, AccessLevel::Read).await?;
+
+        let metric = self
+            .metric
+            .get(req.metric_id)
+            .await?
+            .ok_or_else(|| Error::not_found("metric", req.metric_id.0))?;
+
+        if metric.kind != MetricKind::Primitive {
+            return Err(Error::validation(
+                "bucket aggregation is only available for primitive metrics",
+            ));
+        }
+        if !metric.value_type.is_numeric() {
+            return Err(Error::validation(format!(
+                "bucket aggregation requires a numeric metric, got '{}'",
+                metric.value_type
+            )));
+        }
+
+        let key = ObservationQueryKey {
+            subject_id: req.subject_id,
+            metric_id: req.metric_id,
+        };
+        let buckets = self.observation.aggregate(key, range.into(), spec).await?;
+
+        Ok(AggregateObservationResponse {
+            metric: metric.into(),
+            buckets,
+        })
+    }
+
+    /// 区间内的描述性统计（报告摘要 / 趋势标记）
+    ///
+    /// 只支持数值类型的 Primitive 指标
+    pub async fn observation_stats(
+        &self,
+        actor: &Actor,
+        req: QueryObservationRequest,
+        range: Range<OffsetDateTime>,
+    ) -> Result<ObservationStatsResponse> {
+        authorize(&self.access, actor, req.subject_id, AccessLevel::Read).await?;
+
+        let metric = self
+            .metric
+            .get(req.metric_id)
+            .await?
+            .ok_or_else(|| Error::not_found("metric", req.metric_id.0))?;
+
+        if metric.kind != MetricKind::Primitive {
+            return Err(Error::validation(
+                "statistics are only available for primitive metrics",
+            ));
+        }
+        if !metric.value_type.is_numeric() {
+            return Err(Error::validation(format!(
+                "statistics require a numeric metric, got '{}'",
+                metric.value_type
+            )));
+        }
+
+        let key = ObservationQueryKey {
+            subject_id: req.subject_id,
+            metric_id: req.metric_id,
+        };
+        let stats = self.observation.stats(key, range.into()).await?;
+
+        Ok(ObservationStatsResponse {
+            metric: metric.into(),
+            stats,
+        })
+    }
+
+    /// subject 的最新指标快照：每个 metric 最近一次的观测值
+    ///
+    /// `include_derived` 为 true 时，依赖全部出现在快照中的 Derived 指标
+    /// 也会通过 recipe 计算，取最近一个可计算时刻的结果
+    pub async fn latest_snapshot(
+        &self,
+        actor: &Actor,
+        subject_id: SubjectId,
+        include_derived: bool,
+    ) -> Result<LatestSnapshotResponse> {
+        authorize(&self.access, actor, subject_id, AccessLevel::Read).await?;
+
+        let latest = self.observation.latest_by_subject(subject_id).await?;
+        let metric_ids: Vec<MetricId> = latest.iter().map(|o| o.metric_id).collect();
+        let mut metrics: BTreeMap<i64, Metric> = self
+            .metric
+            .list_by_ids(metric_ids)
+            .await?
+            .into_iter()
+            .map(|m| (m.id.0, m))
+            .collect();
+
+        let mut items = Vec::with_capacity(latest.len());
+        for o in latest {
+            let Some(metric) = metrics.remove(&o.metric_id.0) else {
+                continue;
+            };
+            items.push(LatestMetricValue {
+                metric: metric.into(),
+                point: ObservationPoint {
+                    value: o.value,
+                    value_num: o.value_num,
+                    observed_at: o.observed_at,
+                    flag: None,
+                },
+            });
+        }
+
+        if include_derived {
+            let present: BTreeSet<i64> = items.iter().map(|i| i.metric.id).collect();
+            let derived = self.latest_derived(subject_id, &present).await?;
+            items.extend(derived);
+        }
+
+        items.sort_by(|a, b| a.metric.metric_name.cmp(&b.metric.metric_name));
+
+        Ok(LatestSnapshotResponse { subject_id, items })
+    }
+
+    /// 实时推送的起点：当前最新的观测 ID
+    ///
+    /// 只支持 Primitive 指标（Derived 指标没有直接写入的观测）
+    pub async fn latest_observation_id(
+        &self,
+        actor: &Actor,
+        req: &QueryObservationRequest,
+    ) -> Result<Option<ObservationId>> {
+        authorize(&self.access, actor, req.subject_id, AccessLevel::Read).await?;
+
+        let metric = self
+            .metric
+            .get(req.metric_id)
+            .await?
+            .ok_or_else(|| Error::not_found("metric", req.metric_id.0))?;
+
+        if metric.kind != MetricKind::Primitive {
+            return Err(Error::validation(
+                "live updates are only available for primitive metrics",
+            ));
+        }
+
+        let key = ObservationQueryKey {
+            subject_id: req.subject_id,
+            metric_id: req.metric_id,
+        };
+        self.observation.latest_id(key).await
+    }
+
+    /// 拉取 `after` 之后新写入的观测（按写入顺序）
+    pub async fn list_new_observations(
+        &self,
+        actor: &Actor,
+        req: &QueryObservationRequest,
+        after: Option<ObservationId>,
+        limit: u64,
+    ) -> Result<Vec<Observation>> {
+        // 授权可能在推送过程中被撤销，每次拉取都要校验
+        authorize(&self.access, actor, req.subject_id, AccessLevel::Read).await?;
+
+        let key = ObservationQueryKey {
+            subject_id: req.subject_id,
+            metric_id: req.metric_id,
+        };
+        self.observation.list_after(key, after, limit).await
+    }
+
+    /// subject 的人口学信息（未填写 profile 时各字段为 None）
+    pub async fn demographics(&self, subject_id: SubjectId) -> Result<Demographics> {
+        Demographics::load(&self.profile, subject_id).await
+    }
+
+    /// 按参考范围标记数据点（low / normal / high / critical）
+    ///
+    /// 单位与 metric 不同的范围（早于创建时换算的旧数据）先换算到规范单位，
+    /// 无法换算的跳过；按 subject 的性别和观测时的年龄为每个数据点选择最具体的范围
+    async fn flag_points(
+        &self,
+        subject_id: SubjectId,
+        metric: &Metric,
+        points: &mut [ObservationPoint],
+    ) -> Result<()> {
+        let ranges = self.reference_range.list_by_metric(metric.id).await?;
+        let units = if ranges.iter().any(|r| r.unit.is_some()) {
+            self.unit.list_by_metric(metric.id).await?
+        } else {
+            Vec::new()
+        };
+        let ranges: Vec<_> = ranges
+            .into_iter()
+            .filter_map(|r| match r.unit.as_deref() {
+                None => Some(r),
+                Some(unit) => resolve_conversion(metric.unit.as_deref(), &units, unit)
+                    .map(|conversion| r.convert(conversion, metric.unit.clone())),
+            })
+            .collect();
+
+        if ranges.is_empty() {
+            return Ok(());
+        }
+
+        let demographics = self.demographics(subject_id).await?;
+        for point in points {
+            let age = demographics.age_at(point.observed_at);
+            point.flag = select_range(&ranges, demographics.sex, age)
+                .zip(point.value_num)
+                .map(|(range, v)| range.flag(v));
+        }
+
+        Ok(())
+    }
+
+    /// 把查询结果换算为 `unit`（只支持数值类型的指标）
+    ///
+    /// 参考范围标记基于规范单位计算，换算后保持不变
+    pub async fn convert_observation(
+        &self,
+        resp: &mut QueryObservationResponse,
+        unit: &str,
+    ) -> Result<()> {
+        let conversion = self.read_conversion(&resp.metric, unit).await?;
+        if conversion.is_identity() {
+            return Ok(());
+        }
+
+        for point in &mut resp.points {
+            if let Some(v) = point.value_num {
+                let v = conversion.apply(v);
+                point.value_num = Some(v);
+                point.value = ObservationValue(Self::format_converted(&resp.metric, v));
+            }
+        }
+        resp.metric.unit = Some(unit.trim().to_string());
+
+        Ok(())
+    }
+
+    /// 把分桶聚合结果换算为 `unit`
+    ///
+    /// 换算系数为正，极值、均值、首尾值与分位数都可以直接换算
+    pub async fn convert_buckets(
+        &self,
+        resp: &mut AggregateObservationResponse,
+        unit: &str,
+    ) -> Result<()> {
+        let conversion = self.read_conversion(&resp.metric, unit).await?;
+        if conversion.is_identity() {
+            return Ok(());
+        }
+
+        let apply = |v: &mut Option<f64>| *v = v.map(|v| conversion.apply(v));
+        for bucket in &mut resp.buckets {
+            apply(&mut bucket.min);
+            apply(&mut bucket.max);
+            apply(&mut bucket.avg);
+            apply(&mut bucket.first);
+            apply(&mut bucket.last);
+            for p in &mut bucket.percentiles {
+                *p = conversion.apply(*p);
             }
         }
+        resp.metric.unit = Some(unit.trim().to_string());
+
+        Ok(())
     }
 
+    /// 写入前把值从 `unit` 换算为指标的规范单位
+    async fn to_canonical_unit(
+        &self,
+        metric: &Metric,
+        value: ObservationValue,
+        unit: Option<&str>,
+    ) -> Result<ObservationValue> {
+        let Some(unit) = Self::given_unit(unit) else {
+            return Ok(value);
+        };
+        let conversion = self
+            .conversion(metric.id, metric.unit.as_deref(), unit)
+            .await?;
+        Self::apply_conversion(metric, value, conversion)
+    }
+
+    /// 写入时指定的单位（空白视为未指定）
+    fn given_unit(unit: Option<&str>) -> Option<&str> {
+        unit.map(str::trim).filter(|u| !u.is_empty())
+    }
+
+    /// 按换算系数把值换算为规范单位（非数值指标只接受恒等换算）
+    fn apply_conversion(
+        metric: &Metric,
+        value: ObservationValue,
+        conversion: UnitConversion,
+    ) -> Result<ObservationValue> {
+        if conversion.is_identity() {
+            return Ok(value);
+        }
+        if !metric.value_type.is_numeric() {
+            return Err(Error::validation(format!(
+                "metric {} is not numeric and cannot be converted",
+                metric.code.as_ref()
+            )));
+        }
+
+        let v: f64 = value.as_str().trim().parse().map_err(|_| {
+            Error::validation(format!("'{}' is not a number", value.as_str().trim()))
+        })?;
+        let summary = MetricSummary::from(metric.clone());
+        Ok(ObservationValue(Self::format_converted(
+            &summary,
+            conversion.apply(v),
+        )))
+    }
+
+    /// 读取时从规范单位换算到 `unit` 的系数
+    async fn read_conversion(&self, metric: &MetricSummary, unit: &str) -> Result<UnitConversion> {
+        if !metric.value_type.is_numeric() {
+            return Err(Error::validation(format!(
+                "metric {} is not numeric and cannot be converted",
+                metric.metric_code.as_ref()
+            )));
+        }
+        let conversion = self
+            .conversion(MetricId(metric.id), metric.unit.as_deref(), unit.trim())
+            .await?;
+        Ok(conversion.inverse())
+    }
+
+    /// `unit` -> 规范单位的换算（无法换算时报错）
+    async fn conversion(
+        &self,
+        metric_id: MetricId,
+        canonical: Option<&str>,
+        unit: &str,
+    ) -> Result<UnitConversion> {
+        self.resolve_conversion(metric_id, canonical, unit)
+            .await?
+            .ok_or_else(|| {
+                Error::validation(format!(
+                    "unit '{}' cannot be converted to the metric unit '{}'",
+                    unit,
+                    canonical.unwrap_or_default()
+                ))
+            })
+    }
+
+    /// `unit` -> 规范单位的换算（无法换算时为 None）
+    async fn resolve_conversion(
+        &self,
+        metric_id: MetricId,
+        canonical: Option<&str>,
+        unit: &str,
+    ) -> Result<Option<UnitConversion>> {
+        let units = self.unit.list_by_metric(metric_id).await?;
+        Ok(resolve_conversion(canonical, &units, unit))
+    }
+
+    /// 格式化换算后的数值
+    ///
+    /// 换算结果没有精确的小数形式：Integer 指标四舍五入，Decimal 指标按其精度舍入
+    /// （否则换算后的值会超出允许的小数位数），其余保留 6 位小数并去掉末尾的 0
+    fn format_converted(metric: &MetricSummary, value: f64) -> String {
+        match (&metric.value_type, metric.display_precision) {
+            (MetricValueType::Integer, _) => format!("{}", value.round()),
+            (MetricValueType::Decimal, Some(precision)) => {
+                format!("{:.*}", usize::from(precision), value)
+            }
+            _ => {
+                let s = format!("{:.6}", value);
+                s.trim_end_matches('0').trim_end_matches('.').to_string()
+            }
+        }
+    }
+
+    /// 按 metric 的值类型校验观测值，返回规范形式
+    fn normalize(metric: &Metric, value: &ObservationValue) -> Result<NormalizedValue> {
+        normalize_value(&metric.value_type, value.as_str(), metric.display_precision).map_err(
+            |reason| {
+                Error::validation(format!(
+                    "invalid value for metric {}: {}",
+                    metric.code.as_ref(),
+                    reason
+                ))
+            },
+        )
+    }
+
+    /// 查找或创建共享来源
+    async fn resolve_source(&self, source: ResolveDataSource) -> Result<DataSourceId> {
+        let name = source.name.trim();
+        if name.is_empty() {
+            return Err(Error::validation("source name must not be empty"));
+        }
+
+        let source = self
+            .data_source
+            .resolve(ResolveDataSource {
+                name: name.to_string(),
+                ..source
+            })
+            .await?;
+        Ok(source.id)
+    }
+
+    /// 校验 subject 可写：存在、未停用，且 actor 有写权限
+    async fn ensure_writable(&self, actor: &Actor, subject_id: SubjectId) -> Result<()> {
+        let subject = self
+            .subject
+            .get(subject_id)
+            .await?
+            .ok_or_else(|| Error::not_found("subject", subject_id.0))?;
+        authorize(&self.access, actor, subject_id, AccessLevel::Write).await?;
+
+        if !subject.is_active() {
+            return Err(Error::validation(format!(
+                "subject {} is deactivated",
+                subject_id.0
+            )));
+        }
+        Ok(())
+    }
+
+    /// 快照中的 Derived 指标
+    ///
+    /// 只计算当前版本 recipe 的依赖都已有观测的指标，其余跳过
+    async fn latest_derived(
+        &self,
+        subject_id: SubjectId,
+        present: &BTreeSet<i64>,
+    ) -> Result<Vec<LatestMetricValue>> {
+        let mut by_metric: BTreeMap<i64, Vec<Recipe>> = BTreeMap::new();
+        for recipe in self.recipe.list(QueryRecipe { calc_key: None }).await? {
+            by_metric.entry(recipe.metric_id).or_default().push(recipe);
+        }
+
+        let mut items = Vec::new();
+        for (metric_id, recipes) in by_metric {
+            let Some(current) = recipes.iter().find(|r| r.effective_to.is_none()) else {
+                continue;
+            };
+            let deps: Vec<i64> = serde_json::from_value(current.deps.clone())
+                .map_err(|_| Error::internal("invalid deps for recipe"))?;
+            if !deps.iter().all(|id| present.contains(id)) {
+                continue;
+            }
+
+            let Some(metric) = self.metric.get(MetricId(metric_id)).await? else {
+                continue;
+            };
+            let all = Range {
+                from: None,
+                to: None,
+            };
+            let mut resp = self
+                .eval_composite_recipe(subject_id, recipes, metric.into(), all)
+                .await?;
+            if let Some(point) = resp.points.pop() {
+                items.push(LatestMetricValue {
+                    metric: resp.metric,
+                    point,
+                });
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// 计算 Derived 指标
+    ///
+    /// 每个 recipe 版本只计算其生效区间 `[effective_from, effective_to)` 内的观测，
+    /// 修改公式不会改写历史结果
     async fn eval_composite_recipe(
         &self,
         subject_id: SubjectId,
-        recipe: Recipe,
+        recipes: Vec<Recipe>,
         metric: MetricSummary,
         range: Range<OffsetDateTime>,
     ) -> Result<QueryObservationResponse> {
-        let deps_raw: Vec<i64> = serde_json::from_value(recipe.deps.clone())
-            .map_err(|_| Error::internal("invalid deps for recipe"))?;
-        let deps = deps_raw
-            .into_iter()
-            .map(pg_tables::table::metric::dto::MetricId)
-            .collect::<Vec<_>>();
+        let mut points = Vec::new();
+
+        for recipe in recipes {
+            // 版本区间与查询区间取交集，没有交集的版本跳过
+            let from = match (range.from, recipe.effective_from) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            };
+            let to = match (range.to, recipe.effective_to) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            if matches!((from, to), (Some(from), Some(to)) if from > to) {
+                continue;
+            }
 
-        let calc = get_calc(&recipe.calc_key).ok_or(Error::internal("unknown calc_key"))?;
+            let deps_raw: Vec<i64> = serde_json::from_value(recipe.deps.clone())
+                .map_err(|_| Error::internal("invalid deps for recipe"))?;
+            let deps = deps_raw
+                .into_iter()
+                .map(pg_tables::table::metric::dto::MetricId)
+                .collect::<Vec<_>>();
 
-        let rows = self
-            .observation
-            .query_observation_by_metrics(subject_id, deps, range.into())
-            .await?;
+            let calc = get_calc(&recipe.calc_key).ok_or(Error::internal("unknown calc_key"))?;
+            let arg_map = parse_arg_map(&recipe.arg_map)?;
 
-        let mut points = Vec::with_capacity(rows.len());
-        for row in rows {
-            let inputs = parse_inputs(&row.inputs)?;
-            let value = calc(&inputs)?;
-            points.push(ObservationPoint {
-                value: ObservationValue(value.to_string()),
-                observed_at: row.observed_at,
-            });
+            let rows = self
+                .observation
+                .query_observation_by_metrics(subject_id, deps, Range { from, to }.into())
+                .await?;
+
+            for row in rows {
+                // 查询区间的终点是闭区间，版本的终点是开区间
+                if !recipe.is_effective_at(row.observed_at) {
+                    continue;
+                }
+                let inputs = parse_inputs(&row.inputs)?;
+                // 同一时刻缺少部分依赖时无法计算，跳过该时刻
+                let Ok(args) = bind_args(&arg_map, &inputs) else {
+                    continue;
+                };
+                let value = (calc.func)(&args)?;
+                points.push(ObservationPoint {
+                    value: ObservationValue(value.to_string()),
+                    value_num: Some(value),
+                    observed_at: row.observed_at,
+                    flag: None,
+                });
+            }
         }
 
+        points.sort_by_key(|p| p.observed_at);
+
         Ok(QueryObservationResponse { metric, points })
     }
 
@@ -0,0 +1,261 @@
+use pg_tables::{
+    PaginatedResponse,
+    pg_core::DbContext,
+    table::{
+        dto::PaginationInput,
+        metric::{
+            dto::{MetricId, MetricStatus},
+            service::MetricService,
+        },
+        metric_reference_range::{dto::ReferenceRangeId, service::ReferenceRangeService},
+        metric_unit::{
+            dto::{CreateMetricUnit, MetricUnitId, same_unit},
+            registry::{molar_conversion, resolve_conversion},
+            service::MetricUnitService,
+        },
+    },
+};
+
+use crate::{
+    Error, Result,
+    api::access::require_admin,
+    dto::{
+        access::Actor,
+        metric::{
+            CreateMetricRequest, CreateMetricUnitRequest, CreateReferenceRangeRequest,
+            ListMetricsRequest, MetricResponse, MetricUnitResponse, ReferenceRangeResponse,
+            UnitFormula, UpdateMetricRequest,
+        },
+    },
+};
+
+/// 指标管理（仅管理员）
+pub struct MetricApi {
+    metric: MetricService,
+    reference_range: ReferenceRangeService,
+    unit: MetricUnitService,
+}
+
+impl MetricApi {
+    pub fn new(db: DbContext) -> Self {
+        Self {
+            metric: MetricService::new(db.clone()),
+            reference_range: ReferenceRangeService::new(db.clone()),
+            unit: MetricUnitService::new(db),
+        }
+    }
+
+    /// 创建指标（code 唯一，可视化 / 小数位数需与值类型匹配）
+    pub async fn create(&self, actor: &Actor, req: CreateMetricRequest) -> Result<MetricResponse> {
+        require_admin(actor)?;
+        self.metric.create(req).await
+    }
+
+    pub async fn get(&self, actor: &Actor, id: MetricId) -> Result<MetricResponse> {
+        require_admin(actor)?;
+        self.metric
+            .get(id)
+            .await?
+            .ok_or_else(|| Error::not_found("metric", id.0))
+    }
+
+    /// 分页查询全部指标（包含已废弃指标）
+    pub async fn list(
+        &self,
+        actor: &Actor,
+        req: ListMetricsRequest,
+        pagination: Option<PaginationInput>,
+    ) -> Result<PaginatedResponse<MetricResponse>> {
+        require_admin(actor)?;
+        self.metric.list(req, pagination).await
+    }
+
+    pub async fn update(
+        &self,
+        actor: &Actor,
+        id: MetricId,
+        req: UpdateMetricRequest,
+    ) -> Result<MetricResponse> {
+        require_admin(actor)?;
+        self.metric
+            .update(id, req)
+            .await?
+            .ok_or_else(|| Error::not_found("metric", id.0))
+    }
+
+    /// 废弃指标：不再出现在选择列表中，历史数据仍可查询
+    pub async fn deprecate(&self, actor: &Actor, id: MetricId) -> Result<MetricResponse> {
+        self.set_status(actor, id, MetricStatus::Deprecated).await
+    }
+
+    /// 恢复已废弃的指标
+    pub async fn reactivate(&self, actor: &Actor, id: MetricId) -> Result<MetricResponse> {
+        self.set_status(actor, id, MetricStatus::Active).await
+    }
+
+    /// 指标的全部参考范围
+    pub async fn list_reference_ranges(
+        &self,
+        actor: &Actor,
+        id: MetricId,
+    ) -> Result<Vec<ReferenceRangeResponse>> {
+        require_admin(actor)?;
+        self.ensure_numeric(id).await?;
+        self.reference_range.list_by_metric(id).await
+    }
+
+    /// 添加参考范围（只支持数值类型的指标）
+    pub async fn create_reference_range(
+        &self,
+        actor: &Actor,
+        req: CreateReferenceRangeRequest,
+    ) -> Result<ReferenceRangeResponse> {
+        require_admin(actor)?;
+        let metric = self.ensure_numeric(req.metric_id).await?;
+        let req = self.range_in_metric_unit(&metric, req).await?;
+        self.reference_range.create(req).await
+    }
+
+    /// 参考范围按指标的规范单位保存：指定了其他单位时先换算，无法换算时拒绝
+    async fn range_in_metric_unit(
+        &self,
+        metric: &MetricResponse,
+        req: CreateReferenceRangeRequest,
+    ) -> Result<CreateReferenceRangeRequest> {
+        let Some(unit) = req.unit.as_deref() else {
+            return Ok(req);
+        };
+        let units = self.unit.list_by_metric(metric.id).await?;
+        let conversion =
+            resolve_conversion(metric.unit.as_deref(), &units, unit).ok_or_else(|| {
+                Error::validation(format!(
+                    "reference range unit '{}' cannot be converted to the metric unit '{}'",
+                    unit,
+                    metric.unit.as_deref().unwrap_or_default()
+                ))
+            })?;
+        Ok(req.convert(conversion, metric.unit.clone()))
+    }
+
+    /// 删除参考范围（必须属于该指标）
+    pub async fn delete_reference_range(
+        &self,
+        actor: &Actor,
+        id: MetricId,
+        range_id: ReferenceRangeId,
+    ) -> Result<()> {
+        require_admin(actor)?;
+
+        let range = self
+            .reference_range
+            .get(range_id)
+            .await?
+            .filter(|r| r.metric_id == id)
+            .ok_or_else(|| Error::not_found("reference_range", range_id.0))?;
+        self.reference_range.delete(range.id).await?;
+        Ok(())
+    }
+
+    /// 指标登记的全部备选单位
+    pub async fn list_units(&self, actor: &Actor, id: MetricId) -> Result<Vec<MetricUnitResponse>> {
+        require_admin(actor)?;
+        self.ensure_numeric(id).await?;
+        self.unit.list_by_metric(id).await
+    }
+
+    /// 登记备选单位（指标必须是数值类型且有规范单位）
+    pub async fn create_unit(
+        &self,
+        actor: &Actor,
+        req: CreateMetricUnitRequest,
+    ) -> Result<MetricUnitResponse> {
+        require_admin(actor)?;
+
+        let metric = self.ensure_numeric(req.metric_id).await?;
+        let canonical = metric.unit.as_deref().ok_or_else(|| {
+            Error::validation(format!(
+                "metric {} has no unit to convert to",
+                metric.code.as_ref()
+            ))
+        })?;
+        if same_unit(canonical, &req.unit) {
+            return Err(Error::validation(format!(
+                "'{}' is already the unit of metric {}",
+                req.unit.trim(),
+                metric.code.as_ref()
+            )));
+        }
+
+        let conversion = match req.formula {
+            UnitFormula::Affine(conversion) => conversion,
+            UnitFormula::MolarMass(molar_mass) => {
+                if !molar_mass.is_finite() || molar_mass <= 0.0 {
+                    return Err(Error::validation("molar mass must be a positive number"));
+                }
+                molar_conversion(&req.unit, canonical, molar_mass).ok_or_else(|| {
+                    Error::validation(format!(
+                        "cannot convert '{}' to '{}' by molar mass",
+                        req.unit.trim(),
+                        canonical
+                    ))
+                })?
+            }
+        };
+
+        self.unit
+            .create(CreateMetricUnit {
+                metric_id: req.metric_id,
+                unit: req.unit,
+                conversion,
+            })
+            .await
+    }
+
+    /// 删除备选单位（必须属于该指标）
+    pub async fn delete_unit(
+        &self,
+        actor: &Actor,
+        id: MetricId,
+        unit_id: MetricUnitId,
+    ) -> Result<()> {
+        require_admin(actor)?;
+
+        let unit = self
+            .unit
+            .get(unit_id)
+            .await?
+            .filter(|u| u.metric_id == id)
+            .ok_or_else(|| Error::not_found("metric_unit", unit_id.0))?;
+        self.unit.delete(unit.id).await?;
+        Ok(())
+    }
+
+    async fn ensure_numeric(&self, id: MetricId) -> Result<MetricResponse> {
+        let metric = self
+            .metric
+            .get(id)
+            .await?
+            .ok_or_else(|| Error::not_found("metric", id.0))?;
+        if !metric.value_type.is_numeric() {
+            return Err(Error::validation(format!(
+                "metric {} is not numeric, got '{}'",
+                metric.code.as_ref(),
+                metric.value_type
+            )));
+        }
+        Ok(metric)
+    }
+
+    async fn set_status(
+        &self,
+        actor: &Actor,
+        id: MetricId,
+        status: MetricStatus,
+    ) -> Result<MetricResponse> {
+        require_admin(actor)?;
+        self.metric
+            .set_status(id, status)
+            .await?
+            .ok_or_else(|| Error::not_found("metric", id.0))
+    }
+}
@@ -1,2 +1,8 @@
+pub mod access;
+pub mod account;
+pub mod data_source;
+pub mod job;
 pub mod medical;
+pub mod metric;
 pub mod recipe;
+pub mod subject;
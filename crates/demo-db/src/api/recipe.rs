@@ -1,30 +1,171 @@
+use std::collections::BTreeSet;
+
 use pg_tables::{
     pg_core::DbContext,
-    table::recipe::{dto::QueryRecipe, service::RecipeService},
+    table::{
+        metric::{
+            dto::{MetricId, MetricKind},
+            service::MetricService,
+        },
+        recipe::{dto::QueryRecipe, service::RecipeService},
+    },
 };
 
-use crate::{Result, dto::recipe::{CreateRecipeRequest, RecipeResponse}};
+use crate::{
+    Error, Result,
+    api::access::require_admin,
+    calc::{get_calc, parse_arg_map},
+    dto::{
+        access::Actor,
+        recipe::{CreateRecipeRequest, PublishRecipeRequest, RecipeResponse},
+    },
+};
 
+/// Recipe 管理（仅管理员）
 pub struct RecipeApi {
     recipe: RecipeService,
+    metric: MetricService,
 }
 
 impl RecipeApi {
     pub fn new(db: DbContext) -> Self {
         Self {
-            recipe: RecipeService::new(db),
+            recipe: RecipeService::new(db.clone()),
+            metric: MetricService::new(db),
         }
     }
 
-    pub async fn create(&self, req: CreateRecipeRequest) -> Result<RecipeResponse> {
+    /// 创建 Recipe（metric 的第 1 个版本）
+    pub async fn create(&self, actor: &Actor, req: CreateRecipeRequest) -> Result<RecipeResponse> {
+        require_admin(actor)?;
+
+        if self
+            .recipe
+            .get_by_metric_id(MetricId(req.metric_id))
+            .await?
+            .is_some()
+        {
+            return Err(Error::already_exists(
+                "Recipe",
+                "metric_id",
+                req.metric_id.to_string(),
+            ));
+        }
+        self.validate(&req).await?;
+
         self.recipe.create(req).await
     }
 
-    pub async fn get(&self, id: i64) -> Result<RecipeResponse> {
+    /// 发布新版本：从 `effective_from` 起替换当前版本，旧版本保留给历史数据
+    pub async fn publish(
+        &self,
+        actor: &Actor,
+        req: PublishRecipeRequest,
+    ) -> Result<RecipeResponse> {
+        require_admin(actor)?;
+
+        let metric_id = req.recipe.metric_id;
+        self.recipe
+            .get_by_metric_id(MetricId(metric_id))
+            .await?
+            .ok_or_else(|| Error::not_found("recipe", metric_id))?;
+        self.validate(&req.recipe).await?;
+
+        self.recipe.publish(req).await
+    }
+
+    /// metric 的全部 recipe 版本
+    pub async fn list_versions(
+        &self,
+        actor: &Actor,
+        metric_id: MetricId,
+    ) -> Result<Vec<RecipeResponse>> {
+        require_admin(actor)?;
+        self.recipe.list_by_metric_id(metric_id).await
+    }
+
+    pub async fn get(&self, actor: &Actor, id: i64) -> Result<RecipeResponse> {
+        require_admin(actor)?;
         self.recipe.get(id).await
     }
 
-    pub async fn list(&self, req: QueryRecipe) -> Result<Vec<RecipeResponse>> {
+    pub async fn list(&self, actor: &Actor, req: QueryRecipe) -> Result<Vec<RecipeResponse>> {
+        require_admin(actor)?;
         self.recipe.list(req).await
     }
+
+    /// 校验 Recipe 定义：
+    /// 1. 目标 metric 存在且为 Derived
+    /// 2. calc_key 已注册
+    /// 3. deps 是不重复的、已存在的 Primitive metric id
+    /// 4. arg_map 的参数名与 calc 所需参数一致，且与 deps 一一对应
+    async fn validate(&self, req: &CreateRecipeRequest) -> Result<()> {
+        // 1. 目标 metric
+        let target = self
+            .metric
+            .get(MetricId(req.metric_id))
+            .await?
+            .ok_or_else(|| Error::not_found("metric", req.metric_id))?;
+        if target.kind != MetricKind::Derived {
+            return Err(Error::validation(format!(
+                "metric {} is not a derived metric",
+                req.metric_id
+            )));
+        }
+
+        // 2. calc_key
+        let calc = get_calc(&req.calc_key).ok_or_else(|| {
+            Error::validation(format!("calc_key '{}' is not registered", req.calc_key))
+        })?;
+
+        // 3. deps
+        let deps: Vec<i64> = serde_json::from_value(req.deps.clone())
+            .map_err(|_| Error::validation("deps must be a list of metric ids"))?;
+        if deps.is_empty() {
+            return Err(Error::validation("deps must not be empty"));
+        }
+        let dep_set: BTreeSet<i64> = deps.iter().copied().collect();
+        if dep_set.len() != deps.len() {
+            return Err(Error::validation("deps must not contain duplicates"));
+        }
+        for id in &deps {
+            let dep =
+                self.metric.get(MetricId(*id)).await?.ok_or_else(|| {
+                    Error::validation(format!("dep metric {} does not exist", id))
+                })?;
+            if dep.kind != MetricKind::Primitive {
+                return Err(Error::validation(format!(
+                    "dep metric {} is not a primitive metric",
+                    id
+                )));
+            }
+        }
+
+        // 4. arg_map
+        let arg_map = parse_arg_map(&req.arg_map)?;
+        let expected: BTreeSet<&str> = calc.args.iter().copied().collect();
+        let actual: BTreeSet<&str> = arg_map.keys().map(String::as_str).collect();
+        if expected != actual {
+            return Err(Error::validation(format!(
+                "arg_map must bind exactly the arguments of '{}': {}",
+                calc.key,
+                calc.args.join(", ")
+            )));
+        }
+        if let Some((name, id)) = arg_map.iter().find(|(_, id)| !dep_set.contains(&id.0)) {
+            return Err(Error::validation(format!(
+                "arg_map.{} references metric {} which is not in deps",
+                name, id.0
+            )));
+        }
+        let bound: BTreeSet<i64> = arg_map.values().map(|id| id.0).collect();
+        if let Some(unused) = dep_set.difference(&bound).next() {
+            return Err(Error::validation(format!(
+                "dep metric {} is not bound in arg_map",
+                unused
+            )));
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,125 @@
+use pg_tables::{
+    PaginatedResponse,
+    pg_core::DbContext,
+    table::{
+        dto::PaginationInput,
+        subject::{dto::SubjectId, service::SubjectService},
+        subject_access::{dto::AccessLevel, service::SubjectAccessService},
+        subject_profile::service::SubjectProfileService,
+    },
+};
+
+use time::OffsetDateTime;
+
+use crate::{
+    Error, Result,
+    api::access::authorize,
+    demographics::Demographics,
+    dto::{
+        access::Actor,
+        subject::{
+            CreateSubjectRequest, ListSubjectsRequest, SaveSubjectProfileRequest,
+            SubjectProfileResponse, SubjectResponse,
+        },
+    },
+};
+
+pub struct SubjectApi {
+    subject: SubjectService,
+    profile: SubjectProfileService,
+    access: SubjectAccessService,
+}
+
+impl SubjectApi {
+    pub fn new(db: DbContext) -> Self {
+        Self {
+            subject: SubjectService::new(db.clone()),
+            profile: SubjectProfileService::new(db.clone()),
+            access: SubjectAccessService::new(db),
+        }
+    }
+
+    /// 创建 Subject，创建者自动成为 owner（同一事务内完成）
+    pub async fn create(
+        &self,
+        actor: &Actor,
+        req: CreateSubjectRequest,
+    ) -> Result<SubjectResponse> {
+        self.subject.create_with_owner(req, actor.user_id).await
+    }
+
+    /// 获取 Subject（需要读权限）
+    pub async fn get(&self, actor: &Actor, id: SubjectId) -> Result<SubjectResponse> {
+        let subject = self
+            .subject
+            .get(id)
+            .await?
+            .ok_or_else(|| Error::not_found("subject", id.0))?;
+        authorize(&self.access, actor, id, AccessLevel::Read).await?;
+        Ok(subject)
+    }
+
+    /// 分页查询 actor 可见的 Subject（管理员可见全部）
+    pub async fn list(
+        &self,
+        actor: &Actor,
+        req: ListSubjectsRequest,
+        pagination: Option<PaginationInput>,
+    ) -> Result<PaginatedResponse<SubjectResponse>> {
+        let ids = if actor.is_admin {
+            None
+        } else {
+            let grants = self.access.list_by_user(actor.user_id).await?;
+            Some(grants.into_iter().map(|g| g.subject_id).collect())
+        };
+
+        self.subject.list(req.into_internal(ids), pagination).await
+    }
+
+    /// 获取 Subject 的人口学信息（需要读权限，未填写时返回 None）
+    pub async fn get_profile(
+        &self,
+        actor: &Actor,
+        id: SubjectId,
+    ) -> Result<Option<SubjectProfileResponse>> {
+        self.subject
+            .get(id)
+            .await?
+            .ok_or_else(|| Error::not_found("subject", id.0))?;
+        authorize(&self.access, actor, id, AccessLevel::Read).await?;
+        self.profile.get(id).await
+    }
+
+    /// 当前周岁年龄（按 subject 所在时区的日期，未填写出生日期时为 None）
+    pub async fn age(&self, profile: &SubjectProfileResponse) -> Result<Option<u32>> {
+        let demographics = Demographics::resolve(&self.profile, Some(profile)).await?;
+        Ok(demographics.age_at(OffsetDateTime::now_utc()))
+    }
+
+    /// 保存 Subject 的人口学信息（需要写权限，整体替换）
+    pub async fn save_profile(
+        &self,
+        actor: &Actor,
+        req: SaveSubjectProfileRequest,
+    ) -> Result<SubjectProfileResponse> {
+        let id = req.subject_id;
+        self.subject
+            .get(id)
+            .await?
+            .ok_or_else(|| Error::not_found("subject", id.0))?;
+        authorize(&self.access, actor, id, AccessLevel::Write).await?;
+        self.profile.save(req).await
+    }
+
+    /// 停用 Subject（仅 owner / 管理员）
+    ///
+    /// 历史数据与授权保留，停用后不再接受新的观测数据
+    pub async fn deactivate(&self, actor: &Actor, id: SubjectId) -> Result<SubjectResponse> {
+        authorize(&self.access, actor, id, AccessLevel::Manage).await?;
+
+        self.subject
+            .deactivate(id)
+            .await?
+            .ok_or_else(|| Error::not_found("subject", id.0))
+    }
+}
@@ -1,31 +1,83 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use pg_tables::table::metric::dto::MetricId;
 use serde_json::Value;
 
 use crate::{Error, Result};
 
-type CalcFn = fn(inputs: &HashMap<MetricId, f64>) -> Result<f64>;
+/// 计算函数的具名参数（参数名 -> 数值）
+pub type CalcArgs = HashMap<String, f64>;
 
-pub fn get_calc(calc_key: &str) -> Option<CalcFn> {
-    match calc_key {
-        "tyg_v1" => Some(calc_tyg_v1),
-        _ => None,
-    }
+type CalcFn = fn(args: &CalcArgs) -> Result<f64>;
+
+/// 已注册的计算
+///
+/// recipe 通过 `calc_key` 引用计算，通过 `arg_map`（参数名 -> metric_id）绑定参数
+pub struct CalcDef {
+    pub key: &'static str,
+
+    /// 必需的参数名，与 recipe.arg_map 的 key 一一对应
+    pub args: &'static [&'static str],
+
+    pub func: CalcFn,
+}
+
+const CALCS: &[CalcDef] = &[CalcDef {
+    key: "tyg_v1",
+    args: &["TG", "GLU"],
+    func: calc_tyg_v1,
+}];
+
+pub fn get_calc(calc_key: &str) -> Option<&'static CalcDef> {
+    CALCS.iter().find(|c| c.key == calc_key)
+}
+
+/// 全部已注册的计算（给管理端选择 calc_key 用）
+pub fn list_calcs() -> &'static [CalcDef] {
+    CALCS
 }
 
-fn calc_tyg_v1(inputs: &HashMap<MetricId, f64>) -> Result<f64> {
-    let tg = inputs
-        .get(&MetricId(16))
-        .ok_or(Error::internal("missing TG"))?;
-    let fpg = inputs
-        .get(&MetricId(18))
-        .ok_or(Error::internal("missing FPG"))?;
+fn calc_tyg_v1(args: &CalcArgs) -> Result<f64> {
+    let tg = args.get("TG").ok_or(Error::internal("missing TG"))?;
+    let fpg = args.get("GLU").ok_or(Error::internal("missing GLU"))?;
 
     // 占位实现，后面再换正式公式
     Ok((tg * fpg).ln())
 }
 
+/// 解析 recipe.arg_map：`{"TG": 16, "GLU": 18}`
+pub fn parse_arg_map(arg_map: &Value) -> Result<BTreeMap<String, MetricId>> {
+    let obj = arg_map
+        .as_object()
+        .ok_or(Error::validation("arg_map must be a JSON object"))?;
+
+    let mut map = BTreeMap::new();
+    for (name, v) in obj {
+        let metric_id = v
+            .as_i64()
+            .ok_or_else(|| Error::validation(format!("arg_map.{} must be a metric id", name)))?;
+        map.insert(name.clone(), MetricId(metric_id));
+    }
+
+    Ok(map)
+}
+
+/// 按 arg_map 把同一时刻的依赖值绑定为具名参数
+pub fn bind_args(
+    arg_map: &BTreeMap<String, MetricId>,
+    inputs: &HashMap<MetricId, f64>,
+) -> Result<CalcArgs> {
+    arg_map
+        .iter()
+        .map(|(name, metric_id)| {
+            inputs
+                .get(metric_id)
+                .map(|v| (name.clone(), *v))
+                .ok_or_else(|| Error::internal(format!("missing input for {}", name)))
+        })
+        .collect()
+}
+
 pub fn parse_inputs(inputs: &Value) -> Result<HashMap<MetricId, f64>> {
     let obj = inputs
         .as_object()
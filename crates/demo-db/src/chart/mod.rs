@@ -0,0 +1,115 @@
+//! 图表展示相关的数据处理
+
+use pg_tables::table::observation::dto::ObservationPoint;
+
+/// 折线图降采样的最少点数（少于 3 个点时 LTTB 没有意义）
+pub const MIN_CHART_POINTS: usize = 3;
+
+/// Largest-Triangle-Three-Buckets 降采样
+///
+/// 保留首尾两个点，其余点均分为 `threshold - 2` 个桶，每个桶选出与
+/// 前一个已选点、下一个桶平均点构成三角形面积最大的点。
+/// 与简单平均相比能保留峰值和趋势，适合折线图。
+///
+/// - 输入需按 observed_at 升序；点数不超过 `threshold` 时原样返回（不做任何过滤）
+/// - 需要降采样时只保留有数值的点，没有数值的点无法绘制，直接丢弃
+pub fn lttb(points: Vec<ObservationPoint>, threshold: usize) -> Vec<ObservationPoint> {
+    let threshold = threshold.max(MIN_CHART_POINTS);
+    if points.len() <= threshold {
+        return points;
+    }
+
+    let points: Vec<(f64, f64, ObservationPoint)> = points
+        .into_iter()
+        .filter_map(|p| {
+            let y = p.value_num?;
+            let x = p.observed_at.unix_timestamp_nanos() as f64 / 1e9;
+            Some((x, y, p))
+        })
+        .collect();
+
+    if points.len() <= threshold {
+        return points.into_iter().map(|(_, _, p)| p).collect();
+    }
+
+    let len = points.len();
+    let bucket_size = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut selected = Vec::with_capacity(threshold);
+    selected.push(0);
+
+    let mut a = 0;
+    for i in 0..threshold - 2 {
+        // 当前桶 [start, end)
+        let start = (i as f64 * bucket_size) as usize + 1;
+        let end = (((i + 1) as f64 * bucket_size) as usize + 1).min(len - 1);
+
+        // 下一个桶的平均点（最后一个桶用终点）
+        let next_start = end;
+        let next_end = (((i + 2) as f64 * bucket_size) as usize + 1).min(len);
+        let next = &points[next_start..next_end.max(next_start + 1)];
+        let avg_x = next.iter().map(|p| p.0).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|p| p.1).sum::<f64>() / next.len() as f64;
+
+        let (ax, ay) = (points[a].0, points[a].1);
+        let mut max_area = -1.0;
+        let mut max_index = start;
+        for (j, p) in points.iter().enumerate().take(end).skip(start) {
+            let area = ((ax - avg_x) * (p.1 - ay) - (ax - p.0) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                max_index = j;
+            }
+        }
+
+        selected.push(max_index);
+        a = max_index;
+    }
+
+    selected.push(len - 1);
+
+    let mut points: Vec<Option<ObservationPoint>> =
+        points.into_iter().map(|(_, _, p)| Some(p)).collect();
+    selected
+        .into_iter()
+        .filter_map(|i| points[i].take())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pg_tables::table::observation::dto::ObservationValue;
+    use time::OffsetDateTime;
+
+    use super::*;
+
+    fn point(secs: i64, value: Option<f64>) -> ObservationPoint {
+        ObservationPoint {
+            value: ObservationValue(value.map(|v| v.to_string()).unwrap_or_default()),
+            value_num: value,
+            observed_at: OffsetDateTime::from_unix_timestamp(secs).unwrap(),
+            flag: None,
+        }
+    }
+
+    #[test]
+    fn test_lttb_keeps_short_series_unchanged() {
+        let points = vec![point(0, Some(1.0)), point(60, None), point(120, Some(3.0))];
+
+        assert_eq!(lttb(points.clone(), 3), points);
+        assert_eq!(lttb(points.clone(), 10), points);
+    }
+
+    #[test]
+    fn test_lttb_downsamples_to_threshold() {
+        let points: Vec<_> = (0..100)
+            .map(|i| point(i * 60, Some(if i == 42 { 100.0 } else { 1.0 })))
+            .collect();
+
+        let sampled = lttb(points.clone(), 10);
+        assert_eq!(sampled.len(), 10);
+        assert_eq!(sampled.first(), points.first());
+        assert_eq!(sampled.last(), points.last());
+        // 峰值必须保留
+        assert!(sampled.iter().any(|p| p.value_num == Some(100.0)));
+    }
+}
@@ -0,0 +1,133 @@
+//! 人口学信息相关的计算
+//!
+//! 参考范围、eGFR 等公式依赖性别与“观测当时”的年龄，
+//! 这里统一从 SubjectProfile 推导，避免各处自行计算
+
+use pg_tables::table::{
+    metric_reference_range::dto::Sex,
+    subject::dto::SubjectId,
+    subject_profile::{dto::SubjectProfile, service::SubjectProfileService},
+};
+use time::{Date, OffsetDateTime, UtcOffset};
+
+use crate::Result;
+
+/// 计算 `at` 时刻的周岁年龄（`at` 早于出生日期时为 None）
+///
+/// 按 `at` 自身偏移下的日期计算，调用方需先换算到 subject 所在的时区；
+/// 2 月 29 日出生的人在平年按 3 月 1 日过生日
+pub fn age_at(birth_date: Date, at: OffsetDateTime) -> Option<u32> {
+    let today = at.date();
+    if today < birth_date {
+        return None;
+    }
+
+    let mut years = today.year() - birth_date.year();
+    if (today.month() as u8, today.day()) < (birth_date.month() as u8, birth_date.day()) {
+        years -= 1;
+    }
+    u32::try_from(years).ok()
+}
+
+/// 计算所需的人口学信息（profile 缺失或未填写的字段为 None）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Demographics {
+    pub sex: Option<Sex>,
+    pub birth_date: Option<Date>,
+
+    /// subject 所在时区的当前 UTC 偏移（未设置时区时为 UTC）
+    pub offset: UtcOffset,
+}
+
+impl Demographics {
+    /// 读取 subject 的人口学信息
+    pub(crate) async fn load(
+        service: &SubjectProfileService,
+        subject_id: SubjectId,
+    ) -> Result<Self> {
+        let profile = service.get(subject_id).await?;
+        Self::resolve(service, profile.as_ref()).await
+    }
+
+    /// 从 profile 推导，时区换算为当前的 UTC 偏移
+    pub(crate) async fn resolve(
+        service: &SubjectProfileService,
+        profile: Option<&SubjectProfile>,
+    ) -> Result<Self> {
+        let offset = match profile.and_then(|p| p.time_zone.as_deref()) {
+            Some(time_zone) => service.utc_offset(time_zone).await?,
+            None => UtcOffset::UTC,
+        };
+        Ok(Self {
+            sex: profile.and_then(|p| p.sex),
+            birth_date: profile.and_then(|p| p.birth_date),
+            offset,
+        })
+    }
+
+    /// 观测时刻的年龄（按 subject 所在时区的日期）
+    pub fn age_at(&self, at: OffsetDateTime) -> Option<u32> {
+        self.birth_date
+            .and_then(|d| age_at(d, at.to_offset(self.offset)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{date, datetime, offset};
+
+    use super::*;
+
+    fn demographics(birth_date: Date, offset: UtcOffset) -> Demographics {
+        Demographics {
+            sex: None,
+            birth_date: Some(birth_date),
+            offset,
+        }
+    }
+
+    #[test]
+    fn test_age_at_birthday() {
+        let birth = date!(1990 - 06 - 15);
+        assert_eq!(age_at(birth, datetime!(2020-06-14 23:59 UTC)), Some(29));
+        assert_eq!(age_at(birth, datetime!(2020-06-15 00:00 UTC)), Some(30));
+        assert_eq!(age_at(birth, datetime!(2020-12-31 12:00 UTC)), Some(30));
+        assert_eq!(age_at(birth, datetime!(1990-06-15 00:00 UTC)), Some(0));
+    }
+
+    #[test]
+    fn test_age_at_feb_29() {
+        let birth = date!(2000 - 02 - 29);
+        // 平年按 3 月 1 日过生日
+        assert_eq!(age_at(birth, datetime!(2021-02-28 12:00 UTC)), Some(20));
+        assert_eq!(age_at(birth, datetime!(2021-03-01 00:00 UTC)), Some(21));
+        // 闰年当天即生日
+        assert_eq!(age_at(birth, datetime!(2024-02-28 12:00 UTC)), Some(23));
+        assert_eq!(age_at(birth, datetime!(2024-02-29 00:00 UTC)), Some(24));
+    }
+
+    #[test]
+    fn test_age_at_before_birth() {
+        let birth = date!(1990 - 06 - 15);
+        assert_eq!(age_at(birth, datetime!(1990-06-14 23:59 UTC)), None);
+        assert_eq!(age_at(birth, datetime!(1980-01-01 00:00 UTC)), None);
+    }
+
+    #[test]
+    fn test_age_at_uses_local_date() {
+        let birth = date!(1990 - 06 - 15);
+        // UTC 仍是 6 月 14 日，东八区已经是生日当天
+        let at = datetime!(2020-06-14 20:00 UTC);
+        assert_eq!(demographics(birth, UtcOffset::UTC).age_at(at), Some(29));
+        assert_eq!(demographics(birth, offset!(+8)).age_at(at), Some(30));
+
+        // UTC 已是生日当天，西五区还没到
+        let at = datetime!(2020-06-15 02:00 UTC);
+        assert_eq!(demographics(birth, offset!(-5)).age_at(at), Some(29));
+
+        // 按本地日期计算时，出生当天的本地凌晨不算早于出生
+        let at = datetime!(1990-06-14 18:00 UTC);
+        assert_eq!(demographics(birth, offset!(+8)).age_at(at), Some(0));
+        assert_eq!(demographics(birth, UtcOffset::UTC).age_at(at), None);
+    }
+}
@@ -0,0 +1,43 @@
+//! 访问控制相关 DTO
+
+use pg_tables::table::{
+    subject::dto::SubjectId,
+    subject_access::dto::{AccessRole, SubjectAccess},
+};
+
+/// 发起操作的用户（由 web 层从鉴权信息构造）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actor {
+    /// 鉴权层的用户标识
+    pub user_id: i64,
+
+    /// 管理员不受 Subject 授权限制
+    pub is_admin: bool,
+}
+
+impl Actor {
+    /// 普通用户
+    pub fn user(user_id: i64) -> Self {
+        Self {
+            user_id,
+            is_admin: false,
+        }
+    }
+
+    /// 管理员
+    pub fn admin(user_id: i64) -> Self {
+        Self {
+            user_id,
+            is_admin: true,
+        }
+    }
+}
+
+/// 授予 Subject 访问权限
+pub struct GrantAccessRequest {
+    pub subject_id: SubjectId,
+    pub user_id: i64,
+    pub role: AccessRole,
+}
+
+pub type SubjectAccessResponse = SubjectAccess;
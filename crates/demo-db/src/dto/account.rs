@@ -0,0 +1,17 @@
+//! 账号 / 登录相关 DTO
+
+use pg_tables::table::account::dto::Account;
+
+/// 注册本地账号（同时创建账号本人的 Subject）
+pub struct RegisterAccountRequest {
+    pub username: String,
+    pub password: String,
+}
+
+/// 账号密码登录
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+pub type AccountResponse = Account;
@@ -0,0 +1,7 @@
+//! 数据来源管理相关 DTO
+
+use pg_tables::table::data_source::dto::{DataSource, UpdateDataSource, VoidDataSourceResult};
+
+pub type UpdateDataSourceRequest = UpdateDataSource;
+pub type DataSourceResponse = DataSource;
+pub type VoidDataSourceResponse = VoidDataSourceResult;
@@ -0,0 +1,4 @@
+pub type EnqueueJobRequest = pg_tables::table::job::dto::EnqueueJob;
+pub type ClaimJobRequest = pg_tables::table::job::dto::ClaimJob;
+pub type JobResponse = pg_tables::table::job::dto::Job;
+pub type ListJobsRequest = pg_tables::table::job::dto::ListJobs;
@@ -6,13 +6,17 @@
 //! - 不引入额外抽象
 
 use pg_tables::table::{
-    data_source::dto::{CreateDataSource, DataSourceId},
-    metric::dto::{MetricId, MetricSummary},
-    observation::dto::{ObservationId, ObservationPoint, ObservationValue},
+    data_source::dto::{CreateDataSource, DataSource, DataSourceId, ResolveDataSource},
+    metric::dto::{MetricId, MetricSummary, MetricVisualization},
+    observation::dto::{
+        ObservationBucket, ObservationId, ObservationPoint, ObservationStats, ObservationValue,
+    },
     subject::dto::SubjectId,
 };
 use time::OffsetDateTime;
 
+use crate::chart::lttb;
+
 /// =========================
 /// 业务输入 DTO
 /// =========================
@@ -28,8 +32,11 @@ pub struct RecordObservationRequest {
     /// 观测发生的时间
     pub observed_at: OffsetDateTime,
 
-    /// 数据来源（设备 / 手工 / 第三方）
-    pub source: Option<String>,
+    /// 值的单位（None 表示已是指标的规范单位，否则写入前换算）
+    pub unit: Option<String>,
+
+    /// 数据来源（设备 / 手工 / 第三方），按 (kind, name) 复用已有的共享来源
+    pub source: Option<ResolveDataSource>,
 }
 
 /// 记录一次健康观测（带 source 创建）
@@ -38,7 +45,25 @@ pub struct RecordObservationWithSourceRequest {
     pub metric_id: MetricId,
     pub value: ObservationValue,
     pub observed_at: OffsetDateTime,
+    pub unit: Option<String>,
+    pub source: CreateDataSource,
+}
+
+/// 记录一份报告：新建来源及其下的全部观测（同一观测时间）
+pub struct RecordReportRequest {
+    pub subject_id: SubjectId,
+    pub observed_at: OffsetDateTime,
     pub source: CreateDataSource,
+    pub values: Vec<ReportValue>,
+}
+
+/// 报告中的一项指标值
+pub struct ReportValue {
+    pub metric_id: MetricId,
+    pub value: ObservationValue,
+
+    /// 报告上的单位（None 表示已是指标的规范单位，否则写入前换算）
+    pub unit: Option<String>,
 }
 
 /// =========================
@@ -49,7 +74,30 @@ pub struct RecordObservationWithSourceRequest {
 #[derive(Debug, Clone)]
 pub struct RecordObservationResult {
     pub observation_id: ObservationId,
-    pub source_id: DataSourceId,
+
+    /// 实际关联的来源（未指定来源时为 None）
+    pub source_id: Option<DataSourceId>,
+}
+
+/// 记录报告的结果
+#[derive(Debug, Clone)]
+pub struct RecordReportResult {
+    pub source: DataSource,
+
+    /// 写入的观测条数
+    pub records_inserted: usize,
+
+    /// 单位无法换算而跳过的值
+    pub skipped: Vec<SkippedReportValue>,
+}
+
+/// 报告中因单位无法换算而未写入的值
+#[derive(Debug, Clone)]
+pub struct SkippedReportValue {
+    pub metric_id: MetricId,
+
+    /// 报告上的单位
+    pub unit: String,
 }
 
 /// 查询recipe依赖的观测数据
@@ -62,3 +110,40 @@ pub struct QueryObservationResponse {
     pub metric: MetricSummary,
     pub points: Vec<ObservationPoint>,
 }
+
+impl QueryObservationResponse {
+    /// 按 LTTB 把数据点降采样到最多 `max_points` 个
+    ///
+    /// 只对折线图生效：柱状图 / 列表 / 单值需要每一个原始值，保持不变
+    pub fn downsample(&mut self, max_points: usize) {
+        if self.metric.visualization != MetricVisualization::LineChart {
+            return;
+        }
+        let points = std::mem::take(&mut self.points);
+        self.points = lttb(points, max_points);
+    }
+}
+
+/// 按时间桶聚合后的观测数据
+pub struct AggregateObservationResponse {
+    pub metric: MetricSummary,
+    pub buckets: Vec<ObservationBucket>,
+}
+
+/// 区间内的描述性统计
+pub struct ObservationStatsResponse {
+    pub metric: MetricSummary,
+    pub stats: ObservationStats,
+}
+
+/// 某个 metric 的最新值
+pub struct LatestMetricValue {
+    pub metric: MetricSummary,
+    pub point: ObservationPoint,
+}
+
+/// subject 当前的指标快照（每个 metric 一条最新值）
+pub struct LatestSnapshotResponse {
+    pub subject_id: SubjectId,
+    pub items: Vec<LatestMetricValue>,
+}
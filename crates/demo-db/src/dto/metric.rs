@@ -0,0 +1,32 @@
+//! 指标管理相关 DTO
+
+use pg_tables::table::{
+    metric::dto::{CreateMetric, ListMetric, Metric, MetricId, UpdateMetric},
+    metric_reference_range::dto::{CreateReferenceRange, ReferenceRange},
+    metric_unit::dto::{MetricUnit, UnitConversion},
+};
+
+pub type CreateMetricRequest = CreateMetric;
+pub type ListMetricsRequest = ListMetric;
+pub type UpdateMetricRequest = UpdateMetric;
+pub type MetricResponse = Metric;
+pub type CreateReferenceRangeRequest = CreateReferenceRange;
+pub type ReferenceRangeResponse = ReferenceRange;
+pub type MetricUnitResponse = MetricUnit;
+
+/// 登记备选单位
+pub struct CreateMetricUnitRequest {
+    pub metric_id: MetricId,
+    pub unit: String,
+    pub formula: UnitFormula,
+}
+
+/// 备选单位到规范单位的换算方式
+pub enum UnitFormula {
+    /// 直接给出 `规范值 = 值 * factor + offset`
+    Affine(UnitConversion),
+
+    /// 按物质的摩尔质量（g/mol）在质量浓度与摩尔浓度之间换算，
+    /// 例如血糖 180.16、胆固醇 386.65
+    MolarMass(f64),
+}
@@ -1,3 +1,9 @@
+pub mod access;
+pub mod account;
 pub mod base;
+pub mod data_source;
+pub mod job;
 pub mod medical;
+pub mod metric;
 pub mod recipe;
+pub mod subject;
@@ -1,2 +1,4 @@
 pub type CreateRecipeRequest = pg_tables::table::recipe::dto::CreateRecipe;
 pub type RecipeResponse = pg_tables::table::recipe::dto::Recipe;
+pub type QueryRecipeRequest = pg_tables::table::recipe::dto::QueryRecipe;
+pub type PublishRecipeRequest = pg_tables::table::recipe::dto::PublishRecipe;
@@ -0,0 +1,30 @@
+//! Subject 管理相关 DTO
+
+use pg_tables::table::{
+    subject::dto::{CreateSubject, ListSubject, Subject, SubjectId, SubjectKind},
+    subject_profile::dto::{SaveSubjectProfile, SubjectProfile},
+};
+
+pub type CreateSubjectRequest = CreateSubject;
+pub type SubjectResponse = Subject;
+pub type SaveSubjectProfileRequest = SaveSubjectProfile;
+pub type SubjectProfileResponse = SubjectProfile;
+
+/// 查询 Subject（可见范围由 actor 的授权决定）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListSubjectsRequest {
+    pub kind: Option<SubjectKind>,
+
+    /// 是否包含已停用的 Subject
+    pub include_deactivated: bool,
+}
+
+impl ListSubjectsRequest {
+    pub(crate) fn into_internal(self, ids: Option<Vec<SubjectId>>) -> ListSubject {
+        ListSubject {
+            kind: self.kind,
+            ids,
+            include_deactivated: self.include_deactivated,
+        }
+    }
+}
@@ -1,12 +1,23 @@
 pub mod api;
 pub mod calc;
+pub mod chart;
+pub mod demographics;
 pub mod dto;
 
 pub use pg_tables::pg_core::{Error, Result};
 // Re-export types needed by web-server
 pub use pg_tables::table::{
-    data_source::dto::{CreateDataSource, DataSourceKind},
-    metric::dto::MetricId,
-    observation::dto::ObservationValue,
-    subject::dto::SubjectId,
+    account::dto::AccountId,
+    data_source::dto::{CreateDataSource, DataSourceId, DataSourceKind, ResolveDataSource},
+    dto::PaginationInput,
+    job::dto::{ClaimJob, EnqueueJob, Job, JobId, JobStatus, ListJobs},
+    metric::dto::{
+        MetricCode, MetricId, MetricKind, MetricStatus, MetricValueType, MetricVisualization,
+    },
+    metric_reference_range::dto::{ReferenceFlag, ReferenceRangeId, Sex},
+    metric_unit::dto::{MetricUnitId, UnitConversion},
+    observation::dto::{BucketSpec, BucketUnit, Observation, ObservationId, ObservationValue},
+    subject::dto::{SubjectId, SubjectKind},
+    subject_access::dto::{AccessLevel, AccessRole},
+    subject_profile::dto::SubjectProfile,
 };
@@ -2,6 +2,20 @@ pub use sea_orm_migration::prelude::*;
 
 mod m0001_phase_a_core;
 mod m0002_recipe;
+mod m0003_job;
+mod m0004_job_progress;
+mod m0005_subject_access;
+mod m0006_account;
+mod m0007_subject_deactivation;
+mod m0008_metric_display;
+mod m0009_recipe_version;
+mod m0010_data_source_void;
+mod m0011_data_source_shared;
+mod m0012_observation_fk;
+mod m0013_observation_typed_value;
+mod m0014_metric_reference_range;
+mod m0015_subject_profile;
+mod m0016_metric_unit;
 
 pub struct Migrator;
 
@@ -11,6 +25,20 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m0001_phase_a_core::Migration),
             Box::new(m0002_recipe::Migration),
+            Box::new(m0003_job::Migration),
+            Box::new(m0004_job_progress::Migration),
+            Box::new(m0005_subject_access::Migration),
+            Box::new(m0006_account::Migration),
+            Box::new(m0007_subject_deactivation::Migration),
+            Box::new(m0008_metric_display::Migration),
+            Box::new(m0009_recipe_version::Migration),
+            Box::new(m0010_data_source_void::Migration),
+            Box::new(m0011_data_source_shared::Migration),
+            Box::new(m0012_observation_fk::Migration),
+            Box::new(m0013_observation_typed_value::Migration),
+            Box::new(m0014_metric_reference_range::Migration),
+            Box::new(m0015_subject_profile::Migration),
+            Box::new(m0016_metric_unit::Migration),
         ]
     }
 }
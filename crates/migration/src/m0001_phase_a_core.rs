@@ -258,7 +258,7 @@ impl MigrationTrait for Migration {
 
 // Table identifier enums
 #[derive(DeriveIden)]
-enum Subject {
+pub enum Subject {
     Table,
     SubjectId,
     SubjectType,
@@ -280,7 +280,7 @@ pub enum Metric {
 }
 
 #[derive(DeriveIden)]
-enum DataSource {
+pub enum DataSource {
     Table,
     SourceId,
     SourceType,
@@ -290,7 +290,7 @@ enum DataSource {
 }
 
 #[derive(DeriveIden)]
-enum Observation {
+pub enum Observation {
     Table,
     ObservationId,
     SubjectId,
@@ -54,7 +54,7 @@ impl MigrationTrait for Migration {
 }
 
 #[derive(DeriveIden)]
-enum Recipe {
+pub enum Recipe {
     Table,
     RecipeId,
     MetricId,
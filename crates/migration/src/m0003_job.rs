@@ -0,0 +1,132 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Job::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Job::JobId)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Job::Queue)
+                            .string()
+                            .not_null()
+                            .comment("Queue name, e.g. 'markdown_ingest'"),
+                    )
+                    .col(ColumnDef::new(Job::Payload).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(Job::Status)
+                            .string()
+                            .not_null()
+                            .default("pending")
+                            .comment("Status: pending, running, succeeded, failed"),
+                    )
+                    .col(
+                        ColumnDef::new(Job::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(Job::MaxAttempts)
+                            .integer()
+                            .not_null()
+                            .default(3),
+                    )
+                    .col(
+                        ColumnDef::new(Job::RunAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                            .comment("Job becomes visible to workers at this time"),
+                    )
+                    .col(
+                        ColumnDef::new(Job::LockedBy)
+                            .string()
+                            .null()
+                            .comment("Worker currently holding the job"),
+                    )
+                    .col(
+                        ColumnDef::new(Job::LockedUntil)
+                            .timestamp_with_time_zone()
+                            .null()
+                            .comment("Visibility timeout; expired jobs can be claimed again"),
+                    )
+                    .col(ColumnDef::new(Job::Result).json_binary().null())
+                    .col(ColumnDef::new(Job::LastError).text().null())
+                    .col(
+                        ColumnDef::new(Job::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Job::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Job::FinishedAt)
+                            .timestamp_with_time_zone()
+                            .null()
+                            .comment("Set when the job succeeded or failed permanently"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Claim query: WHERE queue = ? AND status = ? ORDER BY run_at
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_job_queue_status_run_at")
+                    .table(Job::Table)
+                    .col(Job::Queue)
+                    .col(Job::Status)
+                    .col(Job::RunAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Job::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Job {
+    Table,
+    JobId,
+    Queue,
+    Payload,
+    Status,
+    Attempts,
+    MaxAttempts,
+    RunAt,
+    LockedBy,
+    LockedUntil,
+    Result,
+    LastError,
+    CreatedAt,
+    UpdatedAt,
+    FinishedAt,
+}
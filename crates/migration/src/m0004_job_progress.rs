@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0003_job::Job;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Job::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(JobProgress::SubjectId)
+                            .big_integer()
+                            .null()
+                            .comment("Subject the job works on, used for listing"),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(JobProgress::Progress)
+                            .json_binary()
+                            .null()
+                            .comment("Progress reported by the worker while running"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Task listing: WHERE subject_id = ? ORDER BY job_id DESC
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_job_subject_id")
+                    .table(Job::Table)
+                    .col(JobProgress::SubjectId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_job_subject_id")
+                    .table(Job::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Job::Table)
+                    .drop_column(JobProgress::Progress)
+                    .drop_column(JobProgress::SubjectId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum JobProgress {
+    SubjectId,
+    Progress,
+}
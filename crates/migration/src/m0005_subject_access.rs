@@ -0,0 +1,107 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::Subject;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SubjectAccess::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SubjectAccess::AccessId)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectAccess::SubjectId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectAccess::UserId)
+                            .big_integer()
+                            .not_null()
+                            .comment("Authenticated user id (JWT subject)"),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectAccess::Role)
+                            .string()
+                            .not_null()
+                            .comment("Role: owner, caregiver, clinician, read_only"),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectAccess::GrantedBy)
+                            .big_integer()
+                            .null()
+                            .comment("User who granted the access"),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectAccess::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SubjectAccess::Table, SubjectAccess::SubjectId)
+                            .to(Subject::Table, Subject::SubjectId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One grant per (subject, user); also serves the access check lookup
+        manager
+            .create_index(
+                Index::create()
+                    .name("uq_subject_access_subject_user")
+                    .table(SubjectAccess::Table)
+                    .col(SubjectAccess::SubjectId)
+                    .col(SubjectAccess::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Listing subjects a user can access
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_subject_access_user_id")
+                    .table(SubjectAccess::Table)
+                    .col(SubjectAccess::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SubjectAccess::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SubjectAccess {
+    Table,
+    AccessId,
+    SubjectId,
+    UserId,
+    Role,
+    GrantedBy,
+    CreatedAt,
+}
@@ -0,0 +1,148 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::Subject;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Account::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Account::AccountId)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Account::Username)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Account::PasswordHash)
+                            .string()
+                            .not_null()
+                            .comment("Argon2 PHC string"),
+                    )
+                    .col(
+                        ColumnDef::new(Account::SubjectId)
+                            .big_integer()
+                            .not_null()
+                            .comment("Subject representing the account holder"),
+                    )
+                    .col(
+                        ColumnDef::new(Account::Roles)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb"))
+                            .comment("Global roles, e.g. [\"admin\"]"),
+                    )
+                    .col(
+                        ColumnDef::new(Account::DisabledAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(Account::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Account::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Account::Table, Account::SubjectId)
+                            .to(Subject::Table, Subject::SubjectId)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RevokedToken::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RevokedToken::TokenHash)
+                            .string()
+                            .not_null()
+                            .primary_key()
+                            .comment("SHA-256 (hex) of the revoked JWT"),
+                    )
+                    .col(
+                        ColumnDef::new(RevokedToken::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .comment("Token expiry; the entry can be purged afterwards"),
+                    )
+                    .col(
+                        ColumnDef::new(RevokedToken::RevokedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_revoked_token_expires_at")
+                    .table(RevokedToken::Table)
+                    .col(RevokedToken::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RevokedToken::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Account::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Account {
+    Table,
+    AccountId,
+    Username,
+    PasswordHash,
+    SubjectId,
+    Roles,
+    DisabledAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum RevokedToken {
+    Table,
+    TokenHash,
+    ExpiresAt,
+    RevokedAt,
+}
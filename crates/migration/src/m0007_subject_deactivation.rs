@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::Subject;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Subjects are soft-deleted: observations keep pointing at them.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Subject::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SubjectDeactivation::DeactivatedAt)
+                            .timestamp_with_time_zone()
+                            .null()
+                            .comment("Set when the subject is deactivated; no more writes"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Subject::Table)
+                    .drop_column(SubjectDeactivation::DeactivatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SubjectDeactivation {
+    DeactivatedAt,
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::Metric;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Metric::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(MetricDisplay::Description)
+                            .text()
+                            .null()
+                            .comment("Human readable description shown to users"),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(MetricDisplay::DisplayPrecision)
+                            .small_integer()
+                            .null()
+                            .comment("Decimal places used when rendering numeric values"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Metric::Table)
+                    .drop_column(MetricDisplay::DisplayPrecision)
+                    .drop_column(MetricDisplay::Description)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum MetricDisplay {
+    Description,
+    DisplayPrecision,
+}
@@ -0,0 +1,106 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0002_recipe::Recipe;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Existing rows become consecutive versions per metric; each version stays
+/// effective until the next one was created, the first one has no lower bound.
+const BACKFILL_SQL: &str = r#"
+WITH ordered AS (
+    SELECT
+        recipe_id,
+        ROW_NUMBER() OVER (PARTITION BY metric_id ORDER BY recipe_id) AS version,
+        LEAD(created_at) OVER (PARTITION BY metric_id ORDER BY recipe_id) AS next_created_at
+    FROM recipe
+)
+UPDATE recipe r
+SET version = o.version,
+    effective_from = CASE WHEN o.version = 1 THEN NULL ELSE r.created_at END,
+    effective_to = o.next_created_at
+FROM ordered o
+WHERE r.recipe_id = o.recipe_id
+"#;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Recipe::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(RecipeVersion::Version)
+                            .integer()
+                            .not_null()
+                            .default(1)
+                            .comment("Version number per metric, starting at 1"),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(RecipeVersion::EffectiveFrom)
+                            .timestamp_with_time_zone()
+                            .null()
+                            .comment("Inclusive lower bound on observed_at; NULL = unbounded"),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(RecipeVersion::EffectiveTo)
+                            .timestamp_with_time_zone()
+                            .null()
+                            .comment("Exclusive upper bound on observed_at; NULL = current"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(BACKFILL_SQL)
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uq_recipe_metric_version")
+                    .table(Recipe::Table)
+                    .col(Recipe::MetricId)
+                    .col(RecipeVersion::Version)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("uq_recipe_metric_version")
+                    .table(Recipe::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Recipe::Table)
+                    .drop_column(RecipeVersion::EffectiveTo)
+                    .drop_column(RecipeVersion::EffectiveFrom)
+                    .drop_column(RecipeVersion::Version)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum RecipeVersion {
+    Version,
+    EffectiveFrom,
+    EffectiveTo,
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::{DataSource, Observation};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A voided source (e.g. a wrongly uploaded report) is kept for audit,
+        // its observations are retracted together with it.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DataSource::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SourceVoid::VoidedAt)
+                            .timestamp_with_time_zone()
+                            .null()
+                            .comment("Set when the source is voided"),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SourceVoid::VoidReason)
+                            .text()
+                            .null()
+                            .comment("Why the source was voided"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Observation::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SourceVoid::VoidedAt)
+                            .timestamp_with_time_zone()
+                            .null()
+                            .comment("Set when retracted; voided rows are excluded from queries"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_observation_source")
+                    .table(Observation::Table)
+                    .col(Observation::SourceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_observation_source")
+                    .table(Observation::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Observation::Table)
+                    .drop_column(SourceVoid::VoidedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DataSource::Table)
+                    .drop_column(SourceVoid::VoidReason)
+                    .drop_column(SourceVoid::VoidedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SourceVoid {
+    VoidedAt,
+    VoidReason,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::DataSource;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Shared sources (a device, manual entry, ...) are identified by kind + name
+/// and reused across observations. One-off sources such as uploaded reports
+/// keep `is_shared = false` and may repeat names. A voided shared source
+/// releases its name so the next write creates a fresh one.
+const CREATE_UNIQUE_SQL: &str = r#"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_data_source_shared_kind_name
+ON data_source (source_type, source_name)
+WHERE is_shared AND voided_at IS NULL
+"#;
+
+const DROP_UNIQUE_SQL: &str = "DROP INDEX IF EXISTS uq_data_source_shared_kind_name";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing rows were created one per observation / upload and may
+        // repeat names, so they all stay one-off.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DataSource::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SourceShared::IsShared)
+                            .boolean()
+                            .not_null()
+                            .default(false)
+                            .comment("Reused across observations; unique by kind + name"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(CREATE_UNIQUE_SQL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(DROP_UNIQUE_SQL)
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DataSource::Table)
+                    .drop_column(SourceShared::IsShared)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SourceShared {
+    IsShared,
+}
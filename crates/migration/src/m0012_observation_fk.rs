@@ -0,0 +1,93 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::{DataSource, Observation, Subject};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Observations whose subject no longer exists keep their history under a
+/// deactivated placeholder subject instead of being deleted.
+const BACKFILL_SUBJECTS_SQL: &str = r#"
+INSERT INTO subject (subject_id, subject_type, created_at, deactivated_at)
+SELECT DISTINCT o.subject_id, 'unknown', now(), now()
+FROM observation o
+LEFT JOIN subject s ON s.subject_id = o.subject_id
+WHERE s.subject_id IS NULL
+"#;
+
+/// Placeholder subjects were inserted with explicit ids. The next id is
+/// `MAX + 1` (`is_called = false`), i.e. 1 on an empty table.
+const RESET_SUBJECT_SEQ_SQL: &str = r#"
+SELECT setval(
+    pg_get_serial_sequence('subject', 'subject_id'),
+    COALESCE((SELECT MAX(subject_id) FROM subject), 0) + 1,
+    false
+)
+"#;
+
+/// Dangling source references (e.g. the old `DataSourceId(0)` placeholder)
+/// carry no information, drop them.
+const CLEAR_ORPHAN_SOURCES_SQL: &str = r#"
+UPDATE observation o
+SET source_id = NULL
+WHERE o.source_id IS NOT NULL
+  AND NOT EXISTS (SELECT 1 FROM data_source d WHERE d.source_id = o.source_id)
+"#;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(BACKFILL_SUBJECTS_SQL).await?;
+        db.execute_unprepared(RESET_SUBJECT_SEQ_SQL).await?;
+        db.execute_unprepared(CLEAR_ORPHAN_SOURCES_SQL).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_observation_subject")
+                    .from(Observation::Table, Observation::SubjectId)
+                    .to(Subject::Table, Subject::SubjectId)
+                    .on_delete(ForeignKeyAction::Restrict)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_observation_source")
+                    .from(Observation::Table, Observation::SourceId)
+                    .to(DataSource::Table, DataSource::SourceId)
+                    .on_delete(ForeignKeyAction::Restrict)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_observation_source")
+                    .table(Observation::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_observation_subject")
+                    .table(Observation::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
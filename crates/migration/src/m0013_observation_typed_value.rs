@@ -0,0 +1,186 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::Observation;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Older rows were written before values were normalised, so only values that
+/// parse cleanly are projected; anything else stays NULL.
+const BACKFILL_NUM_SQL: &str = r#"
+UPDATE observation o
+SET value_num = trim(o.value)::double precision
+FROM metric m
+WHERE m.metric_id = o.metric_id
+  AND m.value_type IN ('int', 'float', 'decimal')
+  AND trim(o.value) ~ '^[+-]?([0-9]+\.?[0-9]*|\.[0-9]+)([eE][+-]?[0-9]{1,2})?$'
+"#;
+
+/// Boolean wordings accepted at write time (`pg_tables::table::observation::value`).
+/// Copied rather than imported so the migration does not change with later code.
+const TRUE_WORDS: &[&str] = &[
+    "true",
+    "yes",
+    "y",
+    "1",
+    "positive",
+    "pos",
+    "+",
+    "(+)",
+    "（+）",
+    "阳性",
+    "是",
+    "有",
+    "弱阳性",
+    "强阳性",
+    "检出",
+    "异常",
+];
+const FALSE_WORDS: &[&str] = &[
+    "false",
+    "no",
+    "n",
+    "0",
+    "negative",
+    "neg",
+    "-",
+    "(-)",
+    "（-）",
+    "阴性",
+    "否",
+    "无",
+    "未检出",
+    "未发现",
+    "正常",
+];
+
+fn backfill_bool_sql() -> String {
+    let list = |words: &[&str]| {
+        words
+            .iter()
+            .map(|w| format!("'{}'", w.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    format!(
+        r#"
+UPDATE observation o
+SET value_bool = CASE
+    WHEN lower(trim(o.value)) IN ({}) THEN TRUE
+    WHEN lower(trim(o.value)) IN ({}) THEN FALSE
+END
+FROM metric m
+WHERE m.metric_id = o.metric_id
+  AND m.value_type = 'bool'
+"#,
+        list(TRUE_WORDS),
+        list(FALSE_WORDS)
+    )
+}
+
+/// Covers the per-series range scans; `value_num` is included so numeric
+/// aggregations can be answered from the index alone.
+const CREATE_SERIES_INDEX_SQL: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_observation_series
+ON observation (subject_id, metric_id, observed_at)
+INCLUDE (value_num)
+"#;
+
+const DROP_SERIES_INDEX_SQL: &str = "DROP INDEX IF EXISTS idx_observation_series";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Observation::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(TypedValue::ValueNum)
+                            .double()
+                            .null()
+                            .comment("Numeric projection of value for int/float/decimal metrics"),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(TypedValue::ValueBool)
+                            .boolean()
+                            .null()
+                            .comment("Boolean projection of value for bool metrics"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(BACKFILL_NUM_SQL).await?;
+        db.execute_unprepared(&backfill_bool_sql()).await?;
+        db.execute_unprepared(CREATE_SERIES_INDEX_SQL).await?;
+
+        // (subject_id, metric_id) is a prefix of the new index
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_observation_subject_metric")
+                    .table(Observation::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_observation_subject_metric")
+                    .table(Observation::Table)
+                    .col(Observation::SubjectId)
+                    .col(Observation::MetricId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(DROP_SERIES_INDEX_SQL)
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Observation::Table)
+                    .drop_column(TypedValue::ValueBool)
+                    .drop_column(TypedValue::ValueNum)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum TypedValue {
+    ValueNum,
+    ValueBool,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use pg_tables::table::observation::value::boolean_words;
+
+    use super::*;
+
+    #[test]
+    fn test_backfill_matches_write_time_words() {
+        for (value, words) in [(true, TRUE_WORDS), (false, FALSE_WORDS)] {
+            let backfill: BTreeSet<_> = words.iter().copied().collect();
+            let write_time: BTreeSet<_> = boolean_words(value).collect();
+            assert_eq!(backfill, write_time, "{value}");
+        }
+    }
+}
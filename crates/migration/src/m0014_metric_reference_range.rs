@@ -0,0 +1,137 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::Metric;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MetricReferenceRange::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::RangeId)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::MetricId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::Sex)
+                            .string()
+                            .null()
+                            .comment("male / female; NULL applies to both"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::AgeMin)
+                            .integer()
+                            .null()
+                            .comment("Lower age bound in years (inclusive)"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::AgeMax)
+                            .integer()
+                            .null()
+                            .comment("Upper age bound in years (exclusive)"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::Low)
+                            .double()
+                            .null()
+                            .comment("Lower limit of the normal range"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::High)
+                            .double()
+                            .null()
+                            .comment("Upper limit of the normal range"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::CriticalLow)
+                            .double()
+                            .null()
+                            .comment("Values at or below are critical"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::CriticalHigh)
+                            .double()
+                            .null()
+                            .comment("Values at or above are critical"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::Unit)
+                            .string()
+                            .null()
+                            .comment("Unit of the limits; NULL means the metric unit"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::Source)
+                            .string()
+                            .null()
+                            .comment("Where the range comes from (lab, guideline, ...)"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricReferenceRange::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_metric_reference_range_metric")
+                            .from(MetricReferenceRange::Table, MetricReferenceRange::MetricId)
+                            .to(Metric::Table, Metric::MetricId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Ranges are always loaded per metric
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_metric_reference_range_metric_id")
+                    .table(MetricReferenceRange::Table)
+                    .col(MetricReferenceRange::MetricId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MetricReferenceRange::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum MetricReferenceRange {
+    Table,
+    RangeId,
+    MetricId,
+    Sex,
+    AgeMin,
+    AgeMax,
+    Low,
+    High,
+    CriticalLow,
+    CriticalHigh,
+    Unit,
+    Source,
+    CreatedAt,
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::Subject;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Demographics live outside `subject`, which stays an identity anchor.
+        // At most one profile per subject, so the subject id is the primary key.
+        manager
+            .create_table(
+                Table::create()
+                    .table(SubjectProfile::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SubjectProfile::SubjectId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SubjectProfile::DisplayName).string().null())
+                    .col(ColumnDef::new(SubjectProfile::BirthDate).date().null())
+                    .col(
+                        ColumnDef::new(SubjectProfile::Sex)
+                            .string()
+                            .null()
+                            .comment("male / female"),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectProfile::HeightCm)
+                            .double()
+                            .null()
+                            .comment("Height in centimetres"),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectProfile::TimeZone)
+                            .string()
+                            .null()
+                            .comment("IANA time zone name, e.g. Asia/Shanghai"),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectProfile::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SubjectProfile::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_subject_profile_subject")
+                            .from(SubjectProfile::Table, SubjectProfile::SubjectId)
+                            .to(Subject::Table, Subject::SubjectId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SubjectProfile::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SubjectProfile {
+    Table,
+    SubjectId,
+    DisplayName,
+    BirthDate,
+    Sex,
+    HeightCm,
+    TimeZone,
+    CreatedAt,
+    UpdatedAt,
+}
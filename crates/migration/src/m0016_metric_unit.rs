@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m0001_phase_a_core::Metric;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `metric.unit` is the canonical unit values are stored in; each row
+        // here is an alternative unit with an affine conversion to it:
+        // canonical = value * factor + offset
+        manager
+            .create_table(
+                Table::create()
+                    .table(MetricUnit::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MetricUnit::UnitId)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MetricUnit::MetricId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MetricUnit::Unit)
+                            .string()
+                            .not_null()
+                            .comment("Alternative unit, e.g. mg/dL"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricUnit::Factor)
+                            .double()
+                            .not_null()
+                            .comment("Multiplier to the canonical unit"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricUnit::Offset)
+                            .double()
+                            .not_null()
+                            .default(0.0)
+                            .comment("Added after multiplying (affine units, e.g. temperature)"),
+                    )
+                    .col(
+                        ColumnDef::new(MetricUnit::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_metric_unit_metric")
+                            .from(MetricUnit::Table, MetricUnit::MetricId)
+                            .to(Metric::Table, Metric::MetricId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One conversion per (metric, unit); also serves the per-metric lookup
+        manager
+            .create_index(
+                Index::create()
+                    .name("uq_metric_unit_metric_unit")
+                    .table(MetricUnit::Table)
+                    .col(MetricUnit::MetricId)
+                    .col(MetricUnit::Unit)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MetricUnit::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum MetricUnit {
+    Table,
+    UnitId,
+    MetricId,
+    Unit,
+    Factor,
+    Offset,
+    CreatedAt,
+}
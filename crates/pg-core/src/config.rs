@@ -28,6 +28,10 @@ pub struct DatabaseConfig {
     /// Enable SQL query logging
     #[serde(default = "default_sql_logging")]
     pub sql_logging: bool,
+
+    /// Apply pending migrations on startup instead of refusing to start
+    #[serde(default = "default_auto_migrate")]
+    pub auto_migrate: bool,
 }
 
 fn default_max_connections() -> u32 {
@@ -50,6 +54,10 @@ fn default_sql_logging() -> bool {
     false
 }
 
+fn default_auto_migrate() -> bool {
+    false
+}
+
 impl DatabaseConfig {
     /// Create a new database configuration with default settings
     pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
@@ -61,6 +69,7 @@ impl DatabaseConfig {
             connect_timeout: default_connect_timeout(),
             idle_timeout: default_idle_timeout(),
             sql_logging: default_sql_logging(),
+            auto_migrate: default_auto_migrate(),
         }
     }
 
@@ -93,4 +102,10 @@ impl DatabaseConfig {
         self.sql_logging = enabled;
         self
     }
+
+    /// Enable applying pending migrations on startup
+    pub fn with_auto_migrate(mut self, enabled: bool) -> Self {
+        self.auto_migrate = enabled;
+        self
+    }
 }
@@ -3,7 +3,7 @@ pub mod database;
 pub mod entity;
 pub mod validation;
 
-use sea_orm::DbErr;
+use sea_orm::{DbErr, RuntimeErr, error::SqlxError};
 use thiserror::Error;
 
 use crate::error::{business::BusinessError, entity::EntityError};
@@ -49,6 +49,7 @@ impl Error {
     pub fn kind(&self) -> ErrorKind {
         match self {
             Error::Entity(EntityError::NotFound { .. }) => ErrorKind::NotFound,
+            Error::Entity(EntityError::AlreadyExists { .. }) => ErrorKind::Conflict,
             Error::Validation(_) => ErrorKind::Validation,
             Error::Business(BusinessError::PermissionDenied(_)) => ErrorKind::Permission,
             Error::Database(_) | Error::DatabaseOp(_) => ErrorKind::Database,
@@ -133,6 +134,20 @@ impl Error {
         matches!(self, Self::Database(_))
     }
 
+    /// Check if error is transient (connection loss, pool timeout) so retrying may succeed
+    ///
+    /// Constraint violations and other errors reported by PostgreSQL itself are
+    /// not transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Database(database::DatabaseError::Connection(_)) => true,
+            Self::Database(database::DatabaseError::Operation(e)) | Self::DatabaseOp(e) => {
+                is_transient_db_err(e)
+            }
+            _ => false,
+        }
+    }
+
     /// Get validation errors if this is a Validation variant
     pub fn get_validation_errors(&self) -> Option<&validator::ValidationErrors> {
         match self {
@@ -142,6 +157,19 @@ impl Error {
     }
 }
 
+fn is_transient_db_err(err: &DbErr) -> bool {
+    match err {
+        DbErr::ConnectionAcquire(_) | DbErr::Conn(_) => true,
+        DbErr::Exec(RuntimeErr::SqlxError(e)) | DbErr::Query(RuntimeErr::SqlxError(e)) => {
+            matches!(
+                e.as_ref(),
+                SqlxError::Io(_) | SqlxError::PoolTimedOut | SqlxError::PoolClosed
+            )
+        }
+        _ => false,
+    }
+}
+
 /// Backward compatibility alias
 
 /// Result type alias using PgError
@@ -167,6 +195,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transient_error() {
+        assert!(Error::db_connection("Connection refused").is_transient());
+        assert!(
+            Error::DatabaseOp(DbErr::ConnectionAcquire(sea_orm::ConnAcquireErr::Timeout))
+                .is_transient()
+        );
+        assert!(!Error::DatabaseOp(DbErr::RecordNotFound("job".into())).is_transient());
+        assert!(!Error::validation("Invalid email format").is_transient());
+    }
+
     #[test]
     fn test_validation_error() {
         let err = Error::validation("Invalid email format");
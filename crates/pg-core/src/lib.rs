@@ -1,7 +1,9 @@
 mod config;
 mod error;
 mod manager;
+mod migrate;
 
+pub mod lock;
 pub mod query;
 pub mod repository;
 
@@ -11,6 +13,7 @@ use std::sync::Arc;
 
 pub use config::DatabaseConfig;
 pub use error::{Error, ErrorKind, Result};
+pub use lock::{AdvisoryLockGuard, LeaderElection, TransactionLockGuard, advisory_key};
 pub use manager::DatabaseManager;
 pub use query::{OrderBy, PaginatedResponse, PaginationParams};
 pub use repository::{Repository, base::BaseRepository};
@@ -46,13 +49,15 @@ mod tests {
             .max_connections(20)
             .min_connections(5)
             .connect_timeout(60)
-            .with_sql_logging(true);
+            .with_sql_logging(true)
+            .with_auto_migrate(true);
 
         assert_eq!(config.name, "test");
         assert_eq!(config.max_connections, 20);
         assert_eq!(config.min_connections, 5);
         assert_eq!(config.connect_timeout, 60);
         assert_eq!(config.sql_logging, true);
+        assert_eq!(config.auto_migrate, true);
     }
 
     #[test]
@@ -64,5 +69,6 @@ mod tests {
         assert_eq!(config.connect_timeout, 30);
         assert_eq!(config.idle_timeout, 600);
         assert_eq!(config.sql_logging, false);
+        assert_eq!(config.auto_migrate, false);
     }
 }
@@ -0,0 +1,374 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
+use sea_orm::{
+    ConnectionTrait, DatabaseTransaction, DbBackend, DbErr, FromQueryResult, RuntimeErr, Statement,
+    TransactionTrait,
+    sqlx::{self, Postgres, pool::PoolConnection},
+};
+use tracing::{debug, warn};
+
+use crate::{DbContext, error::Result};
+
+/// Derive a stable advisory lock key from a name
+///
+/// Uses 64-bit FNV-1a so every instance (and every build) maps the same
+/// name to the same key, e.g. `advisory_key("report:42")`.
+pub fn advisory_key(name: &str) -> i64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    for byte in name.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash as i64
+}
+
+/// Acquire a session-scoped advisory lock without waiting
+///
+/// Returns `None` when another session already holds the key.
+/// The lock is held by a connection checked out of the pool for the
+/// lifetime of the guard; no transaction is kept open.
+pub async fn try_lock(ctx: &DbContext, key: i64) -> Result<Option<AdvisoryLockGuard>> {
+    let mut conn = acquire_connection(ctx).await?;
+
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+        .bind(key)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(query_err)?;
+    if !locked {
+        return Ok(None);
+    }
+
+    debug!("Acquired advisory lock: {}", key);
+    Ok(Some(AdvisoryLockGuard {
+        key,
+        conn: Some(conn),
+    }))
+}
+
+/// Acquire a session-scoped advisory lock, waiting until it is free
+pub async fn lock(ctx: &DbContext, key: i64) -> Result<AdvisoryLockGuard> {
+    let mut conn = acquire_connection(ctx).await?;
+
+    sqlx::query("SELECT pg_advisory_lock($1)")
+        .bind(key)
+        .execute(&mut *conn)
+        .await
+        .map_err(query_err)?;
+
+    debug!("Acquired advisory lock: {}", key);
+    Ok(AdvisoryLockGuard {
+        key,
+        conn: Some(conn),
+    })
+}
+
+/// Acquire a transaction-scoped advisory lock without waiting
+///
+/// The lock lives as long as the returned transaction; PostgreSQL releases
+/// it on commit or rollback.
+pub async fn try_xact_lock(ctx: &DbContext, key: i64) -> Result<Option<TransactionLockGuard>> {
+    let txn = ctx.inner().begin().await?;
+
+    if !query_bool(
+        &txn,
+        format!("SELECT pg_try_advisory_xact_lock({}) AS locked", key),
+    )
+    .await?
+    {
+        txn.rollback().await?;
+        return Ok(None);
+    }
+
+    Ok(Some(TransactionLockGuard { key, txn }))
+}
+
+/// Acquire a transaction-scoped advisory lock, waiting until it is free
+pub async fn xact_lock(ctx: &DbContext, key: i64) -> Result<TransactionLockGuard> {
+    let txn = ctx.inner().begin().await?;
+    acquire_xact_lock(&txn, key).await?;
+    Ok(TransactionLockGuard { key, txn })
+}
+
+/// Block on a transaction-scoped advisory lock within an existing transaction
+pub(crate) async fn acquire_xact_lock<C>(conn: &C, key: i64) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    conn.execute_unprepared(&format!("SELECT pg_advisory_xact_lock({})", key))
+        .await?;
+    Ok(())
+}
+
+/// RAII guard of a session-scoped advisory lock
+///
+/// Prefer calling [`AdvisoryLockGuard::release`]. A guard dropped without
+/// release closes its connection instead of returning it to the pool, so
+/// PostgreSQL ends the session and frees the lock.
+pub struct AdvisoryLockGuard {
+    key: i64,
+    conn: Option<PoolConnection<Postgres>>,
+}
+
+impl AdvisoryLockGuard {
+    /// The locked key
+    pub fn key(&self) -> i64 {
+        self.key
+    }
+
+    /// Check in `pg_locks` that this session still holds the lock
+    ///
+    /// Returns false when the connection is gone or the lock was lost.
+    pub async fn is_held(&mut self) -> bool {
+        let Some(conn) = self.conn.as_mut() else {
+            return false;
+        };
+
+        // A bigint advisory key is split into classid (high 32 bits) and
+        // objid (low 32 bits), with objsubid = 1
+        sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM pg_locks \
+             WHERE locktype = 'advisory' AND pid = pg_backend_pid() AND granted \
+             AND classid = $1::bigint::oid AND objid = $2::bigint::oid AND objsubid = 1)",
+        )
+        .bind(i64::from((self.key as u64 >> 32) as u32))
+        .bind(i64::from(self.key as u64 as u32))
+        .fetch_one(&mut **conn)
+        .await
+        .unwrap_or(false)
+    }
+
+    /// Release the lock and return the connection to the pool
+    pub async fn release(mut self) -> Result<()> {
+        let Some(mut conn) = self.conn.take() else {
+            return Ok(());
+        };
+
+        let unlocked = sqlx::query_scalar::<_, bool>("SELECT pg_advisory_unlock($1)")
+            .bind(self.key)
+            .fetch_one(&mut *conn)
+            .await;
+        match unlocked {
+            Ok(true) => {
+                debug!("Released advisory lock: {}", self.key);
+                Ok(())
+            }
+            Ok(false) => {
+                // Not held by this session any more; do not reuse the connection
+                conn.close_on_drop();
+                warn!("Advisory lock {} was not held on release", self.key);
+                Ok(())
+            }
+            Err(e) => {
+                conn.close_on_drop();
+                Err(query_err(e))
+            }
+        }
+    }
+}
+
+impl Drop for AdvisoryLockGuard {
+    fn drop(&mut self) {
+        if let Some(mut conn) = self.conn.take() {
+            conn.close_on_drop();
+        }
+    }
+}
+
+/// Guard of a transaction-scoped advisory lock
+pub struct TransactionLockGuard {
+    key: i64,
+    txn: DatabaseTransaction,
+}
+
+impl TransactionLockGuard {
+    /// The locked key
+    pub fn key(&self) -> i64 {
+        self.key
+    }
+
+    /// Transaction holding the lock (work done here is covered by the lock)
+    pub fn transaction(&self) -> &DatabaseTransaction {
+        &self.txn
+    }
+
+    /// Commit the transaction and release the lock
+    pub async fn commit(self) -> Result<()> {
+        self.txn.commit().await?;
+        Ok(())
+    }
+
+    /// Roll back the transaction and release the lock
+    pub async fn rollback(self) -> Result<()> {
+        self.txn.rollback().await?;
+        Ok(())
+    }
+}
+
+/// Leader election on top of an advisory lock
+///
+/// Every instance runs the same election; the one holding the lock is the
+/// leader until its guard is released or its connection dies.
+#[derive(Clone)]
+pub struct LeaderElection {
+    ctx: DbContext,
+    key: i64,
+    retry_interval: Duration,
+}
+
+impl LeaderElection {
+    /// Create an election for the given name (e.g. "job:cleanup")
+    pub fn new(ctx: DbContext, name: &str) -> Self {
+        Self {
+            ctx,
+            key: advisory_key(name),
+            retry_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Set how long to wait between attempts in [`LeaderElection::acquire`]
+    pub fn retry_interval(mut self, interval: Duration) -> Self {
+        self.retry_interval = interval;
+        self
+    }
+
+    /// Try to become leader once
+    pub async fn try_acquire(&self) -> Result<Option<AdvisoryLockGuard>> {
+        try_lock(&self.ctx, self.key).await
+    }
+
+    /// Wait until this instance becomes leader
+    pub async fn acquire(&self) -> Result<AdvisoryLockGuard> {
+        loop {
+            if let Some(guard) = self.try_acquire().await? {
+                return Ok(guard);
+            }
+            tokio::time::sleep(self.retry_interval).await;
+        }
+    }
+
+    /// Run `job` only if this instance wins the election
+    ///
+    /// Returns `None` without running the job when another instance is leader.
+    pub async fn run_if_leader<F, Fut, T>(&self, job: F) -> Result<Option<T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let Some(guard) = self.try_acquire().await? else {
+            return Ok(None);
+        };
+
+        let output = job().await;
+        guard.release().await?;
+        Ok(Some(output))
+    }
+}
+
+async fn acquire_connection(ctx: &DbContext) -> Result<PoolConnection<Postgres>> {
+    ctx.inner()
+        .get_postgres_connection_pool()
+        .acquire()
+        .await
+        .map_err(|e| DbErr::Conn(RuntimeErr::SqlxError(Arc::new(e))).into())
+}
+
+fn query_err(e: sqlx::Error) -> crate::Error {
+    DbErr::Query(RuntimeErr::SqlxError(Arc::new(e))).into()
+}
+
+async fn query_bool<C>(conn: &C, sql: String) -> Result<bool>
+where
+    C: ConnectionTrait,
+{
+    #[derive(Debug, FromQueryResult)]
+    struct LockRow {
+        locked: bool,
+    }
+
+    let row = LockRow::find_by_statement(Statement::from_string(DbBackend::Postgres, sql))
+        .one(conn)
+        .await?;
+    Ok(row.map(|r| r.locked).unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advisory_key_is_stable() {
+        assert_eq!(advisory_key(""), 0xcbf2_9ce4_8422_2325_u64 as i64);
+        assert_eq!(advisory_key("a"), 0xaf63_dc4c_8601_ec8c_u64 as i64);
+        assert_eq!(advisory_key("report:42"), advisory_key("report:42"));
+    }
+
+    #[test]
+    fn test_advisory_key_differs_by_name() {
+        assert_ne!(advisory_key("report:42"), advisory_key("report:43"));
+    }
+
+    /// Connect to `DATABASE_URL`; the DB-backed tests are skipped when it is unset
+    async fn test_ctx() -> Option<DbContext> {
+        let url = std::env::var("DATABASE_URL").ok()?;
+        let db = sea_orm::Database::connect(url)
+            .await
+            .expect("failed to connect to DATABASE_URL");
+        Some(DbContext::new(Arc::new(db)))
+    }
+
+    #[tokio::test]
+    async fn test_try_lock_contention_and_release() {
+        let Some(ctx) = test_ctx().await else {
+            return;
+        };
+        let key = advisory_key("pg-core-test:contention");
+
+        let mut guard = try_lock(&ctx, key).await.unwrap().expect("lock is free");
+        assert!(guard.is_held().await);
+        assert!(try_lock(&ctx, key).await.unwrap().is_none());
+
+        guard.release().await.unwrap();
+        let guard = try_lock(&ctx, key)
+            .await
+            .unwrap()
+            .expect("lock was released");
+        guard.release().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dropped_guard_frees_lock() {
+        let Some(ctx) = test_ctx().await else {
+            return;
+        };
+        let key = advisory_key("pg-core-test:drop");
+
+        let guard = try_lock(&ctx, key).await.unwrap().expect("lock is free");
+        drop(guard);
+
+        // The connection is closed in the background; wait for the session to end
+        let guard = tokio::time::timeout(Duration::from_secs(5), lock(&ctx, key))
+            .await
+            .expect("lock was not freed after drop")
+            .unwrap();
+        guard.release().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_leader_election_runs_on_one_instance() {
+        let Some(ctx) = test_ctx().await else {
+            return;
+        };
+        let first = LeaderElection::new(ctx.clone(), "pg-core-test:leader");
+        let second = LeaderElection::new(ctx, "pg-core-test:leader");
+
+        let leader = first.try_acquire().await.unwrap().expect("no leader yet");
+        assert!(second.run_if_leader(|| async {}).await.unwrap().is_none());
+
+        leader.release().await.unwrap();
+        assert_eq!(second.run_if_leader(|| async { 1 }).await.unwrap(), Some(1));
+    }
+}
@@ -1,17 +1,20 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm_migration::MigratorTrait;
 use tracing::{debug, info};
 
 use crate::{
     DbContext,
     config::DatabaseConfig,
     error::{Error, Result},
+    migrate,
 };
 
 /// Multi-database connection manager
 pub struct DatabaseManager {
     connections: HashMap<String, DbContext>,
+    auto_migrate: HashMap<String, bool>,
 }
 
 impl DatabaseManager {
@@ -24,6 +27,7 @@ impl DatabaseManager {
         }
 
         let mut connections = HashMap::new();
+        let mut auto_migrate = HashMap::new();
 
         for config in configs {
             info!("Connecting to database: {}", config.name);
@@ -42,10 +46,30 @@ impl DatabaseManager {
             let ctx = conn_to_context(db);
 
             debug!("Successfully connected to database: {}", config.name);
+            auto_migrate.insert(config.name.clone(), config.auto_migrate);
             connections.insert(config.name.clone(), ctx);
         }
 
-        Ok(Self { connections })
+        Ok(Self {
+            connections,
+            auto_migrate,
+        })
+    }
+
+    /// Apply or verify migrations on every database
+    ///
+    /// Databases with `auto_migrate` enabled get pending migrations applied;
+    /// the others return a config error when their schema is behind.
+    pub async fn run_migrations<M>(&self) -> Result<()>
+    where
+        M: MigratorTrait,
+    {
+        for (name, ctx) in &self.connections {
+            let auto_migrate = self.auto_migrate.get(name).copied().unwrap_or(false);
+            migrate::sync_schema::<M>(name, ctx.inner(), auto_migrate).await?;
+        }
+
+        Ok(())
     }
 
     /// Get a database connection by name
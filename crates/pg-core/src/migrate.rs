@@ -0,0 +1,66 @@
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use sea_orm_migration::MigratorTrait;
+use tracing::{info, warn};
+
+use crate::{
+    error::{Error, Result},
+    lock::{acquire_xact_lock, advisory_key},
+};
+
+/// Advisory lock name shared by every instance running migrations
+const MIGRATION_LOCK_NAME: &str = "pg-core:migrations";
+
+/// Bring the schema of one database up to date, or refuse when it is behind
+///
+/// Runs inside a single transaction holding a transaction-scoped advisory lock,
+/// so instances starting at the same time apply migrations one after another.
+/// The lock is released when the transaction commits or rolls back.
+pub(crate) async fn sync_schema<M>(
+    name: &str,
+    db: &DatabaseConnection,
+    auto_migrate: bool,
+) -> Result<()>
+where
+    M: MigratorTrait,
+{
+    let txn = db.begin().await?;
+
+    acquire_xact_lock(&txn, advisory_key(MIGRATION_LOCK_NAME)).await?;
+
+    let pending = M::get_pending_migrations(&txn).await?;
+    if pending.is_empty() {
+        txn.commit().await?;
+        info!("Database schema is up to date: {}", name);
+        return Ok(());
+    }
+
+    let names = pending
+        .iter()
+        .map(|m| m.name().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if !auto_migrate {
+        txn.rollback().await?;
+        warn!("Database schema is behind for {}: {}", name, names);
+        return Err(Error::config(format!(
+            "Database '{}' has {} pending migration(s): {}. Enable auto_migrate or run the \
+             migration CLI",
+            name,
+            pending.len(),
+            names
+        )));
+    }
+
+    info!(
+        "Applying {} pending migration(s) to {}: {}",
+        pending.len(),
+        name,
+        names
+    );
+    M::up(&txn, None).await?;
+    txn.commit().await?;
+    info!("Database schema migrated: {}", name);
+
+    Ok(())
+}
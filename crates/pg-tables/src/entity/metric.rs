@@ -17,16 +17,35 @@ pub struct Model {
     pub visualization: String,
     pub status: String,
     pub created_at: TimeDateTimeWithTimeZone,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub display_precision: Option<i16>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::metric_reference_range::Entity")]
+    MetricReferenceRange,
+    #[sea_orm(has_many = "super::metric_unit::Entity")]
+    MetricUnit,
     #[sea_orm(has_many = "super::observation::Entity")]
     Observation,
     #[sea_orm(has_one = "super::recipe::Entity", has_many = "super::recipe::Entity")]
     Recipe,
 }
 
+impl Related<super::metric_reference_range::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MetricReferenceRange.def()
+    }
+}
+
+impl Related<super::metric_unit::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MetricUnit.def()
+    }
+}
+
 impl Related<super::observation::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Observation.def()
@@ -2,8 +2,15 @@
 
 pub mod prelude;
 
+pub mod account;
 pub mod data_source;
+pub mod job;
 pub mod metric;
+pub mod metric_reference_range;
+pub mod metric_unit;
 pub mod observation;
 pub mod recipe;
+pub mod revoked_token;
 pub mod subject;
+pub mod subject_access;
+pub mod subject_profile;
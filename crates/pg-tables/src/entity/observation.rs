@@ -3,7 +3,7 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "observation")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -14,10 +14,22 @@ pub struct Model {
     pub observed_at: TimeDateTimeWithTimeZone,
     pub recorded_at: TimeDateTimeWithTimeZone,
     pub source_id: Option<i64>,
+    pub voided_at: Option<TimeDateTimeWithTimeZone>,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub value_num: Option<f64>,
+    pub value_bool: Option<bool>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::data_source::Entity",
+        from = "Column::SourceId",
+        to = "super::data_source::Column::SourceId",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    DataSource,
     #[sea_orm(
         belongs_to = "super::metric::Entity",
         from = "Column::MetricId",
@@ -26,6 +38,20 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Metric,
+    #[sea_orm(
+        belongs_to = "super::subject::Entity",
+        from = "Column::SubjectId",
+        to = "super::subject::Column::SubjectId",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Subject,
+}
+
+impl Related<super::data_source::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DataSource.def()
+    }
 }
 
 impl Related<super::metric::Entity> for Entity {
@@ -34,4 +60,10 @@ impl Related<super::metric::Entity> for Entity {
     }
 }
 
+impl Related<super::subject::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Subject.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
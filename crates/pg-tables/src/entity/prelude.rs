@@ -1,6 +1,9 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0
 
 pub use super::{
-    data_source::Entity as DataSource, metric::Entity as Metric,
-    observation::Entity as Observation, recipe::Entity as Recipe, subject::Entity as Subject,
+    account::Entity as Account, data_source::Entity as DataSource, job::Entity as Job,
+    metric::Entity as Metric, metric_reference_range::Entity as MetricReferenceRange,
+    metric_unit::Entity as MetricUnit, observation::Entity as Observation,
+    recipe::Entity as Recipe, revoked_token::Entity as RevokedToken, subject::Entity as Subject,
+    subject_access::Entity as SubjectAccess, subject_profile::Entity as SubjectProfile,
 };
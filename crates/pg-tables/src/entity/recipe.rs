@@ -17,6 +17,9 @@ pub struct Model {
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub expr: Json,
     pub created_at: TimeDateTimeWithTimeZone,
+    pub version: i32,
+    pub effective_from: Option<TimeDateTimeWithTimeZone>,
+    pub effective_to: Option<TimeDateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -10,9 +10,43 @@ pub struct Model {
     pub subject_id: i64,
     pub subject_type: String,
     pub created_at: TimeDateTimeWithTimeZone,
+    pub deactivated_at: Option<TimeDateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::account::Entity")]
+    Account,
+    #[sea_orm(has_many = "super::observation::Entity")]
+    Observation,
+    #[sea_orm(has_many = "super::subject_access::Entity")]
+    SubjectAccess,
+    #[sea_orm(has_one = "super::subject_profile::Entity")]
+    SubjectProfile,
+}
+
+impl Related<super::account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl Related<super::observation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Observation.def()
+    }
+}
+
+impl Related<super::subject_access::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SubjectAccess.def()
+    }
+}
+
+impl Related<super::subject_profile::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SubjectProfile.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
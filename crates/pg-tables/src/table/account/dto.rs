@@ -0,0 +1,77 @@
+use time::OffsetDateTime;
+
+use crate::table::subject::dto::SubjectId;
+
+/// Account 表示：
+/// 一个可以登录系统的本地账号
+///
+/// 注意：
+/// - Account 是“谁在操作”，Subject 是“关于谁的数据”
+/// - 每个 Account 关联一个代表账号本人的 Subject
+/// - 密码只以哈希形式存储，哈希算法由上层决定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    /// 系统内稳定的账号标识（即鉴权层的 user_id）
+    pub id: AccountId,
+
+    /// 登录名（唯一）
+    pub username: String,
+
+    /// 密码哈希（PHC 格式字符串）
+    pub password_hash: String,
+
+    /// 账号本人对应的 Subject
+    pub subject_id: SubjectId,
+
+    /// 全局角色（例如 admin）
+    pub roles: Vec<String>,
+
+    /// 停用时间（停用后不可登录 / 刷新 token）
+    pub disabled_at: Option<OffsetDateTime>,
+
+    /// 创建时间
+    pub created_at: OffsetDateTime,
+
+    /// 最近更新时间
+    pub updated_at: OffsetDateTime,
+}
+
+impl Account {
+    /// 账号是否可用
+    pub fn is_active(&self) -> bool {
+        self.disabled_at.is_none()
+    }
+}
+
+/// 创建 Account 的输入参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateAccount {
+    pub username: String,
+    pub password_hash: String,
+    pub subject_id: SubjectId,
+    pub roles: Vec<String>,
+}
+
+/// 注册账号的输入参数（同时创建账号本人的 Subject 并授予 owner 权限）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterAccount {
+    pub username: String,
+    pub password_hash: String,
+    pub roles: Vec<String>,
+}
+
+/// Account 的强类型 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccountId(pub i64);
+
+impl From<i64> for AccountId {
+    fn from(value: i64) -> Self {
+        AccountId(value)
+    }
+}
+
+impl From<AccountId> for i64 {
+    fn from(id: AccountId) -> Self {
+        id.0
+    }
+}
@@ -0,0 +1,155 @@
+use pg_core::{DbContext, Error, impl_repository};
+use sea_orm::{prelude::*, *};
+use time::OffsetDateTime;
+
+use crate::{
+    Repository, Result,
+    entity::{account, prelude::Account as AccountEntity, subject, subject_access},
+    table::{
+        account::dto::{Account, AccountId, CreateAccount, RegisterAccount},
+        subject::dto::{SubjectId, SubjectKind},
+        subject_access::dto::AccessRole,
+    },
+};
+
+impl_repository!(AccountRepo, AccountEntity, account::Model);
+
+/// ===============================
+/// Service（对外能力）
+/// ===============================
+
+/// Account service（本地账号，单表）
+pub struct AccountService {
+    repo: AccountRepo,
+}
+
+impl AccountService {
+    /// 创建 service
+    pub fn new(ctx: DbContext) -> Self {
+        Self {
+            repo: AccountRepo::new(ctx.clone()),
+        }
+    }
+
+    /// 创建一个新的 Account（username 唯一）
+    pub async fn create(&self, input: CreateAccount) -> Result<Account> {
+        if self.get_by_username(&input.username).await?.is_some() {
+            return Err(Error::already_exists("Account", "username", input.username));
+        }
+
+        let now = Self::now_utc();
+        let active = account::ActiveModel {
+            username: Set(input.username),
+            password_hash: Set(input.password_hash),
+            subject_id: Set(input.subject_id.0),
+            roles: Set(serde_json::json!(input.roles)),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let model = self.repo.insert(active).await?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 注册账号：在同一事务内创建本人的 Subject、Account 与 owner 授权
+    ///
+    /// 任意一步失败时整体回滚，不会留下无主的 Subject；
+    /// 并发注册同一 username 时由唯一索引拒绝，返回 already_exists
+    pub async fn register(&self, input: RegisterAccount) -> Result<Account> {
+        let txn = self.repo.db().begin().await?;
+        let now = Self::now_utc();
+
+        let subject = subject::ActiveModel {
+            subject_type: Set(SubjectKind::User.to_string()),
+            created_at: Set(now),
+            deactivated_at: Set(None),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let username = input.username.clone();
+        let model = account::ActiveModel {
+            username: Set(input.username),
+            password_hash: Set(input.password_hash),
+            subject_id: Set(subject.subject_id),
+            roles: Set(serde_json::json!(input.roles)),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| match e.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(_)) => {
+                Error::already_exists("Account", "username", username)
+            }
+            _ => e.into(),
+        })?;
+
+        subject_access::ActiveModel {
+            subject_id: Set(subject.subject_id),
+            user_id: Set(model.account_id),
+            role: Set(AccessRole::Owner.as_str().to_string()),
+            granted_by: Set(None),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 根据 ID 获取 Account
+    pub async fn get(&self, id: AccountId) -> Result<Option<Account>> {
+        let model = self.repo.find_by_id(id.0).await?;
+        Ok(model.map(Self::from_model))
+    }
+
+    /// 是否存在拥有指定全局角色的可用账号
+    pub async fn has_active_role(&self, role: &str) -> Result<bool> {
+        let count = AccountEntity::find()
+            .filter(Expr::cust_with_values(
+                "roles @> $1::jsonb",
+                [serde_json::json!([role])],
+            ))
+            .filter(account::Column::DisabledAt.is_null())
+            .count(self.repo.db())
+            .await?;
+        Ok(count > 0)
+    }
+
+    /// 根据登录名获取 Account
+    pub async fn get_by_username(&self, username: &str) -> Result<Option<Account>> {
+        let model = AccountEntity::find()
+            .filter(account::Column::Username.eq(username))
+            .one(self.repo.db())
+            .await?;
+        Ok(model.map(Self::from_model))
+    }
+
+    fn now_utc() -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+
+    /// ===============================
+    /// 内部映射
+    /// ===============================
+
+    fn from_model(model: account::Model) -> Account {
+        Account {
+            id: AccountId(model.account_id),
+            username: model.username,
+            password_hash: model.password_hash,
+            subject_id: SubjectId(model.subject_id),
+            // 格式不符时视为无角色
+            roles: serde_json::from_value(model.roles).unwrap_or_default(),
+            disabled_at: model.disabled_at,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
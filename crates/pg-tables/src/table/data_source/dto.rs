@@ -21,6 +21,24 @@ pub struct DataSource {
 
     /// 创建时间（审计用途）
     pub created_at: OffsetDateTime,
+
+    /// 作废时间（None 表示有效）
+    pub voided_at: Option<OffsetDateTime>,
+
+    /// 作废原因
+    pub void_reason: Option<String>,
+
+    /// 是否为共享来源（按 kind + name 唯一，跨观测复用）
+    ///
+    /// 上传的报告等一次性来源为 false，允许重名
+    pub shared: bool,
+}
+
+impl DataSource {
+    /// 是否已作废
+    pub fn is_voided(&self) -> bool {
+        self.voided_at.is_some()
+    }
 }
 
 /// 创建 DataSource 的输入参数
@@ -31,6 +49,41 @@ pub struct CreateDataSource {
     pub metadata: Option<JsonValue>,
 }
 
+/// 按 (kind, name) 查找或创建共享 DataSource 的输入参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveDataSource {
+    pub kind: DataSourceKind,
+    pub name: String,
+
+    /// 仅在首次创建时写入，复用已有来源时忽略
+    pub metadata: Option<JsonValue>,
+}
+
+/// 修改 DataSource 的输入参数（None 表示不修改）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateDataSource {
+    pub name: Option<String>,
+
+    /// Some(None) 表示清空元信息
+    pub metadata: Option<Option<JsonValue>>,
+}
+
+impl UpdateDataSource {
+    /// 是否没有任何需要修改的字段
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.metadata.is_none()
+    }
+}
+
+/// 作废 DataSource 的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoidDataSourceResult {
+    pub source: DataSource,
+
+    /// 本次一并作废的 Observation 数量
+    pub voided_observations: u64,
+}
+
 /// 查询 DataSource 的输入参数
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ListDataSource {
@@ -1,20 +1,33 @@
-use pg_core::{DbContext, OrderBy, PaginatedResponse, impl_repository};
+use pg_core::{DbContext, Error, OrderBy, PaginatedResponse, impl_repository};
 use sea_orm::{prelude::*, *};
 use time::OffsetDateTime;
 
 use crate::{
     Repository, Result,
-    entity::{data_source, prelude::DataSource as DataSourceEntity},
+    entity::{data_source, observation, prelude::DataSource as DataSourceEntity},
     table::{
         data_source::dto::{
             CreateDataSource, DataSource, DataSourceId, DataSourceKind, ListDataSource,
+            ResolveDataSource, UpdateDataSource, VoidDataSourceResult,
         },
         dto::PaginationInput,
+        observation::dto::RecordObservation,
     },
 };
 
 impl_repository!(DataSourceRepo, DataSourceEntity, data_source::Model);
 
+/// 查找或创建共享来源（并发写入时由唯一索引保证只有一条）
+///
+/// 冲突时做一次空更新，让 RETURNING 返回已有记录
+const RESOLVE_SQL: &str = r#"
+INSERT INTO data_source (source_type, source_name, metadata, created_at, is_shared)
+VALUES ($1, $2, $3, $4, TRUE)
+ON CONFLICT (source_type, source_name) WHERE is_shared AND voided_at IS NULL
+DO UPDATE SET source_name = EXCLUDED.source_name
+RETURNING *
+"#;
+
 /// ===============================
 /// Service（对外能力）
 /// ===============================
@@ -48,12 +61,174 @@ impl DataSourceService {
         Ok(Self::from_model(model))
     }
 
+    /// 创建 DataSource，并在同一事务内写入其下全部 Observation
+    ///
+    /// 任意一条写入失败时整体回滚，重试不会留下重复的来源或观测；
+    /// `observations` 的 `source_id` 会被替换为新建来源的 ID
+    pub async fn create_with_observations(
+        &self,
+        input: CreateDataSource,
+        observations: Vec<RecordObservation>,
+    ) -> Result<DataSource> {
+        let txn = self.repo.db().begin().await?;
+        let now = Self::now_utc();
+
+        let model = data_source::ActiveModel {
+            source_type: Set(input.kind.to_string()),
+            source_name: Set(input.name),
+            metadata: Set(input.metadata),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        if !observations.is_empty() {
+            let rows = observations.into_iter().map(|o| observation::ActiveModel {
+                subject_id: Set(o.subject_id.0),
+                metric_id: Set(o.metric_id.0),
+                value: Set(o.value.0),
+                value_num: Set(o.value_num),
+                value_bool: Set(o.value_bool),
+                observed_at: Set(o.observed_at),
+                recorded_at: Set(now),
+                source_id: Set(Some(model.source_id)),
+                ..Default::default()
+            });
+            observation::Entity::insert_many(rows).exec(&txn).await?;
+        }
+
+        txn.commit().await?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 按 (kind, name) 查找共享来源，不存在时创建
+    pub async fn resolve(&self, input: ResolveDataSource) -> Result<DataSource> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            RESOLVE_SQL,
+            [
+                input.kind.to_string().into(),
+                input.name.into(),
+                input.metadata.into(),
+                Self::now_utc().into(),
+            ],
+        );
+
+        let model = DataSourceEntity::find()
+            .from_raw_sql(stmt)
+            .one(self.repo.db())
+            .await?
+            .ok_or_else(|| Error::internal("resolve data source returned no row"))?;
+        Ok(Self::from_model(model))
+    }
+
     /// 根据 ID 获取 DataSource
     pub async fn get(&self, id: DataSourceId) -> Result<Option<DataSource>> {
         let model = self.repo.find_by_id(id.0).await?;
         Ok(model.map(Self::from_model))
     }
 
+    /// 修改名称 / 元信息
+    ///
+    /// DataSource 不存在时返回 `None`；共享来源改名后与另一个未作废的共享来源
+    /// (kind, name) 相同时返回 already_exists
+    pub async fn update(
+        &self,
+        id: DataSourceId,
+        input: UpdateDataSource,
+    ) -> Result<Option<DataSource>> {
+        let Some(model) = self.repo.find_by_id(id.0).await? else {
+            return Ok(None);
+        };
+        if input.is_empty() {
+            return Ok(Some(Self::from_model(model)));
+        }
+
+        let name = input
+            .name
+            .clone()
+            .unwrap_or_else(|| model.source_name.clone());
+        if model.is_shared && model.voided_at.is_none() && name != model.source_name {
+            let taken = DataSourceEntity::find()
+                .filter(data_source::Column::SourceType.eq(model.source_type.clone()))
+                .filter(data_source::Column::SourceName.eq(name.clone()))
+                .filter(data_source::Column::IsShared.eq(true))
+                .filter(data_source::Column::VoidedAt.is_null())
+                .filter(data_source::Column::SourceId.ne(model.source_id))
+                .one(self.repo.db())
+                .await?
+                .is_some();
+            if taken {
+                return Err(Error::already_exists("data_source", "name", name));
+            }
+        }
+
+        let mut active: data_source::ActiveModel = model.into();
+        if let Some(name) = input.name {
+            active.source_name = Set(name);
+        }
+        if let Some(metadata) = input.metadata {
+            active.metadata = Set(metadata);
+        }
+
+        // 并发改名时由部分唯一索引兜底
+        let model = active
+            .update(self.repo.db())
+            .await
+            .map_err(|e| match e.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => {
+                    Error::already_exists("data_source", "name", name)
+                }
+                _ => e.into(),
+            })?;
+        Ok(Some(Self::from_model(model)))
+    }
+
+    /// 作废 DataSource，并在同一事务内作废其下全部 Observation
+    ///
+    /// 已作废时不重复写库；DataSource 不存在时返回 `None`
+    pub async fn void(
+        &self,
+        id: DataSourceId,
+        reason: Option<String>,
+    ) -> Result<Option<VoidDataSourceResult>> {
+        let txn = self.repo.db().begin().await?;
+
+        let Some(model) = DataSourceEntity::find_by_id(id.0)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if model.voided_at.is_some() {
+            return Ok(Some(VoidDataSourceResult {
+                source: Self::from_model(model),
+                voided_observations: 0,
+            }));
+        }
+
+        let now = Self::now_utc();
+        let mut active: data_source::ActiveModel = model.into();
+        active.voided_at = Set(Some(now));
+        active.void_reason = Set(reason);
+        let model = active.update(&txn).await?;
+
+        let res = observation::Entity::update_many()
+            .col_expr(observation::Column::VoidedAt, Expr::value(now))
+            .filter(observation::Column::SourceId.eq(id.0))
+            .filter(observation::Column::VoidedAt.is_null())
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+        Ok(Some(VoidDataSourceResult {
+            source: Self::from_model(model),
+            voided_observations: res.rows_affected,
+        }))
+    }
+
     /// 判断 DataSource 是否存在
     pub async fn exists(&self, id: DataSourceId) -> Result<bool> {
         self.repo.exists_by_id(id.0).await
@@ -99,6 +274,9 @@ impl DataSourceService {
             name: model.source_name,
             metadata: model.metadata,
             created_at: model.created_at,
+            voided_at: model.voided_at,
+            void_reason: model.void_reason,
+            shared: model.is_shared,
         }
     }
 }
@@ -0,0 +1,165 @@
+use core::fmt;
+
+use serde_json::Value as JsonValue;
+use time::{Duration, OffsetDateTime};
+
+/// Job 表示：
+/// 一条持久化在 PostgreSQL 中的后台任务
+///
+/// 注意：
+/// - payload / result 的语义由队列（queue）的消费者解释
+/// - 多实例之间通过 `FOR UPDATE SKIP LOCKED` 抢占，不依赖进程内状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    /// 系统内稳定的任务标识
+    pub id: JobId,
+
+    /// 队列名称
+    pub queue: String,
+
+    /// 任务输入
+    pub payload: JsonValue,
+
+    /// 当前状态
+    pub status: JobStatus,
+
+    /// 已尝试次数（每次被领取 +1）
+    pub attempts: i32,
+
+    /// 最大尝试次数，超过后进入 Failed
+    pub max_attempts: i32,
+
+    /// 任务可被领取的时间（重试退避用）
+    pub run_at: OffsetDateTime,
+
+    /// 当前持有任务的 worker
+    pub locked_by: Option<String>,
+
+    /// 可见性超时：超过后任务可被其它 worker 重新领取
+    pub locked_until: Option<OffsetDateTime>,
+
+    /// 任务结果（成功时）
+    pub result: Option<JsonValue>,
+
+    /// 最近一次失败原因
+    pub last_error: Option<String>,
+
+    /// 创建时间
+    pub created_at: OffsetDateTime,
+
+    /// 最近更新时间
+    pub updated_at: OffsetDateTime,
+
+    /// 结束时间（成功、最终失败或取消），TTL 清理依据
+    pub finished_at: Option<OffsetDateTime>,
+
+    /// 任务所属 subject（用于按 subject 列出任务）
+    pub subject_id: Option<i64>,
+
+    /// 执行进度（由 worker 在执行过程中上报，语义由队列消费者解释）
+    pub progress: Option<JsonValue>,
+}
+
+/// 入队参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnqueueJob {
+    pub queue: String,
+    pub payload: JsonValue,
+    pub max_attempts: i32,
+
+    /// 延迟执行（None 表示立即可领取）
+    pub run_at: Option<OffsetDateTime>,
+
+    /// 任务所属 subject（可选）
+    pub subject_id: Option<i64>,
+}
+
+/// 领取任务的参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimJob {
+    pub queue: String,
+    pub worker_id: String,
+
+    /// 可见性超时（worker 需要在此之前 heartbeat 或结束任务）
+    pub visibility_timeout: Duration,
+}
+
+/// 查询 Job 的输入参数（None 表示不过滤）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListJobs {
+    pub queue: Option<String>,
+    pub subject_id: Option<i64>,
+    pub status: Option<JobStatus>,
+}
+
+/// Job 的强类型 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JobId(pub i64);
+
+impl From<i64> for JobId {
+    fn from(value: i64) -> Self {
+        JobId(value)
+    }
+}
+
+impl From<JobId> for i64 {
+    fn from(id: JobId) -> Self {
+        id.0
+    }
+}
+
+/// Job 的生命周期状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// 是否已结束（不会再被领取）
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Succeeded | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for JobStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "pending" => JobStatus::Pending,
+            "running" => JobStatus::Running,
+            "succeeded" => JobStatus::Succeeded,
+            "failed" => JobStatus::Failed,
+            "cancelled" => JobStatus::Cancelled,
+            // 未知状态按 Failed 处理，避免被反复领取
+            _ => JobStatus::Failed,
+        }
+    }
+}
+
+impl From<String> for JobStatus {
+    fn from(value: String) -> Self {
+        JobStatus::from(value.as_str())
+    }
+}
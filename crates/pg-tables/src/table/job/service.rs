@@ -0,0 +1,366 @@
+use pg_core::{DbContext, OrderBy, PaginatedResponse, impl_repository};
+use sea_orm::{prelude::*, *};
+use serde_json::Value as JsonValue;
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+    Repository, Result,
+    entity::{job, prelude::Job as JobEntity},
+    table::{
+        dto::PaginationInput,
+        job::dto::{ClaimJob, EnqueueJob, Job, JobId, JobStatus, ListJobs},
+    },
+};
+
+impl_repository!(JobRepo, JobEntity, job::Model);
+
+/// 领取一条可执行的任务：
+/// - pending 且已到 run_at
+/// - 或 running 但可见性超时已过（worker 崩溃），且还有剩余尝试次数
+///
+/// `FOR UPDATE SKIP LOCKED` 保证多实例并发领取时互不阻塞、不重复
+const CLAIM_SQL: &str = r#"
+UPDATE job
+SET status = 'running',
+    attempts = attempts + 1,
+    locked_by = $2,
+    locked_until = now() + make_interval(secs => $3),
+    updated_at = now()
+WHERE job_id = (
+    SELECT job_id
+    FROM job
+    WHERE queue = $1
+      AND (
+        (status = 'pending' AND run_at <= now())
+        OR (status = 'running' AND locked_until < now() AND attempts < max_attempts)
+      )
+    ORDER BY run_at, job_id
+    FOR UPDATE SKIP LOCKED
+    LIMIT 1
+)
+RETURNING *
+"#;
+
+/// 失败处理：还有剩余次数则退避后回到 pending，否则进入 failed
+const FAIL_SQL: &str = r#"
+UPDATE job
+SET status = CASE
+        WHEN $4::float8 IS NOT NULL AND attempts < max_attempts THEN 'pending'
+        ELSE 'failed'
+    END,
+    run_at = CASE
+        WHEN $4::float8 IS NOT NULL AND attempts < max_attempts
+            THEN now() + make_interval(secs => $4::float8)
+        ELSE run_at
+    END,
+    finished_at = CASE
+        WHEN $4::float8 IS NOT NULL AND attempts < max_attempts THEN NULL
+        ELSE now()
+    END,
+    last_error = $3,
+    locked_by = NULL,
+    locked_until = NULL,
+    updated_at = now()
+WHERE job_id = $1
+  AND locked_by = $2
+  AND status = 'running'
+RETURNING *
+"#;
+
+/// ===============================
+/// Service（对外能力）
+/// ===============================
+
+/// Job service（持久化任务队列，单表）
+pub struct JobService {
+    repo: JobRepo,
+}
+
+impl JobService {
+    /// 创建 service
+    pub fn new(ctx: DbContext) -> Self {
+        Self {
+            repo: JobRepo::new(ctx.clone()),
+        }
+    }
+
+    /// 入队一个新任务
+    pub async fn enqueue(&self, input: EnqueueJob) -> Result<Job> {
+        let now = Self::now_utc();
+
+        let active = job::ActiveModel {
+            queue: Set(input.queue),
+            payload: Set(input.payload),
+            status: Set(JobStatus::Pending.to_string()),
+            attempts: Set(0),
+            max_attempts: Set(input.max_attempts.max(1)),
+            run_at: Set(input.run_at.unwrap_or(now)),
+            subject_id: Set(input.subject_id),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let model = self.repo.insert(active).await?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 根据 ID 获取 Job
+    pub async fn get(&self, id: JobId) -> Result<Option<Job>> {
+        let model = self.repo.find_by_id(id.0).await?;
+        Ok(model.map(Self::from_model))
+    }
+
+    /// 查询 Job（按 queue / subject / status 过滤，最新的在前）
+    pub async fn list(
+        &self,
+        input: ListJobs,
+        pagination: Option<PaginationInput>,
+    ) -> Result<PaginatedResponse<Job>> {
+        let mut condition = Condition::all();
+        let mut has_condition = false;
+
+        if let Some(queue) = input.queue {
+            condition = condition.add(job::Column::Queue.eq(queue));
+            has_condition = true;
+        }
+
+        if let Some(subject_id) = input.subject_id {
+            condition = condition.add(job::Column::SubjectId.eq(subject_id));
+            has_condition = true;
+        }
+
+        if let Some(status) = input.status {
+            condition = condition.add(job::Column::Status.eq(status.as_str()));
+            has_condition = true;
+        }
+
+        let condition = if has_condition { Some(condition) } else { None };
+        let order_by = OrderBy::desc(job::Column::JobId);
+        let params = pagination.unwrap_or_default().to_params();
+
+        let response = self
+            .repo
+            .find_paginated(condition, &params, Some(&order_by))
+            .await?;
+        Ok(response.map(Self::from_model))
+    }
+
+    /// 领取下一条可执行任务（没有则返回 None）
+    pub async fn claim(&self, input: ClaimJob) -> Result<Option<Job>> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            CLAIM_SQL,
+            [
+                input.queue.into(),
+                input.worker_id.into(),
+                input.visibility_timeout.as_seconds_f64().into(),
+            ],
+        );
+
+        let model = JobEntity::find()
+            .from_raw_sql(stmt)
+            .one(self.repo.db())
+            .await?;
+        Ok(model.map(Self::from_model))
+    }
+
+    /// 延长可见性超时（长任务的心跳）
+    ///
+    /// 返回 false 表示任务已不再由该 worker 持有
+    pub async fn heartbeat(
+        &self,
+        id: JobId,
+        worker_id: &str,
+        visibility_timeout: Duration,
+    ) -> Result<bool> {
+        let now = Self::now_utc();
+
+        let res = JobEntity::update_many()
+            .col_expr(
+                job::Column::LockedUntil,
+                Expr::value(now + visibility_timeout),
+            )
+            .col_expr(job::Column::UpdatedAt, Expr::value(now))
+            .filter(job::Column::JobId.eq(id.0))
+            .filter(job::Column::LockedBy.eq(worker_id))
+            .filter(job::Column::Status.eq(JobStatus::Running.as_str()))
+            .exec(self.repo.db())
+            .await?;
+        Ok(res.rows_affected > 0)
+    }
+
+    /// 上报执行进度
+    ///
+    /// 返回 false 表示任务已不再由该 worker 持有（例如已被取消）
+    pub async fn update_progress(
+        &self,
+        id: JobId,
+        worker_id: &str,
+        progress: JsonValue,
+    ) -> Result<bool> {
+        let res = JobEntity::update_many()
+            .col_expr(job::Column::Progress, Expr::value(progress))
+            .col_expr(job::Column::UpdatedAt, Expr::value(Self::now_utc()))
+            .filter(job::Column::JobId.eq(id.0))
+            .filter(job::Column::LockedBy.eq(worker_id))
+            .filter(job::Column::Status.eq(JobStatus::Running.as_str()))
+            .exec(self.repo.db())
+            .await?;
+        Ok(res.rows_affected > 0)
+    }
+
+    /// 取消任务（pending 或 running）
+    ///
+    /// 返回 false 表示任务不存在或已结束；
+    /// 正在执行的 worker 会在下一次 heartbeat / 上报进度时发现任务已不再由它持有
+    pub async fn cancel(&self, id: JobId) -> Result<bool> {
+        let now = Self::now_utc();
+
+        let res = JobEntity::update_many()
+            .col_expr(
+                job::Column::Status,
+                Expr::value(JobStatus::Cancelled.as_str()),
+            )
+            .col_expr(job::Column::LockedBy, Expr::value(Option::<String>::None))
+            .col_expr(
+                job::Column::LockedUntil,
+                Expr::value(Option::<OffsetDateTime>::None),
+            )
+            .col_expr(job::Column::FinishedAt, Expr::value(now))
+            .col_expr(job::Column::UpdatedAt, Expr::value(now))
+            .filter(job::Column::JobId.eq(id.0))
+            .filter(
+                job::Column::Status
+                    .is_in([JobStatus::Pending.as_str(), JobStatus::Running.as_str()]),
+            )
+            .exec(self.repo.db())
+            .await?;
+        Ok(res.rows_affected > 0)
+    }
+
+    /// 标记任务成功
+    ///
+    /// 返回 false 表示任务已不再由该 worker 持有（结果被丢弃）
+    pub async fn complete(&self, id: JobId, worker_id: &str, result: JsonValue) -> Result<bool> {
+        let now = Self::now_utc();
+
+        let res = JobEntity::update_many()
+            .col_expr(
+                job::Column::Status,
+                Expr::value(JobStatus::Succeeded.as_str()),
+            )
+            .col_expr(job::Column::Result, Expr::value(result))
+            .col_expr(job::Column::LastError, Expr::value(Option::<String>::None))
+            .col_expr(job::Column::LockedBy, Expr::value(Option::<String>::None))
+            .col_expr(
+                job::Column::LockedUntil,
+                Expr::value(Option::<OffsetDateTime>::None),
+            )
+            .col_expr(job::Column::FinishedAt, Expr::value(now))
+            .col_expr(job::Column::UpdatedAt, Expr::value(now))
+            .filter(job::Column::JobId.eq(id.0))
+            .filter(job::Column::LockedBy.eq(worker_id))
+            .filter(job::Column::Status.eq(JobStatus::Running.as_str()))
+            .exec(self.repo.db())
+            .await?;
+        Ok(res.rows_affected > 0)
+    }
+
+    /// 标记任务失败
+    ///
+    /// `retry_delay` 为 None 时直接置为 failed；否则在尝试次数未用完时延迟重新排队。
+    /// 返回更新后的任务；None 表示任务已不再由该 worker 持有
+    pub async fn fail(
+        &self,
+        id: JobId,
+        worker_id: &str,
+        error: String,
+        retry_delay: Option<Duration>,
+    ) -> Result<Option<Job>> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            FAIL_SQL,
+            [
+                id.0.into(),
+                worker_id.to_string().into(),
+                error.into(),
+                retry_delay.map(|d| d.as_seconds_f64()).into(),
+            ],
+        );
+
+        let model = JobEntity::find()
+            .from_raw_sql(stmt)
+            .one(self.repo.db())
+            .await?;
+        Ok(model.map(Self::from_model))
+    }
+
+    /// 将可见性超时且已用完尝试次数的 running 任务标记为 failed
+    pub async fn reap_expired(&self) -> Result<u64> {
+        let now = Self::now_utc();
+
+        let res = JobEntity::update_many()
+            .col_expr(job::Column::Status, Expr::value(JobStatus::Failed.as_str()))
+            .col_expr(
+                job::Column::LastError,
+                Expr::value("visibility timeout exceeded"),
+            )
+            .col_expr(job::Column::LockedBy, Expr::value(Option::<String>::None))
+            .col_expr(
+                job::Column::LockedUntil,
+                Expr::value(Option::<OffsetDateTime>::None),
+            )
+            .col_expr(job::Column::FinishedAt, Expr::value(now))
+            .col_expr(job::Column::UpdatedAt, Expr::value(now))
+            .filter(job::Column::Status.eq(JobStatus::Running.as_str()))
+            .filter(job::Column::LockedUntil.lt(now))
+            .filter(Expr::col(job::Column::Attempts).gte(Expr::col(job::Column::MaxAttempts)))
+            .exec(self.repo.db())
+            .await?;
+        Ok(res.rows_affected)
+    }
+
+    /// TTL 清理：删除在 `finished_before` 之前结束的任务
+    pub async fn purge_finished(&self, finished_before: OffsetDateTime) -> Result<u64> {
+        let condition = Condition::all()
+            .add(job::Column::Status.is_in([
+                JobStatus::Succeeded.as_str(),
+                JobStatus::Failed.as_str(),
+                JobStatus::Cancelled.as_str(),
+            ]))
+            .add(job::Column::FinishedAt.lt(finished_before));
+
+        let res = self.repo.delete_many(condition).await?;
+        Ok(res.rows_affected)
+    }
+
+    fn now_utc() -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+
+    /// ===============================
+    /// 内部映射
+    /// ===============================
+
+    fn from_model(model: job::Model) -> Job {
+        Job {
+            id: JobId(model.job_id),
+            queue: model.queue,
+            payload: model.payload,
+            status: JobStatus::from(model.status),
+            attempts: model.attempts,
+            max_attempts: model.max_attempts,
+            run_at: model.run_at,
+            locked_by: model.locked_by,
+            locked_until: model.locked_until,
+            result: model.result,
+            last_error: model.last_error,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            finished_at: model.finished_at,
+            subject_id: model.subject_id,
+            progress: model.progress,
+        }
+    }
+}
@@ -82,11 +82,25 @@ pub struct Metric {
 
     pub visualization: MetricVisualization,
 
+    /// 生命周期状态（废弃后不再出现在选择列表中）
+    pub status: MetricStatus,
+
+    /// 指标说明（展示给用户）
+    pub description: Option<String>,
+
+    /// 数值展示的小数位数（None 表示按原值展示）
+    pub display_precision: Option<u8>,
+
     /// 创建时间（审计用途）
     pub created_at: OffsetDateTime,
 }
 
 impl Metric {
+    /// 是否处于可用状态
+    pub fn is_active(&self) -> bool {
+        self.status == MetricStatus::Active
+    }
+
     /// 尝试将 observation 的值投影为“可比较的数轴值”
     ///
     /// 返回 Some(f64)：可用于排序 / 画图
@@ -111,6 +125,8 @@ pub struct MetricSummary {
     pub unit: Option<String>,
     pub value_type: MetricValueType,
     pub visualization: MetricVisualization,
+    pub status: MetricStatus,
+    pub display_precision: Option<u8>,
 }
 
 impl From<Metric> for MetricSummary {
@@ -122,6 +138,8 @@ impl From<Metric> for MetricSummary {
             unit: metric.unit,
             value_type: metric.value_type,
             visualization: metric.visualization,
+            status: metric.status,
+            display_precision: metric.display_precision,
         }
     }
 }
@@ -134,12 +152,131 @@ pub struct CreateMetric {
     pub name: String,
     pub unit: Option<String>,
     pub value_type: MetricValueType,
+    pub visualization: MetricVisualization,
+    pub status: MetricStatus,
+    pub description: Option<String>,
+    pub display_precision: Option<u8>,
+}
+
+/// 小数位数上限
+pub const MAX_DISPLAY_PRECISION: u8 = 6;
+
+/// 说明文字长度上限（字符数）
+pub const MAX_DESCRIPTION_LEN: usize = 1000;
+
+impl CreateMetric {
+    /// 校验输入，返回第一个不合法的原因
+    pub fn check(&self) -> Result<(), String> {
+        let code = self.code.as_ref();
+        // 例如：lab.biochem.tg、vital.bp.systolic
+        let valid_segment = |seg: &str| {
+            !seg.is_empty()
+                && seg
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        };
+        if !code.split('.').all(valid_segment) {
+            return Err(format!(
+                "metric code '{}' must be dot separated segments of [a-z0-9_]",
+                code
+            ));
+        }
+
+        if self.name.trim().is_empty() {
+            return Err("metric name must not be empty".to_string());
+        }
+
+        if self
+            .description
+            .as_deref()
+            .is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_LEN)
+        {
+            return Err(format!(
+                "description must be at most {} characters",
+                MAX_DESCRIPTION_LEN
+            ));
+        }
+
+        check_display(
+            &self.value_type,
+            &self.visualization,
+            self.display_precision,
+        )
+    }
+}
+
+/// 校验可视化方式、小数位数与值类型是否匹配
+///
+/// - 折线图 / 柱状图只能用于数值类型
+/// - 小数位数只对 Float / Decimal 有意义，Integer 只允许 0
+pub fn check_display(
+    value_type: &MetricValueType,
+    visualization: &MetricVisualization,
+    display_precision: Option<u8>,
+) -> Result<(), String> {
+    let chart = matches!(
+        visualization,
+        MetricVisualization::LineChart | MetricVisualization::BarChart
+    );
+    if chart && !value_type.is_numeric() {
+        return Err(format!(
+            "visualization '{}' requires a numeric value type, got '{}'",
+            visualization, value_type
+        ));
+    }
+
+    if let Some(precision) = display_precision {
+        let allowed = match value_type {
+            MetricValueType::Float | MetricValueType::Decimal => precision <= MAX_DISPLAY_PRECISION,
+            MetricValueType::Integer => precision == 0,
+            MetricValueType::Boolean | MetricValueType::Text => false,
+        };
+        if !allowed {
+            return Err(format!(
+                "display precision {} is not valid for value type '{}'",
+                precision, value_type
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// 更新 Metric 的输入参数
+///
+/// 字段为 None 表示不修改；
+/// code / kind / value_type 决定历史数据的解释方式，不允许修改
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateMetric {
+    pub name: Option<String>,
+
+    /// Some(None) 表示清空单位
+    pub unit: Option<Option<String>>,
+
+    pub visualization: Option<MetricVisualization>,
+}
+
+impl UpdateMetric {
+    /// 是否没有任何需要修改的字段
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.unit.is_none() && self.visualization.is_none()
+    }
+
+    /// 校验输入（与创建时的名称规则一致），返回第一个不合法的原因
+    pub fn check(&self) -> Result<(), String> {
+        if self.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+            return Err("metric name must not be empty".to_string());
+        }
+        Ok(())
+    }
 }
 
 /// 查询 Metric 的输入参数
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ListMetric {
     pub value_type: Option<MetricValueType>,
+    pub kind: Option<MetricKind>,
+    pub status: Option<MetricStatus>,
 }
 
 /// Metric 的强类型 ID
@@ -185,6 +322,16 @@ pub enum MetricValueType {
     Text,
 }
 
+impl MetricValueType {
+    /// 是否为数值类型（可进入数轴 / 图表）
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            MetricValueType::Integer | MetricValueType::Float | MetricValueType::Decimal
+        )
+    }
+}
+
 /// 指标的可视化类型
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MetricVisualization {
@@ -4,13 +4,16 @@ use time::OffsetDateTime;
 
 use crate::{
     Repository, Result,
-    entity::{metric, prelude::Metric as MetricEntity},
+    entity::{
+        metric, metric_reference_range, metric_unit, observation, prelude::Metric as MetricEntity,
+    },
     table::{
         dto::PaginationInput,
         metric::dto::{
-            CreateMetric, ListMetric, Metric, MetricCode, MetricId, MetricKind, MetricValueType,
-            MetricVisualization,
+            CreateMetric, ListMetric, Metric, MetricCode, MetricId, MetricKind, MetricStatus,
+            MetricValueType, MetricVisualization, UpdateMetric, check_display,
         },
+        metric_unit::dto::same_unit,
     },
 };
 
@@ -35,6 +38,8 @@ impl MetricService {
 
     /// 创建一个新的 Metric
     pub async fn create(&self, input: CreateMetric) -> Result<Metric> {
+        input.check().map_err(Error::validation)?;
+
         if self.exists_by_code(&input.code).await? {
             return Err(Error::already_exists("Metric", "metric_code", input.code.0));
         }
@@ -46,6 +51,10 @@ impl MetricService {
             metric_name: Set(input.name),
             unit: Set(input.unit),
             value_type: Set(input.value_type.to_string()),
+            visualization: Set(input.visualization.to_string()),
+            status: Set(input.status.to_string()),
+            description: Set(input.description),
+            display_precision: Set(input.display_precision.map(i16::from)),
             created_at: Set(now),
             ..Default::default()
         };
@@ -54,6 +63,107 @@ impl MetricService {
         Ok(Self::from_model(model))
     }
 
+    /// 更新 Metric 的展示属性（名称 / 单位 / 可视化）
+    ///
+    /// Metric 不存在时返回 `None`；单位已被观测、备选单位或参考范围使用时不允许修改
+    pub async fn update(&self, id: MetricId, input: UpdateMetric) -> Result<Option<Metric>> {
+        input.check().map_err(Error::validation)?;
+
+        let Some(model) = self.repo.find_by_id(id.0).await? else {
+            return Ok(None);
+        };
+        if input.is_empty() {
+            return Ok(Some(Self::from_model(model)));
+        }
+
+        if let Some(visualization) = &input.visualization {
+            let current = Self::from_model(model.clone());
+            check_display(
+                &current.value_type,
+                visualization,
+                current.display_precision,
+            )
+            .map_err(Error::validation)?;
+        }
+
+        // 观测值一律按规范单位存储，单位一旦被使用就不能再改，否则已有数据会被重新解释
+        let unit_changed = input.unit.as_ref().is_some_and(|unit| {
+            match (unit.as_deref(), model.unit.as_deref()) {
+                (Some(a), Some(b)) => !same_unit(a, b),
+                (a, b) => a != b,
+            }
+        });
+        if unit_changed && self.unit_in_use(id).await? {
+            return Err(Error::validation(format!(
+                "unit of metric {} cannot be changed: it already has observations, \
+                 alternative units or reference ranges",
+                model.metric_code
+            )));
+        }
+
+        let mut active: metric::ActiveModel = model.into();
+        if let Some(name) = input.name {
+            active.metric_name = Set(name);
+        }
+        if let Some(unit) = input.unit {
+            active.unit = Set(unit);
+        }
+        if let Some(visualization) = input.visualization {
+            active.visualization = Set(visualization.to_string());
+        }
+
+        let model = self.repo.update(active).await?;
+        Ok(Some(Self::from_model(model)))
+    }
+
+    /// 单位是否已被观测、备选单位或参考范围引用
+    async fn unit_in_use(&self, id: MetricId) -> Result<bool> {
+        let db = self.repo.db();
+
+        let has_observations = observation::Entity::find()
+            .filter(observation::Column::MetricId.eq(id.0))
+            .one(db)
+            .await?
+            .is_some();
+        if has_observations {
+            return Ok(true);
+        }
+
+        let has_units = metric_unit::Entity::find()
+            .filter(metric_unit::Column::MetricId.eq(id.0))
+            .one(db)
+            .await?
+            .is_some();
+        if has_units {
+            return Ok(true);
+        }
+
+        let has_ranges = metric_reference_range::Entity::find()
+            .filter(metric_reference_range::Column::MetricId.eq(id.0))
+            .one(db)
+            .await?
+            .is_some();
+        Ok(has_ranges)
+    }
+
+    /// 设置 Metric 生命周期状态（废弃 / 恢复），状态未变化时不写库
+    ///
+    /// Metric 不存在时返回 `None`
+    pub async fn set_status(&self, id: MetricId, status: MetricStatus) -> Result<Option<Metric>> {
+        let Some(model) = self.repo.find_by_id(id.0).await? else {
+            return Ok(None);
+        };
+        if MetricStatus::from(model.status.as_str()) == status {
+            return Ok(Some(Self::from_model(model)));
+        }
+
+        let mut active: metric::ActiveModel = model.into();
+        active.status = Set(status.to_string());
+
+        let model = self.repo.update(active).await?;
+        Ok(Some(Self::from_model(model)))
+    }
+
     /// 根据 ID 获取 Metric
     pub async fn get(&self, id: MetricId) -> Result<Option<Metric>> {
         let model = self.repo.find_by_id(id.0).await?;
@@ -89,7 +199,7 @@ impl MetricService {
         Ok(found.is_some())
     }
 
-    /// 查询 Metric（可选按类型过滤）
+    /// 查询 Metric（可选按值类型 / 指标类型 / 状态过滤，包含已废弃指标）
     pub async fn list(
         &self,
         input: ListMetric,
@@ -103,6 +213,16 @@ impl MetricService {
             has_condition = true;
         }
 
+        if let Some(kind) = input.kind {
+            condition = condition.add(metric::Column::Kind.eq(kind.to_string()));
+            has_condition = true;
+        }
+
+        if let Some(status) = input.status {
+            condition = condition.add(metric::Column::Status.eq(status.to_string()));
+            has_condition = true;
+        }
+
         let condition = if has_condition { Some(condition) } else { None };
         let order_by = OrderBy::desc(metric::Column::CreatedAt);
         let params = pagination.unwrap_or_default().to_params();
@@ -114,11 +234,29 @@ impl MetricService {
         Ok(response.map(Self::from_model))
     }
 
-    /// 获取可用于选择的 Metric 列表（给前端下拉框用）
+    /// 按 ID 批量获取 Metric（不存在的 ID 忽略，按名称排序）
+    pub async fn list_by_ids(&self, ids: Vec<MetricId>) -> Result<Vec<Metric>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<i64> = ids.into_iter().map(|id| id.0).collect();
+        let condition = Condition::all().add(metric::Column::MetricId.is_in(ids));
+        let order_by = OrderBy::asc(metric::Column::MetricName);
+        let models = self
+            .repo
+            .find_with_filter_and_order(condition, &order_by)
+            .await?;
+
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    /// 获取可用于选择的 Metric 列表（给前端下拉框用，不含已废弃指标）
     pub async fn list_selectable(&self) -> Result<Vec<Metric>> {
         let order_by = OrderBy::asc(metric::Column::MetricName);
 
-        let condition = Condition::all();
+        let condition =
+            Condition::all().add(metric::Column::Status.eq(MetricStatus::Active.to_string()));
         let models = self
             .repo
             .find_with_filter_and_order(condition, &order_by)
@@ -144,6 +282,9 @@ impl MetricService {
             unit: model.unit,
             value_type: MetricValueType::from(model.value_type),
             visualization: MetricVisualization::from(model.visualization),
+            status: MetricStatus::from(model.status),
+            description: model.description,
+            display_precision: model.display_precision.and_then(|p| u8::try_from(p).ok()),
             created_at: model.created_at,
         }
     }
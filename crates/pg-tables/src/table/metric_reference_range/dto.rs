@@ -0,0 +1,465 @@
+use core::fmt;
+
+use time::OffsetDateTime;
+
+use crate::table::{metric::dto::MetricId, metric_unit::dto::UnitConversion};
+
+/// 指标的参考范围
+///
+/// 说明：
+/// - 同一指标可以有多条范围，按性别 / 年龄段区分，未指定的维度表示不限
+/// - 上下限都是可选的（例如只有上限的指标）
+/// - `unit` 为空时表示与 metric 的单位一致
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceRange {
+    pub id: ReferenceRangeId,
+    pub metric_id: MetricId,
+
+    /// 适用性别（None 表示不限）
+    pub sex: Option<Sex>,
+
+    /// 适用年龄段 `[age_min, age_max)`，单位：岁
+    pub age_min: Option<u32>,
+    pub age_max: Option<u32>,
+
+    /// 正常范围（含边界）
+    pub low: Option<f64>,
+    pub high: Option<f64>,
+
+    /// 危急值（达到即为 critical）
+    pub critical_low: Option<f64>,
+    pub critical_high: Option<f64>,
+
+    pub unit: Option<String>,
+
+    /// 范围出处（化验室 / 指南等）
+    pub source: Option<String>,
+
+    pub created_at: OffsetDateTime,
+}
+
+impl ReferenceRange {
+    /// 是否适用于指定性别与年龄
+    ///
+    /// 限定了性别 / 年龄段的范围，在对应信息未知时不适用
+    pub fn applies_to(&self, sex: Option<Sex>, age: Option<u32>) -> bool {
+        if self.sex.is_some_and(|s| sex != Some(s)) {
+            return false;
+        }
+        if self.age_min.is_none() && self.age_max.is_none() {
+            return true;
+        }
+        let Some(age) = age else {
+            return false;
+        };
+        self.age_min.is_none_or(|min| min <= age) && self.age_max.is_none_or(|max| age < max)
+    }
+
+    /// 限定条件越多越具体，选择范围时优先
+    fn specificity(&self) -> u8 {
+        u8::from(self.sex.is_some())
+            + u8::from(self.age_min.is_some())
+            + u8::from(self.age_max.is_some())
+    }
+
+    /// 按范围判定数值
+    pub fn flag(&self, value: f64) -> ReferenceFlag {
+        if self.critical_low.is_some_and(|c| value <= c)
+            || self.critical_high.is_some_and(|c| value >= c)
+        {
+            ReferenceFlag::Critical
+        } else if self.low.is_some_and(|low| value < low) {
+            ReferenceFlag::Low
+        } else if self.high.is_some_and(|high| value > high) {
+            ReferenceFlag::High
+        } else {
+            ReferenceFlag::Normal
+        }
+    }
+
+    /// 把各上下限换算为 `unit`（`conversion` 为当前单位到 `unit` 的换算）
+    pub fn convert(mut self, conversion: UnitConversion, unit: Option<String>) -> Self {
+        for limit in [
+            &mut self.low,
+            &mut self.high,
+            &mut self.critical_low,
+            &mut self.critical_high,
+        ] {
+            *limit = limit.map(|v| conversion.apply(v));
+        }
+        self.unit = unit;
+        self
+    }
+}
+
+/// 从多条范围中选出适用且最具体的一条（同样具体时取先创建的）
+pub fn select_range(
+    ranges: &[ReferenceRange],
+    sex: Option<Sex>,
+    age: Option<u32>,
+) -> Option<&ReferenceRange> {
+    ranges
+        .iter()
+        .filter(|r| r.applies_to(sex, age))
+        .min_by_key(|r| (std::cmp::Reverse(r.specificity()), r.id.0))
+}
+
+/// 创建参考范围的输入参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateReferenceRange {
+    pub metric_id: MetricId,
+    pub sex: Option<Sex>,
+    pub age_min: Option<u32>,
+    pub age_max: Option<u32>,
+    pub low: Option<f64>,
+    pub high: Option<f64>,
+    pub critical_low: Option<f64>,
+    pub critical_high: Option<f64>,
+    pub unit: Option<String>,
+    pub source: Option<String>,
+}
+
+impl CreateReferenceRange {
+    /// 校验范围本身是否自洽
+    pub fn check(&self) -> Result<(), String> {
+        // 按 critical_low <= low <= high <= critical_high 的顺序排列
+        let limits = [self.critical_low, self.low, self.high, self.critical_high];
+        if limits.iter().all(Option::is_none) {
+            return Err("at least one limit is required".to_string());
+        }
+        if limits.iter().flatten().any(|v| !v.is_finite()) {
+            return Err("limits must be finite numbers".to_string());
+        }
+        if self
+            .age_min
+            .zip(self.age_max)
+            .is_some_and(|(min, max)| min >= max)
+        {
+            return Err("age_min must be less than age_max".to_string());
+        }
+
+        let ordered: Vec<f64> = limits.into_iter().flatten().collect();
+        if ordered.windows(2).any(|w| w[0] > w[1]) {
+            return Err(
+                "limits must satisfy critical_low <= low <= high <= critical_high".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// 把各上下限换算为 `unit`（`conversion` 为当前单位到 `unit` 的换算）
+    pub fn convert(mut self, conversion: UnitConversion, unit: Option<String>) -> Self {
+        for limit in [
+            &mut self.low,
+            &mut self.high,
+            &mut self.critical_low,
+            &mut self.critical_high,
+        ] {
+            *limit = limit.map(|v| conversion.apply(v));
+        }
+        self.unit = unit;
+        self
+    }
+}
+
+/// 观测值相对参考范围的判定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceFlag {
+    Low,
+    Normal,
+    High,
+
+    /// 达到危急值（无论偏高还是偏低）
+    Critical,
+}
+
+impl ReferenceFlag {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReferenceFlag::Low => "low",
+            ReferenceFlag::Normal => "normal",
+            ReferenceFlag::High => "high",
+            ReferenceFlag::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for ReferenceFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 性别（用于选择参考范围）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+impl Sex {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Sex::Male => "male",
+            Sex::Female => "female",
+        }
+    }
+
+    /// 严格解析（未知值返回 None）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "male" => Some(Sex::Male),
+            "female" => Some(Sex::Female),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Sex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// ReferenceRange 的强类型 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReferenceRangeId(pub i64);
+
+impl From<i64> for ReferenceRangeId {
+    fn from(value: i64) -> Self {
+        ReferenceRangeId(value)
+    }
+}
+
+impl From<ReferenceRangeId> for i64 {
+    fn from(id: ReferenceRangeId) -> Self {
+        id.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(id: i64) -> ReferenceRange {
+        ReferenceRange {
+            id: ReferenceRangeId(id),
+            metric_id: MetricId(1),
+            sex: None,
+            age_min: None,
+            age_max: None,
+            low: Some(3.9),
+            high: Some(6.1),
+            critical_low: None,
+            critical_high: None,
+            unit: None,
+            source: None,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn create() -> CreateReferenceRange {
+        CreateReferenceRange {
+            metric_id: MetricId(1),
+            sex: None,
+            age_min: None,
+            age_max: None,
+            low: Some(3.9),
+            high: Some(6.1),
+            critical_low: Some(2.8),
+            critical_high: Some(25.0),
+            unit: None,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_applies_to_sex() {
+        let r = ReferenceRange {
+            sex: Some(Sex::Female),
+            ..range(1)
+        };
+        assert!(r.applies_to(Some(Sex::Female), None));
+        assert!(!r.applies_to(Some(Sex::Male), None));
+        // 性别未知时不适用限定了性别的范围
+        assert!(!r.applies_to(None, Some(30)));
+        // 不限性别的范围对任何人适用
+        assert!(range(2).applies_to(None, None));
+    }
+
+    #[test]
+    fn test_applies_to_age() {
+        let r = ReferenceRange {
+            age_min: Some(18),
+            age_max: Some(65),
+            ..range(1)
+        };
+        assert!(!r.applies_to(None, Some(17)));
+        assert!(r.applies_to(None, Some(18)));
+        assert!(r.applies_to(None, Some(64)));
+        // 上限不含
+        assert!(!r.applies_to(None, Some(65)));
+        // 年龄未知时不适用限定了年龄段的范围
+        assert!(!r.applies_to(None, None));
+
+        let open = ReferenceRange {
+            age_min: Some(65),
+            ..range(2)
+        };
+        assert!(open.applies_to(None, Some(90)));
+        assert!(!open.applies_to(None, Some(64)));
+    }
+
+    #[test]
+    fn test_flag() {
+        let r = range(1);
+        assert_eq!(r.flag(3.8), ReferenceFlag::Low);
+        // 正常范围含边界
+        assert_eq!(r.flag(3.9), ReferenceFlag::Normal);
+        assert_eq!(r.flag(6.1), ReferenceFlag::Normal);
+        assert_eq!(r.flag(6.2), ReferenceFlag::High);
+
+        let only_high = ReferenceRange {
+            low: None,
+            ..range(2)
+        };
+        assert_eq!(only_high.flag(-100.0), ReferenceFlag::Normal);
+    }
+
+    #[test]
+    fn test_flag_critical_before_low_high() {
+        let r = ReferenceRange {
+            critical_low: Some(2.8),
+            critical_high: Some(25.0),
+            ..range(1)
+        };
+        // 危急值含边界，且优先于 low / high
+        assert_eq!(r.flag(2.8), ReferenceFlag::Critical);
+        assert_eq!(r.flag(2.9), ReferenceFlag::Low);
+        assert_eq!(r.flag(24.9), ReferenceFlag::High);
+        assert_eq!(r.flag(25.0), ReferenceFlag::Critical);
+    }
+
+    #[test]
+    fn test_select_range_specificity() {
+        let ranges = [
+            range(1),
+            ReferenceRange {
+                sex: Some(Sex::Male),
+                ..range(2)
+            },
+            ReferenceRange {
+                sex: Some(Sex::Male),
+                age_min: Some(18),
+                age_max: Some(65),
+                ..range(3)
+            },
+        ];
+        let id = |sex, age| select_range(&ranges, sex, age).map(|r| r.id.0);
+
+        assert_eq!(id(Some(Sex::Male), Some(30)), Some(3));
+        assert_eq!(id(Some(Sex::Male), Some(70)), Some(2));
+        assert_eq!(id(Some(Sex::Female), Some(30)), Some(1));
+        assert_eq!(id(None, None), Some(1));
+        assert_eq!(select_range(&ranges[1..], None, None), None);
+    }
+
+    #[test]
+    fn test_select_range_tie() {
+        // 同样具体时取 id 较小（先创建）的一条，与列表顺序无关
+        let ranges = [
+            ReferenceRange {
+                sex: Some(Sex::Male),
+                ..range(5)
+            },
+            ReferenceRange {
+                age_min: Some(18),
+                ..range(4)
+            },
+        ];
+        let selected = select_range(&ranges, Some(Sex::Male), Some(30));
+        assert_eq!(selected.map(|r| r.id.0), Some(4));
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(create().check().is_ok());
+
+        let no_limit = CreateReferenceRange {
+            low: None,
+            high: None,
+            critical_low: None,
+            critical_high: None,
+            ..create()
+        };
+        assert!(no_limit.check().is_err());
+
+        let not_finite = CreateReferenceRange {
+            high: Some(f64::INFINITY),
+            ..create()
+        };
+        assert!(not_finite.check().is_err());
+
+        let ages = CreateReferenceRange {
+            age_min: Some(65),
+            age_max: Some(65),
+            ..create()
+        };
+        assert!(ages.check().is_err());
+    }
+
+    #[test]
+    fn test_check_ordering() {
+        let reversed = CreateReferenceRange {
+            low: Some(6.1),
+            high: Some(3.9),
+            ..create()
+        };
+        assert!(reversed.check().is_err());
+
+        let critical_inside = CreateReferenceRange {
+            critical_low: Some(4.0),
+            ..create()
+        };
+        assert!(critical_inside.check().is_err());
+
+        let critical_below_low = CreateReferenceRange {
+            low: None,
+            high: None,
+            critical_high: Some(2.0),
+            ..create()
+        };
+        assert!(critical_below_low.check().is_err());
+
+        // 相等的上下限是允许的
+        let equal = CreateReferenceRange {
+            low: Some(5.0),
+            high: Some(5.0),
+            ..create()
+        };
+        assert!(equal.check().is_ok());
+    }
+
+    #[test]
+    fn test_convert() {
+        let r = ReferenceRange {
+            critical_high: Some(450.0),
+            unit: Some("mg/dL".to_string()),
+            low: Some(70.0),
+            high: Some(110.0),
+            ..range(1)
+        }
+        .convert(
+            UnitConversion {
+                factor: 2.0,
+                offset: 1.0,
+            },
+            None,
+        );
+        assert_eq!(r.low, Some(141.0));
+        assert_eq!(r.high, Some(221.0));
+        assert_eq!(r.critical_low, None);
+        assert_eq!(r.critical_high, Some(901.0));
+        assert_eq!(r.unit, None);
+    }
+}
@@ -0,0 +1,112 @@
+use pg_core::{DbContext, Error, impl_repository};
+use sea_orm::{prelude::*, *};
+use time::OffsetDateTime;
+
+use crate::{
+    Repository, Result,
+    entity::{metric_reference_range, prelude::MetricReferenceRange as ReferenceRangeEntity},
+    table::{
+        metric::dto::MetricId,
+        metric_reference_range::dto::{
+            CreateReferenceRange, ReferenceRange, ReferenceRangeId, Sex,
+        },
+    },
+};
+
+impl_repository!(
+    ReferenceRangeRepo,
+    ReferenceRangeEntity,
+    metric_reference_range::Model
+);
+
+/// ===============================
+/// Service（对外能力）
+/// ===============================
+
+/// ReferenceRange service（指标参考范围，单表）
+pub struct ReferenceRangeService {
+    repo: ReferenceRangeRepo,
+}
+
+impl ReferenceRangeService {
+    /// 创建 service
+    pub fn new(ctx: DbContext) -> Self {
+        Self {
+            repo: ReferenceRangeRepo::new(ctx.clone()),
+        }
+    }
+
+    /// 创建参考范围
+    pub async fn create(&self, input: CreateReferenceRange) -> Result<ReferenceRange> {
+        input.check().map_err(Error::validation)?;
+
+        let active = metric_reference_range::ActiveModel {
+            metric_id: Set(input.metric_id.0),
+            sex: Set(input.sex.map(|s| s.to_string())),
+            age_min: Set(input.age_min.map(to_db_age).transpose()?),
+            age_max: Set(input.age_max.map(to_db_age).transpose()?),
+            low: Set(input.low),
+            high: Set(input.high),
+            critical_low: Set(input.critical_low),
+            critical_high: Set(input.critical_high),
+            unit: Set(input.unit),
+            source: Set(input.source),
+            created_at: Set(OffsetDateTime::now_utc()),
+            ..Default::default()
+        };
+
+        let model = self.repo.insert(active).await?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 根据 ID 获取参考范围
+    pub async fn get(&self, id: ReferenceRangeId) -> Result<Option<ReferenceRange>> {
+        let model = self.repo.find_by_id(id.0).await?;
+        Ok(model.map(Self::from_model))
+    }
+
+    /// 删除参考范围
+    ///
+    /// 返回 false 表示原本就不存在
+    pub async fn delete(&self, id: ReferenceRangeId) -> Result<bool> {
+        let condition = Condition::all().add(metric_reference_range::Column::RangeId.eq(id.0));
+
+        let res = self.repo.delete_many(condition).await?;
+        Ok(res.rows_affected > 0)
+    }
+
+    /// 列出某个指标的全部参考范围（按创建顺序）
+    pub async fn list_by_metric(&self, metric_id: MetricId) -> Result<Vec<ReferenceRange>> {
+        let models = ReferenceRangeEntity::find()
+            .filter(metric_reference_range::Column::MetricId.eq(metric_id.0))
+            .order_by_asc(metric_reference_range::Column::RangeId)
+            .all(self.repo.db())
+            .await?;
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    /// ===============================
+    /// 内部映射
+    /// ===============================
+
+    fn from_model(model: metric_reference_range::Model) -> ReferenceRange {
+        ReferenceRange {
+            id: ReferenceRangeId(model.range_id),
+            metric_id: MetricId(model.metric_id),
+            sex: model.sex.as_deref().and_then(Sex::parse),
+            age_min: model.age_min.and_then(|v| u32::try_from(v).ok()),
+            age_max: model.age_max.and_then(|v| u32::try_from(v).ok()),
+            low: model.low,
+            high: model.high,
+            critical_low: model.critical_low,
+            critical_high: model.critical_high,
+            unit: model.unit,
+            source: model.source,
+            created_at: model.created_at,
+        }
+    }
+}
+
+fn to_db_age(age: u32) -> Result<i32> {
+    i32::try_from(age).map_err(|_| Error::validation("age is out of range"))
+}
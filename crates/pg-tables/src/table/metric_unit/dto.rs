@@ -0,0 +1,112 @@
+use time::OffsetDateTime;
+
+use crate::table::metric::dto::MetricId;
+
+/// 指标的备选单位
+///
+/// 说明：
+/// - `Metric.unit` 是规范单位，观测值一律按规范单位存储
+/// - 每条记录描述一个备选单位到规范单位的换算：`规范值 = 值 * factor + offset`
+/// - 与具体物质相关的换算（例如血糖 mg/dL -> mmol/L 依赖摩尔质量）只能按指标登记
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricUnit {
+    pub id: MetricUnitId,
+    pub metric_id: MetricId,
+    pub unit: String,
+    pub factor: f64,
+    pub offset: f64,
+    pub created_at: OffsetDateTime,
+}
+
+impl MetricUnit {
+    pub fn conversion(&self) -> UnitConversion {
+        UnitConversion {
+            factor: self.factor,
+            offset: self.offset,
+        }
+    }
+}
+
+/// 登记备选单位的输入参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateMetricUnit {
+    pub metric_id: MetricId,
+    pub unit: String,
+    pub conversion: UnitConversion,
+}
+
+/// 仿射换算：`to = from * factor + offset`
+///
+/// factor 必须为正数，保证换算前后大小顺序不变（极值、分位数可以直接换算）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitConversion {
+    pub factor: f64,
+    pub offset: f64,
+}
+
+impl UnitConversion {
+    pub const IDENTITY: UnitConversion = UnitConversion {
+        factor: 1.0,
+        offset: 0.0,
+    };
+
+    /// 纯比例换算
+    pub const fn scale(factor: f64) -> Self {
+        Self {
+            factor,
+            offset: 0.0,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// 换算到目标单位
+    pub fn apply(&self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }
+
+    /// 反向换算
+    pub fn inverse(&self) -> Self {
+        Self {
+            factor: 1.0 / self.factor,
+            offset: -self.offset / self.factor,
+        }
+    }
+
+    /// 校验换算参数
+    pub fn check(&self) -> Result<(), String> {
+        if !self.factor.is_finite() || self.factor <= 0.0 {
+            return Err("factor must be a positive number".to_string());
+        }
+        if !self.offset.is_finite() {
+            return Err("offset must be a finite number".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 两个单位写法是否相同（忽略首尾空白，µ 与 μ 视为相同）
+///
+/// 大小写有意义（mmol/L 与 Mmol/L、mg 与 Mg 不是同一单位），必须完全一致
+pub fn same_unit(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| s.trim().replace('\u{00B5}', "\u{03BC}");
+    normalize(a) == normalize(b)
+}
+
+/// MetricUnit 的强类型 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MetricUnitId(pub i64);
+
+impl From<i64> for MetricUnitId {
+    fn from(value: i64) -> Self {
+        MetricUnitId(value)
+    }
+}
+
+impl From<MetricUnitId> for i64 {
+    fn from(id: MetricUnitId) -> Self {
+        id.0
+    }
+}
@@ -0,0 +1,213 @@
+//! 单位换算注册表
+//!
+//! 与物质无关的通用换算（质量浓度、温度、长度等）在这里静态登记，
+//! 与物质相关的换算登记在 `metric_unit` 表中，按指标生效
+
+use crate::table::metric_unit::dto::{MetricUnit, UnitConversion, same_unit};
+
+/// 通用换算：(from, to, factor, offset)，反向换算自动推导
+const GENERIC: &[(&str, &str, f64, f64)] = &[
+    // 质量浓度
+    ("mg/dL", "g/L", 0.01, 0.0),
+    ("mg/dL", "mg/L", 10.0, 0.0),
+    ("mg/L", "g/L", 0.001, 0.0),
+    ("g/dL", "g/L", 10.0, 0.0),
+    // 物质的量浓度
+    ("μmol/L", "mmol/L", 0.001, 0.0),
+    // 温度：°C = (°F - 32) * 5 / 9
+    ("°F", "°C", 5.0 / 9.0, -160.0 / 9.0),
+    // 长度 / 质量
+    ("m", "cm", 100.0, 0.0),
+    ("mm", "cm", 0.1, 0.0),
+    ("in", "cm", 2.54, 0.0),
+    ("g", "kg", 0.001, 0.0),
+    ("lb", "kg", 0.453_592_37, 0.0),
+    // 压力
+    ("kPa", "mmHg", 7.500_62, 0.0),
+];
+
+/// 质量浓度单位，换算为 g/L 的系数
+const MASS_CONCENTRATION: &[(&str, f64)] = &[
+    ("g/L", 1.0),
+    ("g/dL", 10.0),
+    ("mg/dL", 0.01),
+    ("mg/L", 0.001),
+];
+
+/// 物质的量浓度单位，换算为 mol/L 的系数
+const MOLAR_CONCENTRATION: &[(&str, f64)] = &[("mmol/L", 1e-3), ("μmol/L", 1e-6)];
+
+/// 通用换算（不依赖具体物质）
+pub fn generic_conversion(from: &str, to: &str) -> Option<UnitConversion> {
+    if same_unit(from, to) {
+        return Some(UnitConversion::IDENTITY);
+    }
+
+    GENERIC.iter().find_map(|&(a, b, factor, offset)| {
+        let conversion = UnitConversion { factor, offset };
+        if same_unit(a, from) && same_unit(b, to) {
+            Some(conversion)
+        } else if same_unit(a, to) && same_unit(b, from) {
+            Some(conversion.inverse())
+        } else {
+            None
+        }
+    })
+}
+
+/// 质量浓度与物质的量浓度之间的换算（依赖物质的摩尔质量，单位 g/mol）
+///
+/// 例如葡萄糖（180.16 g/mol）：mg/dL -> mmol/L 的系数为 10 / 180.16
+pub fn molar_conversion(from: &str, to: &str, molar_mass: f64) -> Option<UnitConversion> {
+    if !molar_mass.is_finite() || molar_mass <= 0.0 {
+        return None;
+    }
+    let lookup = |table: &[(&str, f64)], unit: &str| {
+        table
+            .iter()
+            .find(|(u, _)| same_unit(u, unit))
+            .map(|&(_, factor)| factor)
+    };
+
+    // mass(g/L) / molar_mass(g/mol) = molar(mol/L)
+    if let (Some(mass), Some(molar)) = (
+        lookup(MASS_CONCENTRATION, from),
+        lookup(MOLAR_CONCENTRATION, to),
+    ) {
+        return Some(UnitConversion::scale(mass / molar_mass / molar));
+    }
+    if let (Some(molar), Some(mass)) = (
+        lookup(MOLAR_CONCENTRATION, from),
+        lookup(MASS_CONCENTRATION, to),
+    ) {
+        return Some(UnitConversion::scale(molar * molar_mass / mass));
+    }
+    None
+}
+
+/// 把 `unit` 的值换算到指标的规范单位
+///
+/// 依次尝试：与规范单位相同、指标登记的备选单位、通用换算；
+/// 指标没有规范单位或无法换算时返回 None
+pub fn resolve_conversion(
+    canonical: Option<&str>,
+    units: &[MetricUnit],
+    unit: &str,
+) -> Option<UnitConversion> {
+    let canonical = canonical?;
+    if same_unit(canonical, unit) {
+        return Some(UnitConversion::IDENTITY);
+    }
+
+    units
+        .iter()
+        .find(|u| same_unit(&u.unit, unit))
+        .map(MetricUnit::conversion)
+        .or_else(|| generic_conversion(unit, canonical))
+}
+
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use super::*;
+    use crate::table::{metric::dto::MetricId, metric_unit::dto::MetricUnitId};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn registered(unit: &str, factor: f64, offset: f64) -> MetricUnit {
+        MetricUnit {
+            id: MetricUnitId(1),
+            metric_id: MetricId(1),
+            unit: unit.to_string(),
+            factor,
+            offset,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_identity() {
+        let conversion = resolve_conversion(Some("mmol/L"), &[], " mmol/L ").unwrap();
+        assert!(conversion.is_identity());
+        // µ（U+00B5）与 μ（U+03BC）视为同一写法
+        let conversion = resolve_conversion(Some("μmol/L"), &[], "µmol/L").unwrap();
+        assert!(conversion.is_identity());
+    }
+
+    #[test]
+    fn test_factor() {
+        let conversion = resolve_conversion(Some("g/L"), &[], "mg/dL").unwrap();
+        assert_close(conversion.apply(150.0), 1.5);
+        // 反向由表中的正向推导
+        let conversion = resolve_conversion(Some("mg/dL"), &[], "g/L").unwrap();
+        assert_close(conversion.apply(1.5), 150.0);
+    }
+
+    #[test]
+    fn test_offset() {
+        let conversion = resolve_conversion(Some("°C"), &[], "°F").unwrap();
+        assert_close(conversion.apply(32.0), 0.0);
+        assert_close(conversion.apply(212.0), 100.0);
+
+        let conversion = resolve_conversion(Some("°F"), &[], "°C").unwrap();
+        assert_close(conversion.apply(37.0), 98.6);
+    }
+
+    #[test]
+    fn test_inverse_round_trip() {
+        let conversion = UnitConversion {
+            factor: 5.0 / 9.0,
+            offset: -160.0 / 9.0,
+        };
+        let inverse = conversion.inverse();
+        for v in [-40.0, 0.0, 98.6, 1000.0] {
+            assert_close(inverse.apply(conversion.apply(v)), v);
+        }
+    }
+
+    #[test]
+    fn test_registered_unit_first() {
+        // 按指标登记的换算优先于通用换算
+        let units = [registered("mg/dL", 1.0 / 18.0, 0.0)];
+        let conversion = resolve_conversion(Some("mmol/L"), &units, "mg/dL").unwrap();
+        assert_close(conversion.apply(90.0), 5.0);
+        assert_eq!(
+            resolve_conversion(Some("mmol/L"), &[], "mg/dL"),
+            None,
+            "mass to molar concentration needs a registered unit"
+        );
+    }
+
+    #[test]
+    fn test_unknown_unit() {
+        assert_eq!(resolve_conversion(Some("mmol/L"), &[], "IU/L"), None);
+        // 大小写不同视为不同单位
+        assert_eq!(resolve_conversion(Some("mmol/L"), &[], "MMOL/L"), None);
+        assert_eq!(resolve_conversion(Some("kg"), &[], "Mg"), None);
+    }
+
+    #[test]
+    fn test_null_canonical_unit() {
+        let units = [registered("mg/dL", 0.01, 0.0)];
+        assert_eq!(resolve_conversion(None, &units, "mg/dL"), None);
+        assert_eq!(resolve_conversion(None, &[], "g/L"), None);
+    }
+
+    #[test]
+    fn test_molar_conversion() {
+        // 葡萄糖：90 mg/dL ≈ 4.9956 mmol/L
+        let conversion = molar_conversion("mg/dL", "mmol/L", 180.16).unwrap();
+        assert_close(conversion.apply(90.0), 90.0 * 10.0 / 180.16);
+        let back = molar_conversion("mmol/L", "mg/dL", 180.16).unwrap();
+        assert_close(back.apply(conversion.apply(90.0)), 90.0);
+
+        assert_eq!(molar_conversion("mg/dL", "mmol/L", 0.0), None);
+        assert_eq!(molar_conversion("mg/dL", "g/L", 180.16), None);
+    }
+}
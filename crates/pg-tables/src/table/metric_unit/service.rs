@@ -0,0 +1,99 @@
+use pg_core::{DbContext, Error, impl_repository};
+use sea_orm::{prelude::*, *};
+use time::OffsetDateTime;
+
+use crate::{
+    Repository, Result,
+    entity::{metric_unit, prelude::MetricUnit as MetricUnitEntity},
+    table::{
+        metric::dto::MetricId,
+        metric_unit::dto::{CreateMetricUnit, MetricUnit, MetricUnitId, same_unit},
+    },
+};
+
+impl_repository!(MetricUnitRepo, MetricUnitEntity, metric_unit::Model);
+
+/// ===============================
+/// Service（对外能力）
+/// ===============================
+
+/// MetricUnit service（指标的备选单位，单表）
+pub struct MetricUnitService {
+    repo: MetricUnitRepo,
+}
+
+impl MetricUnitService {
+    /// 创建 service
+    pub fn new(ctx: DbContext) -> Self {
+        Self {
+            repo: MetricUnitRepo::new(ctx.clone()),
+        }
+    }
+
+    /// 登记备选单位（同一指标下单位写法不能重复）
+    pub async fn create(&self, input: CreateMetricUnit) -> Result<MetricUnit> {
+        let unit = input.unit.trim().to_string();
+        if unit.is_empty() {
+            return Err(Error::validation("unit must not be empty"));
+        }
+        input.conversion.check().map_err(Error::validation)?;
+
+        let existing = self.list_by_metric(input.metric_id).await?;
+        if existing.iter().any(|u| same_unit(&u.unit, &unit)) {
+            return Err(Error::already_exists("metric_unit", "unit", unit));
+        }
+
+        let active = metric_unit::ActiveModel {
+            metric_id: Set(input.metric_id.0),
+            unit: Set(unit),
+            factor: Set(input.conversion.factor),
+            offset: Set(input.conversion.offset),
+            created_at: Set(OffsetDateTime::now_utc()),
+            ..Default::default()
+        };
+
+        let model = self.repo.insert(active).await?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 根据 ID 获取备选单位
+    pub async fn get(&self, id: MetricUnitId) -> Result<Option<MetricUnit>> {
+        let model = self.repo.find_by_id(id.0).await?;
+        Ok(model.map(Self::from_model))
+    }
+
+    /// 删除备选单位
+    ///
+    /// 返回 false 表示原本就不存在
+    pub async fn delete(&self, id: MetricUnitId) -> Result<bool> {
+        let condition = Condition::all().add(metric_unit::Column::UnitId.eq(id.0));
+
+        let res = self.repo.delete_many(condition).await?;
+        Ok(res.rows_affected > 0)
+    }
+
+    /// 列出某个指标的全部备选单位（按登记顺序）
+    pub async fn list_by_metric(&self, metric_id: MetricId) -> Result<Vec<MetricUnit>> {
+        let models = MetricUnitEntity::find()
+            .filter(metric_unit::Column::MetricId.eq(metric_id.0))
+            .order_by_asc(metric_unit::Column::UnitId)
+            .all(self.repo.db())
+            .await?;
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    /// ===============================
+    /// 内部映射
+    /// ===============================
+
+    fn from_model(model: metric_unit::Model) -> MetricUnit {
+        MetricUnit {
+            id: MetricUnitId(model.unit_id),
+            metric_id: MetricId(model.metric_id),
+            unit: model.unit,
+            factor: model.factor,
+            offset: model.offset,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -1,6 +1,13 @@
 pub mod dto;
+pub mod account;
 pub mod data_source;
+pub mod job;
 pub mod metric;
+pub mod metric_reference_range;
+pub mod metric_unit;
 pub mod observation;
 pub mod recipe;
+pub mod revoked_token;
 pub mod subject;
+pub mod subject_access;
+pub mod subject_profile;
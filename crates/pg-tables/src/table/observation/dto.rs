@@ -1,9 +1,12 @@
 // pg-tables/src/core/observation.rs
 
+use std::{fmt, str::FromStr};
+
 use time::OffsetDateTime;
 
 use crate::table::{
-    data_source::dto::DataSourceId, metric::dto::MetricId, subject::dto::SubjectId,
+    data_source::dto::DataSourceId, metric::dto::MetricId,
+    metric_reference_range::dto::ReferenceFlag, subject::dto::SubjectId,
 };
 
 /// Observation 表示：
@@ -28,6 +31,12 @@ pub struct Observation {
     /// 语义解释必须结合 Metric.value_type
     pub value: ObservationValue,
 
+    /// 数值投影（Integer / Float / Decimal 指标）
+    pub value_num: Option<f64>,
+
+    /// 布尔投影（Boolean 指标）
+    pub value_bool: Option<bool>,
+
     /// 事实发生时间（设备时间 / 业务时间）
     pub observed_at: OffsetDateTime,
 
@@ -36,14 +45,21 @@ pub struct Observation {
 
     /// 数据来源（可选）
     pub source_id: Option<DataSourceId>,
+
+    /// 作废时间（随来源一起撤回；作废后不再参与查询）
+    pub voided_at: Option<OffsetDateTime>,
 }
 
 /// 记录 Observation 的输入参数
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// `value_num` / `value_bool` 由上层按 Metric.value_type 解析后给出
+#[derive(Debug, Clone, PartialEq)]
 pub struct RecordObservation {
     pub subject_id: SubjectId,
     pub metric_id: MetricId,
     pub value: ObservationValue,
+    pub value_num: Option<f64>,
+    pub value_bool: Option<bool>,
     pub observed_at: OffsetDateTime,
     pub source_id: Option<DataSourceId>,
 }
@@ -54,10 +70,16 @@ pub struct ObservationQueryKey {
     pub metric_id: MetricId,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObservationPoint {
     pub value: ObservationValue,
+
+    /// 数值（非数值指标为 None）
+    pub value_num: Option<f64>,
     pub observed_at: OffsetDateTime,
+
+    /// 相对参考范围的判定（没有适用的范围或非数值时为 None）
+    pub flag: Option<ReferenceFlag>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -79,6 +101,154 @@ pub struct ObservationInputs {
     pub inputs: serde_json::Value,
 }
 
+/// 时间桶粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketUnit {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl BucketUnit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BucketUnit::Minute => "minute",
+            BucketUnit::Hour => "hour",
+            BucketUnit::Day => "day",
+            BucketUnit::Week => "week",
+            BucketUnit::Month => "month",
+        }
+    }
+
+    /// 是否为固定时长（可以用 `date_bin` 按步长分桶）
+    ///
+    /// 天 / 周 / 月在有夏令时的时区里不是固定时长，只能按日历 `date_trunc`
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, BucketUnit::Minute | BucketUnit::Hour)
+    }
+}
+
+impl fmt::Display for BucketUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BucketUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minute" => Ok(BucketUnit::Minute),
+            "hour" => Ok(BucketUnit::Hour),
+            "day" => Ok(BucketUnit::Day),
+            "week" => Ok(BucketUnit::Week),
+            "month" => Ok(BucketUnit::Month),
+            other => Err(format!(
+                "invalid bucket '{}', expected one of minute, hour, day, week, month",
+                other
+            )),
+        }
+    }
+}
+
+/// 单个桶的最大步长
+pub const MAX_BUCKET_STEP: u32 = 1000;
+
+/// 单次查询最多的分位数个数
+pub const MAX_PERCENTILES: usize = 10;
+
+/// 时间桶聚合参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct BucketSpec {
+    pub unit: BucketUnit,
+
+    /// 步长（例如 15 分钟），只有 minute / hour 支持大于 1
+    pub step: u32,
+
+    /// 分桶所用的时区（IANA 名称，例如 Asia/Shanghai）
+    pub time_zone: String,
+
+    /// 需要计算的分位数（0 ~ 1）
+    pub percentiles: Vec<f64>,
+}
+
+impl BucketSpec {
+    /// 校验输入，返回第一个不合法的原因
+    pub fn check(&self) -> Result<(), String> {
+        if self.step == 0 || self.step > MAX_BUCKET_STEP {
+            return Err(format!(
+                "bucket step must be between 1 and {}",
+                MAX_BUCKET_STEP
+            ));
+        }
+        if self.step > 1 && !self.unit.is_fixed() {
+            return Err(format!("bucket '{}' does not support a step", self.unit));
+        }
+        if self.time_zone.trim().is_empty() {
+            return Err("time zone must not be empty".to_string());
+        }
+        if self.percentiles.len() > MAX_PERCENTILES {
+            return Err(format!(
+                "at most {} percentiles are allowed",
+                MAX_PERCENTILES
+            ));
+        }
+        if let Some(p) = self.percentiles.iter().find(|p| !(0.0..=1.0).contains(*p)) {
+            return Err(format!("percentile {} must be between 0 and 1", p));
+        }
+        Ok(())
+    }
+}
+
+/// 一个时间桶内的聚合结果（只统计有数值的观测）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservationBucket {
+    /// 桶的起始时间
+    pub bucket_start: OffsetDateTime,
+
+    pub count: i64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub avg: Option<f64>,
+
+    /// 桶内按观测时间最早 / 最晚的值
+    pub first: Option<f64>,
+    pub last: Option<f64>,
+
+    /// 与 `BucketSpec.percentiles` 一一对应
+    pub percentiles: Vec<f64>,
+}
+
+/// 一段时间内的描述性统计（只统计有数值的观测）
+///
+/// 没有观测时 `count` 为 0，其余字段均为 None
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservationStats {
+    pub count: i64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+
+    /// 样本标准差（少于 2 个值时为 None）
+    pub stddev: Option<f64>,
+
+    /// 区间内最早 / 最晚的值及其观测时间
+    pub first: Option<f64>,
+    pub first_at: Option<OffsetDateTime>,
+    pub last: Option<f64>,
+    pub last_at: Option<OffsetDateTime>,
+
+    /// last - first
+    pub change: Option<f64>,
+
+    /// (last - first) / |first|，first 为 0 时为 None
+    pub change_ratio: Option<f64>,
+}
+
 /// Observation 的强类型 ID
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ObservationId(pub i64);
@@ -1,4 +1,4 @@
-use pg_core::{DbContext, OrderBy, impl_repository, query::SelectExt};
+use pg_core::{DbContext, Error, OrderBy, PaginatedResponse, impl_repository, query::SelectExt};
 use sea_orm::{prelude::*, *};
 use time::OffsetDateTime;
 
@@ -7,17 +7,91 @@ use crate::{
     entity::{observation, prelude::Observation as ObservationEntity},
     table::{
         data_source::dto::DataSourceId,
-        dto::Range,
+        dto::{PaginationInput, Range},
         metric::dto::MetricId,
         observation::dto::{
-            Observation, ObservationId, ObservationInputs, ObservationPoint, ObservationQueryKey,
-            ObservationValue, RecordObservation,
+            BucketSpec, Observation, ObservationBucket, ObservationId, ObservationInputs,
+            ObservationPoint, ObservationQueryKey, ObservationStats, ObservationValue,
+            RecordObservation,
         },
         subject::dto::SubjectId,
     },
 };
 
 impl_repository!(ObservationRepo, ObservationEntity, observation::Model);
+
+/// 按时间桶聚合数值观测
+///
+/// - minute / hour 用 `date_bin` 按步长分桶（以时区内的整点为原点）
+/// - day / week / month 用带时区的 `date_trunc` 按日历分桶
+/// - 分位数以 `{0.5,0.9}` 形式的文本传入
+const AGGREGATE_SQL: &str = r#"
+SELECT
+    CASE
+        WHEN $5 IN ('minute', 'hour') THEN
+            date_bin($6::text::interval, observed_at AT TIME ZONE $7, TIMESTAMP '2000-01-01')
+                AT TIME ZONE $7
+        ELSE date_trunc($5, observed_at, $7)
+    END AS bucket_start,
+    COUNT(*) AS count,
+    MIN(value_num) AS min,
+    MAX(value_num) AS max,
+    AVG(value_num) AS avg,
+    (array_agg(value_num ORDER BY observed_at, observation_id))[1] AS first,
+    (array_agg(value_num ORDER BY observed_at DESC, observation_id DESC))[1] AS last,
+    to_jsonb(percentile_cont($8::text::float8[]) WITHIN GROUP (ORDER BY value_num)) AS percentiles
+FROM observation
+WHERE subject_id = $1
+  AND metric_id = $2
+  AND voided_at IS NULL
+  AND value_num IS NOT NULL
+  AND ($3::timestamptz IS NULL OR observed_at >= $3)
+  AND ($4::timestamptz IS NULL OR observed_at <= $4)
+GROUP BY 1
+ORDER BY 1
+"#;
+
+/// 单个 subject / metric 在区间内的描述性统计（一次查询完成）
+const STATS_SQL: &str = r#"
+WITH s AS (
+    SELECT
+        COUNT(*) AS count,
+        MIN(value_num) AS min,
+        MAX(value_num) AS max,
+        AVG(value_num) AS mean,
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY value_num) AS median,
+        stddev_samp(value_num) AS stddev,
+        (array_agg(value_num ORDER BY observed_at, observation_id))[1] AS first,
+        (array_agg(observed_at ORDER BY observed_at, observation_id))[1] AS first_at,
+        (array_agg(value_num ORDER BY observed_at DESC, observation_id DESC))[1] AS last,
+        (array_agg(observed_at ORDER BY observed_at DESC, observation_id DESC))[1] AS last_at
+    FROM observation
+    WHERE subject_id = $1
+      AND metric_id = $2
+      AND voided_at IS NULL
+      AND value_num IS NOT NULL
+      AND ($3::timestamptz IS NULL OR observed_at >= $3)
+      AND ($4::timestamptz IS NULL OR observed_at <= $4)
+)
+SELECT
+    s.*,
+    last - first AS change,
+    (last - first) / NULLIF(abs(first), 0) AS change_ratio
+FROM s
+"#;
+
+/// 每个 metric 最新的一条观测（同一时刻以后写入的为准）
+const LATEST_BY_SUBJECT_SQL: &str = r#"
+SELECT DISTINCT ON (metric_id) *
+FROM observation
+WHERE subject_id = $1
+  AND voided_at IS NULL
+ORDER BY metric_id, observed_at DESC, observation_id DESC
+"#;
+
+const TIME_ZONE_EXISTS_SQL: &str =
+    "SELECT EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1) AS valid";
+
 /// ===============================
 /// Service（对外能力）
 /// ===============================
@@ -42,6 +116,8 @@ impl ObservationService {
             subject_id: Set(input.subject_id.0),
             metric_id: Set(input.metric_id.0),
             value: Set(input.value.0),
+            value_num: Set(input.value_num),
+            value_bool: Set(input.value_bool),
             observed_at: Set(input.observed_at),
             recorded_at: Set(recorded_at),
             source_id: Set(input.source_id.map(|id| id.0)),
@@ -65,7 +141,8 @@ impl ObservationService {
     ) -> Result<Vec<ObservationPoint>> {
         let mut condition = Condition::all()
             .add(observation::Column::SubjectId.eq(key.subject_id.0))
-            .add(observation::Column::MetricId.eq(key.metric_id.0));
+            .add(observation::Column::MetricId.eq(key.metric_id.0))
+            .add(observation::Column::VoidedAt.is_null());
 
         if let Some(from) = range.from {
             condition = condition.add(observation::Column::ObservedAt.gte(from));
@@ -85,7 +162,9 @@ impl ObservationService {
             .into_iter()
             .map(|obs| ObservationPoint {
                 value: obs.value.into(),
+                value_num: obs.value_num,
                 observed_at: obs.observed_at,
+                flag: None,
             })
             .collect())
     }
@@ -104,7 +183,8 @@ impl ObservationService {
 
         let mut condition = Condition::all()
             .add(observation::Column::SubjectId.eq(subject_id.0))
-            .add(observation::Column::MetricId.is_in(metric_ids));
+            .add(observation::Column::MetricId.is_in(metric_ids))
+            .add(observation::Column::VoidedAt.is_null());
 
         if let Some(from) = range.from {
             condition = condition.add(observation::Column::ObservedAt.gte(from));
@@ -144,6 +224,245 @@ impl ObservationService {
             .collect())
     }
 
+    /// 按时间桶聚合数值观测（只统计 value_num 非空的观测）
+    pub async fn aggregate(
+        &self,
+        key: ObservationQueryKey,
+        range: Range<OffsetDateTime>,
+        spec: BucketSpec,
+    ) -> Result<Vec<ObservationBucket>> {
+        spec.check().map_err(Error::validation)?;
+        self.ensure_time_zone(&spec.time_zone).await?;
+
+        #[derive(Debug, FromQueryResult)]
+        struct BucketRow {
+            bucket_start: OffsetDateTime,
+            count: i64,
+            min: Option<f64>,
+            max: Option<f64>,
+            avg: Option<f64>,
+            first: Option<f64>,
+            last: Option<f64>,
+            percentiles: Option<serde_json::Value>,
+        }
+
+        let percentiles = format!(
+            "{{{}}}",
+            spec.percentiles
+                .iter()
+                .map(f64::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            AGGREGATE_SQL,
+            [
+                key.subject_id.0.into(),
+                key.metric_id.0.into(),
+                range.from.into(),
+                range.to.into(),
+                spec.unit.as_str().into(),
+                format!("{} {}", spec.step, spec.unit).into(),
+                spec.time_zone.clone().into(),
+                percentiles.into(),
+            ],
+        );
+
+        let rows = BucketRow::find_by_statement(stmt)
+            .all(self.repo.db())
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ObservationBucket {
+                bucket_start: row.bucket_start,
+                count: row.count,
+                min: row.min,
+                max: row.max,
+                avg: row.avg,
+                first: row.first,
+                last: row.last,
+                percentiles: row
+                    .percentiles
+                    .and_then(|v| serde_json::from_value(v).ok())
+                    .unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// 区间内的描述性统计：数量、极值、均值、中位数、标准差、首尾值与变化量
+    pub async fn stats(
+        &self,
+        key: ObservationQueryKey,
+        range: Range<OffsetDateTime>,
+    ) -> Result<ObservationStats> {
+        #[derive(Debug, FromQueryResult)]
+        struct StatsRow {
+            count: i64,
+            min: Option<f64>,
+            max: Option<f64>,
+            mean: Option<f64>,
+            median: Option<f64>,
+            stddev: Option<f64>,
+            first: Option<f64>,
+            first_at: Option<OffsetDateTime>,
+            last: Option<f64>,
+            last_at: Option<OffsetDateTime>,
+            change: Option<f64>,
+            change_ratio: Option<f64>,
+        }
+
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            STATS_SQL,
+            [
+                key.subject_id.0.into(),
+                key.metric_id.0.into(),
+                range.from.into(),
+                range.to.into(),
+            ],
+        );
+
+        // 聚合查询总会返回一行
+        let row = StatsRow::find_by_statement(stmt)
+            .one(self.repo.db())
+            .await?
+            .ok_or_else(|| Error::internal("stats query returned no rows"))?;
+
+        Ok(ObservationStats {
+            count: row.count,
+            min: row.min,
+            max: row.max,
+            mean: row.mean,
+            median: row.median,
+            stddev: row.stddev,
+            first: row.first,
+            first_at: row.first_at,
+            last: row.last,
+            last_at: row.last_at,
+            change: row.change,
+            change_ratio: row.change_ratio,
+        })
+    }
+
+    /// 校验时区名称（交给数据库判断，避免引入时区库）
+    async fn ensure_time_zone(&self, time_zone: &str) -> Result<()> {
+        if time_zone == "UTC" {
+            return Ok(());
+        }
+
+        #[derive(Debug, FromQueryResult)]
+        struct TimeZoneRow {
+            valid: bool,
+        }
+
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            TIME_ZONE_EXISTS_SQL,
+            [time_zone.into()],
+        );
+        let valid = TimeZoneRow::find_by_statement(stmt)
+            .one(self.repo.db())
+            .await?
+            .is_some_and(|row| row.valid);
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::validation(format!(
+                "unknown time zone '{}'",
+                time_zone
+            )))
+        }
+    }
+
+    /// subject 下每个 metric 最新的一条观测（按 metric_id 排序）
+    pub async fn latest_by_subject(&self, subject_id: SubjectId) -> Result<Vec<Observation>> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            LATEST_BY_SUBJECT_SQL,
+            [subject_id.0.into()],
+        );
+
+        let models = ObservationEntity::find()
+            .from_raw_sql(stmt)
+            .all(self.repo.db())
+            .await?;
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    /// 指定 subject / metric 下最新写入的 Observation ID
+    pub async fn latest_id(&self, key: ObservationQueryKey) -> Result<Option<ObservationId>> {
+        let model = ObservationEntity::find()
+            .filter(observation::Column::SubjectId.eq(key.subject_id.0))
+            .filter(observation::Column::MetricId.eq(key.metric_id.0))
+            .filter(observation::Column::VoidedAt.is_null())
+            .order_by_desc(observation::Column::ObservationId)
+            .one(self.repo.db())
+            .await?;
+        Ok(model.map(|m| ObservationId(m.observation_id)))
+    }
+
+    /// 查询 `after` 之后写入的 Observation（按写入顺序，最多 `limit` 条）
+    ///
+    /// 用于增量拉取 / 实时推送，游标为 observation_id
+    pub async fn list_after(
+        &self,
+        key: ObservationQueryKey,
+        after: Option<ObservationId>,
+        limit: u64,
+    ) -> Result<Vec<Observation>> {
+        let mut query = ObservationEntity::find()
+            .filter(observation::Column::SubjectId.eq(key.subject_id.0))
+            .filter(observation::Column::MetricId.eq(key.metric_id.0))
+            .filter(observation::Column::VoidedAt.is_null());
+
+        if let Some(after) = after {
+            query = query.filter(observation::Column::ObservationId.gt(after.0));
+        }
+
+        let models = query
+            .order_by_asc(observation::Column::ObservationId)
+            .limit(limit)
+            .all(self.repo.db())
+            .await?;
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    /// 分页查询某个来源写入的全部 Observation（含已作废的，按观测时间排序）
+    pub async fn list_by_source(
+        &self,
+        source_id: DataSourceId,
+        pagination: Option<PaginationInput>,
+    ) -> Result<PaginatedResponse<Observation>> {
+        let condition = Condition::all().add(observation::Column::SourceId.eq(source_id.0));
+        let order_by = OrderBy::asc(observation::Column::ObservedAt);
+        let params = pagination.unwrap_or_default().to_params();
+
+        let response = self
+            .repo
+            .find_paginated(Some(condition), &params, Some(&order_by))
+            .await?;
+        Ok(response.map(Self::from_model))
+    }
+
+    /// 某个来源的 Observation 涉及的 subject（去重）
+    pub async fn list_subject_ids_by_source(
+        &self,
+        source_id: DataSourceId,
+    ) -> Result<Vec<SubjectId>> {
+        let ids: Vec<i64> = ObservationEntity::find()
+            .select_only()
+            .column(observation::Column::SubjectId)
+            .distinct()
+            .filter(observation::Column::SourceId.eq(source_id.0))
+            .into_tuple()
+            .all(self.repo.db())
+            .await?;
+        Ok(ids.into_iter().map(SubjectId).collect())
+    }
+
     fn now_utc() -> OffsetDateTime {
         OffsetDateTime::now_utc()
     }
@@ -158,9 +477,12 @@ impl ObservationService {
             subject_id: SubjectId(model.subject_id),
             metric_id: MetricId(model.metric_id),
             value: ObservationValue(model.value),
+            value_num: model.value_num,
+            value_bool: model.value_bool,
             observed_at: model.observed_at,
             recorded_at: model.recorded_at,
             source_id: model.source_id.map(DataSourceId),
+            voided_at: model.voided_at,
         }
     }
 }
@@ -0,0 +1,295 @@
+//! Observation 值的类型化校验与规范化
+//!
+//! 写入前按 `MetricValueType` 解析原始字符串，不合法的值直接拒绝，
+//! 合法的值统一转换为规范形式后再入库（读取端无需再猜测格式）；
+//! 布尔值保留报告上的原始写法，解析结果只写入 `value_bool`
+
+use crate::table::{metric::dto::MetricValueType, observation::dto::ObservationValue};
+
+/// Text 值长度上限（字符数）
+pub const MAX_TEXT_LEN: usize = 500;
+
+/// 布尔值的同义词（比较前先去空白、转小写）
+///
+/// 化验单上常见“阴性 / 阳性”、“+ / -”等写法
+const TRUE_WORDS: &[&str] = &[
+    "true", "yes", "y", "1", "positive", "pos", "+", "(+)", "（+）", "阳性", "是", "有",
+];
+const FALSE_WORDS: &[&str] = &[
+    "false", "no", "n", "0", "negative", "neg", "-", "(-)", "（-）", "阴性", "否", "无",
+];
+
+/// 化验单定性结果的其它写法（markdown 报告解析会原样产出这些值）
+const LAB_TRUE_WORDS: &[&str] = &["弱阳性", "强阳性", "检出", "异常"];
+const LAB_FALSE_WORDS: &[&str] = &["未检出", "未发现", "正常"];
+
+/// 识别为 `value` 的全部布尔写法（小写）
+///
+/// 迁移回填 `value_bool` 时使用的列表必须与此一致
+pub fn boolean_words(value: bool) -> impl Iterator<Item = &'static str> {
+    let (words, lab_words) = if value {
+        (TRUE_WORDS, LAB_TRUE_WORDS)
+    } else {
+        (FALSE_WORDS, LAB_FALSE_WORDS)
+    };
+    words.iter().chain(lab_words).copied()
+}
+
+/// 规范化后的观测值
+///
+/// 除规范字符串外，同时给出写入 `value_num` / `value_bool` 的类型化投影，
+/// 聚合查询直接使用这两列，不再对字符串做类型转换
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedValue {
+    pub value: ObservationValue,
+
+    /// Integer / Float / Decimal 的数值
+    pub value_num: Option<f64>,
+
+    /// Boolean 的布尔值
+    pub value_bool: Option<bool>,
+}
+
+/// 按值类型解析并规范化观测值
+///
+/// - Integer：整数，规范为十进制形式（"+007" -> "7"）
+/// - Float：有限浮点数，规范为最短十进制形式（"1e3" -> "1000"）
+/// - Decimal：定点小数，保留原有的小数位数（"007.50" -> "7.50"），不做舍入；
+///   小数位数超过 `precision`（指标的 `display_precision`）时拒绝
+/// - Boolean：识别同义词，`value` 保留原始写法（去掉首尾空白，例如 "弱阳性"），
+///   解析出的布尔值只写入 `value_bool`
+/// - Text：去掉首尾空白，不能为空且不超过 [`MAX_TEXT_LEN`]
+pub fn normalize_value(
+    value_type: &MetricValueType,
+    raw: &str,
+    precision: Option<u8>,
+) -> Result<NormalizedValue, String> {
+    let input = raw.trim();
+    if input.is_empty() {
+        return Err("value must not be empty".to_string());
+    }
+
+    match value_type {
+        MetricValueType::Integer => {
+            let v = input
+                .parse::<i64>()
+                .map_err(|_| format!("'{}' is not an integer", input))?;
+            Ok(NormalizedValue::num(v.to_string(), v as f64))
+        }
+        MetricValueType::Float => match input.parse::<f64>() {
+            Ok(v) if v.is_finite() => {
+                // -0 与 0 视为同一个值
+                let v = if v == 0.0 { 0.0 } else { v };
+                Ok(NormalizedValue::num(v.to_string(), v))
+            }
+            _ => Err(format!("'{}' is not a number", input)),
+        },
+        MetricValueType::Decimal => {
+            let canonical = normalize_decimal(input)?;
+            let places = canonical.split_once('.').map_or(0, |(_, frac)| frac.len());
+            if let Some(precision) = precision.filter(|p| places > usize::from(*p)) {
+                return Err(format!(
+                    "'{}' has more than {} decimal places",
+                    input, precision
+                ));
+            }
+            let v = canonical
+                .parse::<f64>()
+                .map_err(|_| format!("'{}' is not a decimal number", input))?;
+            Ok(NormalizedValue::num(canonical, v))
+        }
+        MetricValueType::Boolean => {
+            let word = input.to_lowercase();
+            let is = |value: bool| boolean_words(value).any(|w| w == word);
+            if is(true) {
+                Ok(NormalizedValue::bool(input, true))
+            } else if is(false) {
+                Ok(NormalizedValue::bool(input, false))
+            } else {
+                Err(format!("'{}' is not a boolean value", input))
+            }
+        }
+        MetricValueType::Text => {
+            if input.chars().count() > MAX_TEXT_LEN {
+                return Err(format!(
+                    "text value must be at most {} characters",
+                    MAX_TEXT_LEN
+                ));
+            }
+            Ok(NormalizedValue {
+                value: ObservationValue(input.to_string()),
+                value_num: None,
+                value_bool: None,
+            })
+        }
+    }
+}
+
+impl NormalizedValue {
+    fn num(canonical: String, value: f64) -> Self {
+        Self {
+            value: ObservationValue(canonical),
+            value_num: Some(value),
+            value_bool: None,
+        }
+    }
+
+    fn bool(raw: &str, value: bool) -> Self {
+        Self {
+            value: ObservationValue(raw.to_string()),
+            value_num: None,
+            value_bool: Some(value),
+        }
+    }
+}
+
+/// 定点小数：`[+-]digits[.digits]`，去掉整数部分多余的前导 0
+fn normalize_decimal(input: &str) -> Result<String, String> {
+    let invalid = || format!("'{}' is not a decimal number", input);
+
+    let (negative, unsigned) = match input.as_bytes()[0] {
+        b'-' => (true, &input[1..]),
+        b'+' => (false, &input[1..]),
+        _ => (false, input),
+    };
+    let (int_part, frac_part) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if int_part.is_empty()
+        || !all_digits(int_part)
+        || !all_digits(frac_part)
+        || (unsigned.contains('.') && frac_part.is_empty())
+    {
+        return Err(invalid());
+    }
+
+    let frac = frac_part;
+    let int_part = int_part.trim_start_matches('0');
+    let int_part = if int_part.is_empty() { "0" } else { int_part };
+    let is_zero = int_part == "0" && frac.bytes().all(|b| b == b'0');
+
+    let mut out = String::new();
+    if negative && !is_zero {
+        out.push('-');
+    }
+    out.push_str(int_part);
+    if !frac.is_empty() {
+        out.push('.');
+        out.push_str(frac);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonical(value_type: MetricValueType, raw: &str) -> String {
+        normalize_value(&value_type, raw, None).unwrap().value.0
+    }
+
+    #[test]
+    fn test_integer() {
+        let v = normalize_value(&MetricValueType::Integer, " +007 ", None).unwrap();
+        assert_eq!(v.value.as_str(), "7");
+        assert_eq!(v.value_num, Some(7.0));
+        assert_eq!(v.value_bool, None);
+        assert_eq!(canonical(MetricValueType::Integer, "-12"), "-12");
+        assert!(normalize_value(&MetricValueType::Integer, "1.5", None).is_err());
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(canonical(MetricValueType::Float, "1e3"), "1000");
+        assert_eq!(canonical(MetricValueType::Float, "5.60"), "5.6");
+        assert_eq!(canonical(MetricValueType::Float, "-0"), "0");
+        assert!(normalize_value(&MetricValueType::Float, "NaN", None).is_err());
+        assert!(normalize_value(&MetricValueType::Float, "inf", None).is_err());
+        assert!(normalize_value(&MetricValueType::Float, "abc", None).is_err());
+    }
+
+    #[test]
+    fn test_decimal_keeps_places() {
+        let v = normalize_value(&MetricValueType::Decimal, "007.50", None).unwrap();
+        assert_eq!(v.value.as_str(), "7.50");
+        assert_eq!(v.value_num, Some(7.5));
+        assert_eq!(canonical(MetricValueType::Decimal, "0.0123"), "0.0123");
+        assert_eq!(canonical(MetricValueType::Decimal, "+3"), "3");
+        assert_eq!(canonical(MetricValueType::Decimal, "-0.00"), "0.00");
+        assert_eq!(canonical(MetricValueType::Decimal, "-1.25"), "-1.25");
+    }
+
+    #[test]
+    fn test_decimal_precision() {
+        let normalize = |raw| normalize_value(&MetricValueType::Decimal, raw, Some(2));
+        assert_eq!(normalize("5.6").unwrap().value.as_str(), "5.6");
+        assert_eq!(normalize("5.60").unwrap().value.as_str(), "5.60");
+        assert_eq!(normalize("5").unwrap().value.as_str(), "5");
+        // 多出的位数拒绝，不舍入
+        assert!(normalize("5.605").is_err());
+        assert!(normalize_value(&MetricValueType::Decimal, "5.6", Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_decimal_rejects_malformed() {
+        for raw in ["1.", ".5", "1e3", "1,5", "--1", "+", "1.2.3"] {
+            assert!(
+                normalize_value(&MetricValueType::Decimal, raw, None).is_err(),
+                "{raw} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_boolean_words() {
+        for raw in [
+            "true",
+            "YES",
+            "阳性",
+            "弱阳性",
+            "强阳性",
+            "(+)",
+            "检出",
+            "异常",
+        ] {
+            let v = normalize_value(&MetricValueType::Boolean, raw, None).unwrap();
+            assert_eq!(v.value_bool, Some(true), "{raw}");
+            assert_eq!(v.value.as_str(), raw);
+        }
+        for raw in [
+            "false",
+            "No",
+            "阴性",
+            "未检出",
+            "未发现",
+            "正常",
+            "-",
+            "（-）",
+        ] {
+            let v = normalize_value(&MetricValueType::Boolean, raw, None).unwrap();
+            assert_eq!(v.value_bool, Some(false), "{raw}");
+            assert_eq!(v.value.as_str(), raw);
+        }
+        assert!(normalize_value(&MetricValueType::Boolean, "maybe", None).is_err());
+        assert_eq!(canonical(MetricValueType::Boolean, " 弱阳性 "), "弱阳性");
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(canonical(MetricValueType::Text, "  淡黄色 "), "淡黄色");
+        let long = "a".repeat(MAX_TEXT_LEN + 1);
+        assert!(normalize_value(&MetricValueType::Text, &long, None).is_err());
+    }
+
+    #[test]
+    fn test_rejects_empty() {
+        for value_type in [
+            MetricValueType::Integer,
+            MetricValueType::Float,
+            MetricValueType::Decimal,
+            MetricValueType::Boolean,
+            MetricValueType::Text,
+        ] {
+            assert!(normalize_value(&value_type, "   ", None).is_err());
+        }
+    }
+}
@@ -1,8 +1,6 @@
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-
-
 /// Recipe 的强类型 ID
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RecipeId(pub i64);
@@ -29,6 +27,23 @@ pub struct Recipe {
     pub arg_map: serde_json::Value,
     pub expr: serde_json::Value,
     pub created_at: OffsetDateTime,
+
+    /// 同一 metric 下的版本号（从 1 开始）
+    pub version: i32,
+
+    /// 生效起点（含），None 表示不限
+    pub effective_from: Option<OffsetDateTime>,
+
+    /// 生效终点（不含），None 表示当前版本
+    pub effective_to: Option<OffsetDateTime>,
+}
+
+impl Recipe {
+    /// 该版本是否适用于 `at` 时刻的观测
+    pub fn is_effective_at(&self, at: OffsetDateTime) -> bool {
+        self.effective_from.is_none_or(|from| from <= at)
+            && self.effective_to.is_none_or(|to| at < to)
+    }
 }
 
 /// 创建 Recipe 的输入参数
@@ -41,6 +56,15 @@ pub struct CreateRecipe {
     pub expr: serde_json::Value,
 }
 
+/// 发布新版本 Recipe 的输入参数
+///
+/// 新版本从 `effective_from` 起生效，当前版本在同一时刻结束
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishRecipe {
+    pub recipe: CreateRecipe,
+    pub effective_from: OffsetDateTime,
+}
+
 /// 查询 Recipe 的输入参数
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryRecipe {
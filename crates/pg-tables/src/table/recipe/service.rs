@@ -7,7 +7,7 @@ use crate::{
     entity::recipe,
     table::{
         metric::dto::MetricId,
-        recipe::dto::{CreateRecipe, QueryRecipe, Recipe},
+        recipe::dto::{CreateRecipe, PublishRecipe, QueryRecipe, Recipe},
     },
 };
 
@@ -26,21 +26,63 @@ impl RecipeService {
         }
     }
 
-    /// 创建一个新的 Recipe
+    /// 创建一个新的 Recipe（metric 的第 1 个版本，对全部历史生效）
     pub async fn create(&self, input: CreateRecipe) -> Result<Recipe> {
-        let now = OffsetDateTime::now_utc();
+        let metric_id = input.metric_id;
+        let active = Self::new_version(input, 1, None);
 
-        let active = recipe::ActiveModel {
-            metric_id: Set(input.metric_id),
-            deps: Set(input.deps),
-            calc_key: Set(input.calc_key),
-            arg_map: Set(input.arg_map),
-            expr: Set(input.expr),
-            created_at: Set(now),
-            ..Default::default()
-        };
+        // 并发创建同一 metric 的 recipe 时由唯一索引 (metric_id, version) 拒绝
+        let model = active
+            .insert(self.repo.db())
+            .await
+            .map_err(|e| match e.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => {
+                    PgError::already_exists("Recipe", "metric_id", metric_id.to_string())
+                }
+                _ => e.into(),
+            })?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 发布新版本（事务内完成）
+    ///
+    /// 1. 当前版本的 effective_to 设为新版本的 effective_from
+    /// 2. 插入 version + 1，从 effective_from 起生效
+    ///
+    /// 旧版本保留，用于计算其生效区间内的历史数据
+    pub async fn publish(&self, input: PublishRecipe) -> Result<Recipe> {
+        let metric_id = input.recipe.metric_id;
+        let txn = self.repo.db().begin().await?;
+
+        let current = recipe::Entity::find()
+            .filter(recipe::Column::MetricId.eq(metric_id))
+            .filter(recipe::Column::EffectiveTo.is_null())
+            .order_by_desc(recipe::Column::Version)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| PgError::not_found("Recipe", metric_id))?;
+
+        if current
+            .effective_from
+            .is_some_and(|from| from >= input.effective_from)
+        {
+            return Err(PgError::validation(format!(
+                "effective_from must be later than the current version (v{})",
+                current.version
+            )));
+        }
+
+        let version = current.version + 1;
+        let mut closing: recipe::ActiveModel = current.into();
+        closing.effective_to = Set(Some(input.effective_from));
+        closing.update(&txn).await?;
+
+        let model = Self::new_version(input.recipe, version, Some(input.effective_from))
+            .insert(&txn)
+            .await?;
 
-        let model = self.repo.insert(active).await?;
+        txn.commit().await?;
         Ok(Self::from_model(model))
     }
 
@@ -73,18 +115,46 @@ impl RecipeService {
         Ok(models.into_iter().map(Self::from_model).collect())
     }
 
-    /// 根据 metric_id 获取 Recipe
+    /// 根据 metric_id 获取当前版本的 Recipe
     pub async fn get_by_metric_id(&self, metric_id: MetricId) -> Result<Option<Recipe>> {
-        let model = self
-            .repo
-            .select_one(
-                self.repo
-                    .query_filtered(Condition::all().add(recipe::Column::MetricId.eq(metric_id.0))),
-            )
+        let model = recipe::Entity::find()
+            .filter(recipe::Column::MetricId.eq(metric_id.0))
+            .filter(recipe::Column::EffectiveTo.is_null())
+            .order_by_desc(recipe::Column::Version)
+            .one(self.repo.db())
             .await?;
         Ok(model.map(Self::from_model))
     }
 
+    /// 获取 metric 的全部版本（按版本号升序）
+    pub async fn list_by_metric_id(&self, metric_id: MetricId) -> Result<Vec<Recipe>> {
+        let models = recipe::Entity::find()
+            .filter(recipe::Column::MetricId.eq(metric_id.0))
+            .order_by_asc(recipe::Column::Version)
+            .all(self.repo.db())
+            .await?;
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    fn new_version(
+        input: CreateRecipe,
+        version: i32,
+        effective_from: Option<OffsetDateTime>,
+    ) -> recipe::ActiveModel {
+        recipe::ActiveModel {
+            metric_id: Set(input.metric_id),
+            deps: Set(input.deps),
+            calc_key: Set(input.calc_key),
+            arg_map: Set(input.arg_map),
+            expr: Set(input.expr),
+            created_at: Set(OffsetDateTime::now_utc()),
+            version: Set(version),
+            effective_from: Set(effective_from),
+            effective_to: Set(None),
+            ..Default::default()
+        }
+    }
+
     fn from_model(model: recipe::Model) -> Recipe {
         Recipe {
             id: model.recipe_id,
@@ -94,6 +164,9 @@ impl RecipeService {
             arg_map: model.arg_map,
             expr: model.expr,
             created_at: model.created_at,
+            version: model.version,
+            effective_from: model.effective_from,
+            effective_to: model.effective_to,
         }
     }
 }
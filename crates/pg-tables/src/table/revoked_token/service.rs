@@ -0,0 +1,68 @@
+use pg_core::{DbContext, impl_repository};
+use sea_orm::{prelude::*, *};
+use time::OffsetDateTime;
+
+use crate::{
+    Repository, Result,
+    entity::{prelude::RevokedToken as RevokedTokenEntity, revoked_token},
+};
+
+impl_repository!(RevokedTokenRepo, RevokedTokenEntity, revoked_token::Model);
+
+/// 注销 token（重复注销时保持幂等）
+const REVOKE_SQL: &str = r#"
+INSERT INTO revoked_token (token_hash, expires_at, revoked_at)
+VALUES ($1, $2, $3)
+ON CONFLICT (token_hash) DO NOTHING
+RETURNING *
+"#;
+
+/// ===============================
+/// Service（对外能力）
+/// ===============================
+
+/// RevokedToken service（token 注销列表，单表）
+pub struct RevokedTokenService {
+    repo: RevokedTokenRepo,
+}
+
+impl RevokedTokenService {
+    /// 创建 service
+    pub fn new(ctx: DbContext) -> Self {
+        Self {
+            repo: RevokedTokenRepo::new(ctx.clone()),
+        }
+    }
+
+    /// 注销一个 token（按哈希）
+    pub async fn revoke(&self, token_hash: String, expires_at: OffsetDateTime) -> Result<()> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            REVOKE_SQL,
+            [token_hash.into(), expires_at.into(), Self::now_utc().into()],
+        );
+
+        RevokedTokenEntity::find()
+            .from_raw_sql(stmt)
+            .one(self.repo.db())
+            .await?;
+        Ok(())
+    }
+
+    /// token 是否已被注销
+    pub async fn is_revoked(&self, token_hash: &str) -> Result<bool> {
+        self.repo.exists_by_id(token_hash.to_string()).await
+    }
+
+    /// 清理在 `expired_before` 之前过期的记录
+    pub async fn purge_expired(&self, expired_before: OffsetDateTime) -> Result<u64> {
+        let condition = Condition::all().add(revoked_token::Column::ExpiresAt.lt(expired_before));
+
+        let res = self.repo.delete_many(condition).await?;
+        Ok(res.rows_affected)
+    }
+
+    fn now_utc() -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
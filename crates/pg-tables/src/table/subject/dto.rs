@@ -19,6 +19,16 @@ pub struct Subject {
 
     /// 创建时间（用于审计、排序，不参与业务推理）
     pub created_at: OffsetDateTime,
+
+    /// 停用时间（停用后保留历史数据，但不再接受写入）
+    pub deactivated_at: Option<OffsetDateTime>,
+}
+
+impl Subject {
+    /// 是否仍处于可用状态
+    pub fn is_active(&self) -> bool {
+        self.deactivated_at.is_none()
+    }
 }
 
 /// 创建 Subject 的输入参数
@@ -31,6 +41,12 @@ pub struct CreateSubject {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ListSubject {
     pub kind: Option<SubjectKind>,
+
+    /// 限定在这些 Subject 内（None 表示不限制）
+    pub ids: Option<Vec<SubjectId>>,
+
+    /// 是否包含已停用的 Subject
+    pub include_deactivated: bool,
 }
 
 /// Subject 的强类型 ID
@@ -4,10 +4,11 @@ use time::OffsetDateTime;
 
 use crate::{
     Repository, Result,
-    entity::{prelude::Subject as SubjectEntity, subject},
+    entity::{prelude::Subject as SubjectEntity, subject, subject_access},
     table::{
         dto::PaginationInput,
         subject::dto::{CreateSubject, ListSubject, Subject, SubjectId, SubjectKind},
+        subject_access::dto::AccessRole,
     },
 };
 
@@ -35,6 +36,7 @@ impl SubjectService {
         let active = subject::ActiveModel {
             subject_type: Set(input.kind.to_string()),
             created_at: Set(OffsetDateTime::now_utc()),
+            deactivated_at: Set(None),
             ..Default::default()
         };
 
@@ -43,6 +45,37 @@ impl SubjectService {
         Ok(Self::from_model(model))
     }
 
+    /// 创建 Subject，并在同一事务内授予 `owner_id` owner 权限
+    ///
+    /// 授权失败时 Subject 一并回滚，不会留下无人可访问的 Subject
+    pub async fn create_with_owner(&self, input: CreateSubject, owner_id: i64) -> Result<Subject> {
+        let txn = self.repo.db().begin().await?;
+        let now = OffsetDateTime::now_utc();
+
+        let model = subject::ActiveModel {
+            subject_type: Set(input.kind.to_string()),
+            created_at: Set(now),
+            deactivated_at: Set(None),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        subject_access::ActiveModel {
+            subject_id: Set(model.subject_id),
+            user_id: Set(owner_id),
+            role: Set(AccessRole::Owner.as_str().to_string()),
+            granted_by: Set(Some(owner_id)),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(Self::from_model(model))
+    }
+
     /// 根据 ID 获取 Subject
     pub async fn get(&self, id: SubjectId) -> Result<Option<Subject>> {
         let model = self.repo.find_by_id(id.0).await?;
@@ -54,7 +87,25 @@ impl SubjectService {
         self.repo.exists_by_id(id.0).await
     }
 
-    /// 查询 Subject（可选按类型过滤）
+    /// 停用 Subject（幂等：已停用时保留原停用时间）
+    ///
+    /// Subject 不存在时返回 `None`
+    pub async fn deactivate(&self, id: SubjectId) -> Result<Option<Subject>> {
+        let Some(model) = self.repo.find_by_id(id.0).await? else {
+            return Ok(None);
+        };
+        if model.deactivated_at.is_some() {
+            return Ok(Some(Self::from_model(model)));
+        }
+
+        let mut active: subject::ActiveModel = model.into();
+        active.deactivated_at = Set(Some(OffsetDateTime::now_utc()));
+
+        let model = self.repo.update(active).await?;
+        Ok(Some(Self::from_model(model)))
+    }
+
+    /// 查询 Subject（可选按类型 / ID 范围过滤，默认不含已停用）
     pub async fn list(
         &self,
         input: ListSubject,
@@ -68,6 +119,17 @@ impl SubjectService {
             has_condition = true;
         }
 
+        if let Some(ids) = input.ids {
+            let ids: Vec<i64> = ids.into_iter().map(|id| id.0).collect();
+            condition = condition.add(subject::Column::SubjectId.is_in(ids));
+            has_condition = true;
+        }
+
+        if !input.include_deactivated {
+            condition = condition.add(subject::Column::DeactivatedAt.is_null());
+            has_condition = true;
+        }
+
         let condition = if has_condition { Some(condition) } else { None };
         let order_by = OrderBy::desc(subject::Column::CreatedAt);
         let params = pagination.unwrap_or_default().to_params();
@@ -88,6 +150,7 @@ impl SubjectService {
             id: SubjectId(model.subject_id),
             kind: SubjectKind::from(model.subject_type),
             created_at: model.created_at,
+            deactivated_at: model.deactivated_at,
         }
     }
 }
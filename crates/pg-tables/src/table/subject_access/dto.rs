@@ -0,0 +1,138 @@
+use core::fmt;
+
+use time::OffsetDateTime;
+
+use crate::table::subject::dto::SubjectId;
+
+/// SubjectAccess 表示：
+/// 某个用户对某个 Subject 的访问授权
+///
+/// 注意：
+/// - user_id 是鉴权层的用户标识（JWT subject），不是 SubjectId
+/// - 一个用户对一个 Subject 至多一条授权
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubjectAccess {
+    /// 系统内稳定的授权标识
+    pub id: SubjectAccessId,
+
+    /// 被授权访问的 Subject
+    pub subject_id: SubjectId,
+
+    /// 被授权的用户
+    pub user_id: i64,
+
+    /// 授权角色
+    pub role: AccessRole,
+
+    /// 授权人（系统授予时为空）
+    pub granted_by: Option<i64>,
+
+    /// 创建时间
+    pub created_at: OffsetDateTime,
+}
+
+/// 授予访问权限的输入参数（已存在时更新角色）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrantSubjectAccess {
+    pub subject_id: SubjectId,
+    pub user_id: i64,
+    pub role: AccessRole,
+    pub granted_by: Option<i64>,
+}
+
+/// SubjectAccess 的强类型 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubjectAccessId(pub i64);
+
+impl From<i64> for SubjectAccessId {
+    fn from(value: i64) -> Self {
+        SubjectAccessId(value)
+    }
+}
+
+impl From<SubjectAccessId> for i64 {
+    fn from(id: SubjectAccessId) -> Self {
+        id.0
+    }
+}
+
+/// 授权角色
+///
+/// - Owner：读写观测，并可管理授权
+/// - Caregiver（家属）/ Clinician（医生）：读写观测
+/// - ReadOnly：只读
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessRole {
+    Owner,
+    Caregiver,
+    Clinician,
+    ReadOnly,
+}
+
+/// 访问操作的级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessLevel {
+    Read,
+    Write,
+    Manage,
+}
+
+impl AccessLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessLevel::Read => "read",
+            AccessLevel::Write => "write",
+            AccessLevel::Manage => "manage",
+        }
+    }
+}
+
+impl AccessRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessRole::Owner => "owner",
+            AccessRole::Caregiver => "caregiver",
+            AccessRole::Clinician => "clinician",
+            AccessRole::ReadOnly => "read_only",
+        }
+    }
+
+    /// 该角色是否允许指定级别的操作
+    pub fn allows(&self, level: AccessLevel) -> bool {
+        match level {
+            AccessLevel::Read => true,
+            AccessLevel::Write => !matches!(self, AccessRole::ReadOnly),
+            AccessLevel::Manage => matches!(self, AccessRole::Owner),
+        }
+    }
+
+    /// 严格解析（未知角色返回 None）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "owner" => Some(AccessRole::Owner),
+            "caregiver" => Some(AccessRole::Caregiver),
+            "clinician" => Some(AccessRole::Clinician),
+            "read_only" => Some(AccessRole::ReadOnly),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AccessRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for AccessRole {
+    fn from(value: &str) -> Self {
+        // 未知角色按只读处理，避免越权
+        AccessRole::parse(value).unwrap_or(AccessRole::ReadOnly)
+    }
+}
+
+impl From<String> for AccessRole {
+    fn from(value: String) -> Self {
+        AccessRole::from(value.as_str())
+    }
+}
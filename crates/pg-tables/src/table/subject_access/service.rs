@@ -0,0 +1,132 @@
+use pg_core::{DbContext, Error, impl_repository};
+use sea_orm::{prelude::*, *};
+use time::OffsetDateTime;
+
+use crate::{
+    Repository, Result,
+    entity::{prelude::SubjectAccess as SubjectAccessEntity, subject_access},
+    table::{
+        subject::dto::SubjectId,
+        subject_access::dto::{AccessRole, GrantSubjectAccess, SubjectAccess, SubjectAccessId},
+    },
+};
+
+impl_repository!(
+    SubjectAccessRepo,
+    SubjectAccessEntity,
+    subject_access::Model
+);
+
+/// 授权（已存在则更新角色与授权人）
+const GRANT_SQL: &str = r#"
+INSERT INTO subject_access (subject_id, user_id, role, granted_by, created_at)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (subject_id, user_id)
+DO UPDATE SET role = EXCLUDED.role, granted_by = EXCLUDED.granted_by
+RETURNING *
+"#;
+
+/// ===============================
+/// Service（对外能力）
+/// ===============================
+
+/// SubjectAccess service（Subject 访问授权，单表）
+pub struct SubjectAccessService {
+    repo: SubjectAccessRepo,
+}
+
+impl SubjectAccessService {
+    /// 创建 service
+    pub fn new(ctx: DbContext) -> Self {
+        Self {
+            repo: SubjectAccessRepo::new(ctx.clone()),
+        }
+    }
+
+    /// 授予访问权限（已存在时更新角色）
+    pub async fn grant(&self, input: GrantSubjectAccess) -> Result<SubjectAccess> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            GRANT_SQL,
+            [
+                input.subject_id.0.into(),
+                input.user_id.into(),
+                input.role.as_str().into(),
+                input.granted_by.into(),
+                Self::now_utc().into(),
+            ],
+        );
+
+        let model = SubjectAccessEntity::find()
+            .from_raw_sql(stmt)
+            .one(self.repo.db())
+            .await?
+            .ok_or_else(|| Error::internal("grant returned no row"))?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 撤销访问权限
+    ///
+    /// 返回 false 表示原本就没有授权
+    pub async fn revoke(&self, subject_id: SubjectId, user_id: i64) -> Result<bool> {
+        let condition = Condition::all()
+            .add(subject_access::Column::SubjectId.eq(subject_id.0))
+            .add(subject_access::Column::UserId.eq(user_id));
+
+        let res = self.repo.delete_many(condition).await?;
+        Ok(res.rows_affected > 0)
+    }
+
+    /// 查询用户对 Subject 的角色（无授权返回 None）
+    pub async fn get_role(
+        &self,
+        subject_id: SubjectId,
+        user_id: i64,
+    ) -> Result<Option<AccessRole>> {
+        let model = SubjectAccessEntity::find()
+            .filter(subject_access::Column::SubjectId.eq(subject_id.0))
+            .filter(subject_access::Column::UserId.eq(user_id))
+            .one(self.repo.db())
+            .await?;
+        Ok(model.map(|m| AccessRole::from(m.role)))
+    }
+
+    /// 列出某个 Subject 的全部授权
+    pub async fn list_by_subject(&self, subject_id: SubjectId) -> Result<Vec<SubjectAccess>> {
+        let models = SubjectAccessEntity::find()
+            .filter(subject_access::Column::SubjectId.eq(subject_id.0))
+            .order_by_asc(subject_access::Column::AccessId)
+            .all(self.repo.db())
+            .await?;
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    /// 列出某个用户拥有的全部授权
+    pub async fn list_by_user(&self, user_id: i64) -> Result<Vec<SubjectAccess>> {
+        let models = SubjectAccessEntity::find()
+            .filter(subject_access::Column::UserId.eq(user_id))
+            .order_by_asc(subject_access::Column::AccessId)
+            .all(self.repo.db())
+            .await?;
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    fn now_utc() -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+
+    /// ===============================
+    /// 内部映射
+    /// ===============================
+
+    fn from_model(model: subject_access::Model) -> SubjectAccess {
+        SubjectAccess {
+            id: SubjectAccessId(model.access_id),
+            subject_id: SubjectId(model.subject_id),
+            user_id: model.user_id,
+            role: AccessRole::from(model.role),
+            granted_by: model.granted_by,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use time::{Date, OffsetDateTime};
+
+use crate::table::{metric_reference_range::dto::Sex, subject::dto::SubjectId};
+
+/// 显示名称长度上限（字符数）
+pub const MAX_DISPLAY_NAME_LEN: usize = 100;
+
+/// 身高的合理范围（厘米）
+pub const MIN_HEIGHT_CM: f64 = 20.0;
+pub const MAX_HEIGHT_CM: f64 = 300.0;
+
+/// SubjectProfile 表示：
+/// 某个 Subject 的人口学信息
+///
+/// 注意：
+/// - Subject 本身只是身份锚点，人口学信息单独存放
+/// - 每个 Subject 至多一份，所有字段都是可选的
+/// - 用于按性别 / 年龄选择参考范围、计算 eGFR 等公式
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectProfile {
+    pub subject_id: SubjectId,
+
+    /// 显示名称（昵称）
+    pub display_name: Option<String>,
+
+    /// 出生日期
+    pub birth_date: Option<Date>,
+
+    /// 性别
+    pub sex: Option<Sex>,
+
+    /// 身高（厘米）
+    pub height_cm: Option<f64>,
+
+    /// 所在时区（IANA 名称，例如 Asia/Shanghai）
+    pub time_zone: Option<String>,
+
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+/// 保存 SubjectProfile 的输入参数（整体替换，已存在时覆盖）
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSubjectProfile {
+    pub subject_id: SubjectId,
+    pub display_name: Option<String>,
+    pub birth_date: Option<Date>,
+    pub sex: Option<Sex>,
+    pub height_cm: Option<f64>,
+    pub time_zone: Option<String>,
+}
+
+impl SaveSubjectProfile {
+    /// 校验字段取值（时区由数据库校验）
+    pub fn check(&self, today: Date) -> Result<(), String> {
+        if self
+            .display_name
+            .as_deref()
+            .is_some_and(|n| n.chars().count() > MAX_DISPLAY_NAME_LEN)
+        {
+            return Err(format!(
+                "display_name must be at most {} characters",
+                MAX_DISPLAY_NAME_LEN
+            ));
+        }
+        if self.birth_date.is_some_and(|d| d > today) {
+            return Err("birth_date must not be in the future".to_string());
+        }
+        if self
+            .height_cm
+            .is_some_and(|h| !(MIN_HEIGHT_CM..=MAX_HEIGHT_CM).contains(&h))
+        {
+            return Err(format!(
+                "height_cm must be between {} and {}",
+                MIN_HEIGHT_CM, MAX_HEIGHT_CM
+            ));
+        }
+        Ok(())
+    }
+}
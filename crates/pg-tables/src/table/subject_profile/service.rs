@@ -0,0 +1,158 @@
+use pg_core::{DbContext, Error, impl_repository};
+use sea_orm::{prelude::*, *};
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::{
+    Repository, Result,
+    entity::{prelude::SubjectProfile as SubjectProfileEntity, subject_profile},
+    table::{
+        metric_reference_range::dto::Sex,
+        subject::dto::SubjectId,
+        subject_profile::dto::{SaveSubjectProfile, SubjectProfile},
+    },
+};
+
+impl_repository!(
+    SubjectProfileRepo,
+    SubjectProfileEntity,
+    subject_profile::Model
+);
+
+/// 保存（已存在则整体覆盖，保留创建时间）
+const SAVE_SQL: &str = r#"
+INSERT INTO subject_profile
+    (subject_id, display_name, birth_date, sex, height_cm, time_zone, created_at, updated_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $7)
+ON CONFLICT (subject_id)
+DO UPDATE SET
+    display_name = EXCLUDED.display_name,
+    birth_date = EXCLUDED.birth_date,
+    sex = EXCLUDED.sex,
+    height_cm = EXCLUDED.height_cm,
+    time_zone = EXCLUDED.time_zone,
+    updated_at = EXCLUDED.updated_at
+RETURNING *
+"#;
+
+const TIME_ZONE_EXISTS_SQL: &str =
+    "SELECT EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1) AS valid";
+
+/// 时区当前的 UTC 偏移（秒）
+const UTC_OFFSET_SQL: &str = r#"
+SELECT EXTRACT(EPOCH FROM (now() AT TIME ZONE $1) - (now() AT TIME ZONE 'UTC'))::int4 AS seconds
+"#;
+
+/// ===============================
+/// Service（对外能力）
+/// ===============================
+
+/// SubjectProfile service（Subject 人口学信息，单表）
+pub struct SubjectProfileService {
+    repo: SubjectProfileRepo,
+}
+
+impl SubjectProfileService {
+    /// 创建 service
+    pub fn new(ctx: DbContext) -> Self {
+        Self {
+            repo: SubjectProfileRepo::new(ctx.clone()),
+        }
+    }
+
+    /// 获取 Subject 的 profile（未填写时返回 None）
+    pub async fn get(&self, subject_id: SubjectId) -> Result<Option<SubjectProfile>> {
+        let model = self.repo.find_by_id(subject_id.0).await?;
+        Ok(model.map(Self::from_model))
+    }
+
+    /// 保存 profile（整体替换）
+    pub async fn save(&self, input: SaveSubjectProfile) -> Result<SubjectProfile> {
+        let now = OffsetDateTime::now_utc();
+        input.check(now.date()).map_err(Error::validation)?;
+        if let Some(time_zone) = input.time_zone.as_deref() {
+            self.ensure_time_zone(time_zone).await?;
+        }
+
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            SAVE_SQL,
+            [
+                input.subject_id.0.into(),
+                input.display_name.into(),
+                input.birth_date.into(),
+                input.sex.map(|s| s.to_string()).into(),
+                input.height_cm.into(),
+                input.time_zone.into(),
+                now.into(),
+            ],
+        );
+
+        let model = SubjectProfileEntity::find()
+            .from_raw_sql(stmt)
+            .one(self.repo.db())
+            .await?
+            .ok_or_else(|| Error::internal("save profile returned no row"))?;
+        Ok(Self::from_model(model))
+    }
+
+    /// 时区当前的 UTC 偏移（同样交给数据库换算）
+    pub async fn utc_offset(&self, time_zone: &str) -> Result<UtcOffset> {
+        #[derive(Debug, FromQueryResult)]
+        struct OffsetRow {
+            seconds: i32,
+        }
+
+        let stmt =
+            Statement::from_sql_and_values(DbBackend::Postgres, UTC_OFFSET_SQL, [time_zone.into()]);
+        let row = OffsetRow::find_by_statement(stmt)
+            .one(self.repo.db())
+            .await?
+            .ok_or_else(|| Error::internal("utc offset query returned no row"))?;
+        UtcOffset::from_whole_seconds(row.seconds)
+            .map_err(|e| Error::internal(format!("invalid utc offset of '{}': {}", time_zone, e)))
+    }
+
+    /// 校验时区名称（交给数据库判断，避免引入时区库）
+    async fn ensure_time_zone(&self, time_zone: &str) -> Result<()> {
+        #[derive(Debug, FromQueryResult)]
+        struct TimeZoneRow {
+            valid: bool,
+        }
+
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            TIME_ZONE_EXISTS_SQL,
+            [time_zone.into()],
+        );
+        let valid = TimeZoneRow::find_by_statement(stmt)
+            .one(self.repo.db())
+            .await?
+            .is_some_and(|row| row.valid);
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::validation(format!(
+                "unknown time zone '{}'",
+                time_zone
+            )))
+        }
+    }
+
+    /// ===============================
+    /// 内部映射
+    /// ===============================
+
+    fn from_model(model: subject_profile::Model) -> SubjectProfile {
+        SubjectProfile {
+            subject_id: SubjectId(model.subject_id),
+            display_name: model.display_name,
+            birth_date: model.birth_date,
+            sex: model.sex.as_deref().and_then(Sex::parse),
+            height_cm: model.height_cm,
+            time_zone: model.time_zone,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use demo_db::{AccessRole, dto::access::SubjectAccessResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    dto::medical::format_rfc3339_utc,
+    error::{Error, Result},
+};
+
+// =========================
+// Subject Access
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GrantAccessRequest {
+    /// 被授权的用户 ID
+    pub user_id: i64,
+
+    /// 角色：owner / caregiver / clinician / read_only
+    pub role: String,
+}
+
+impl GrantAccessRequest {
+    pub fn parse_role(&self) -> Result<AccessRole> {
+        AccessRole::parse(self.role.trim()).ok_or_else(|| {
+            Error::Custom(format!(
+                "invalid role '{}', expected one of owner, caregiver, clinician, read_only",
+                self.role
+            ))
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubjectAccessDto {
+    pub subject_id: i64,
+    pub user_id: i64,
+    pub role: String,
+    pub granted_by: Option<i64>,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+}
+
+impl From<SubjectAccessResponse> for SubjectAccessDto {
+    fn from(access: SubjectAccessResponse) -> Self {
+        Self {
+            subject_id: access.subject_id.0,
+            user_id: access.user_id,
+            role: access.role.to_string(),
+            granted_by: access.granted_by,
+            created_at: format_rfc3339_utc(access.created_at),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListSubjectAccessResponse {
+    pub grants: Vec<SubjectAccessDto>,
+}
@@ -0,0 +1,64 @@
+use demo_db::dto::account::AccountResponse;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// =========================
+// Auth
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterRequest {
+    /// 登录名
+    pub username: String,
+
+    /// 密码（至少 8 位）
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LogoutRequest {
+    /// 同时注销的 refresh token（可选）
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountDto {
+    /// 账号 ID（即鉴权层的 user_id）
+    pub user_id: i64,
+    pub username: String,
+    /// 账号本人对应的 Subject
+    pub subject_id: i64,
+    pub roles: Vec<String>,
+}
+
+impl From<AccountResponse> for AccountDto {
+    fn from(account: AccountResponse) -> Self {
+        Self {
+            user_id: account.id.0,
+            username: account.username,
+            subject_id: account.subject_id.0,
+            roles: account.roles,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    /// `/auth/refresh` 时不返回
+    pub refresh_token: Option<String>,
+    /// 固定为 `Bearer`
+    pub token_type: String,
+    pub account: AccountDto,
+}
@@ -0,0 +1,163 @@
+use demo_db::{
+    PaginationInput,
+    dto::data_source::{
+        DataSourceResponse, UpdateDataSourceRequest as UpdateDataSource, VoidDataSourceResponse,
+    },
+};
+use pg_tables::table::observation::dto::Observation;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    dto::medical::format_rfc3339_utc,
+    error::{Error, Result},
+};
+
+// =========================
+// Data Sources
+// =========================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DataSourceDto {
+    pub source_id: i64,
+    /// device / manual / import / system
+    pub kind: String,
+    pub name: String,
+    pub metadata: Option<JsonValue>,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+    pub voided: bool,
+    /// RFC3339 (UTC)，未作废时为 null
+    pub voided_at: Option<String>,
+    pub void_reason: Option<String>,
+    /// 共享来源按 kind + name 复用；上传的报告等为一次性来源
+    pub shared: bool,
+}
+
+impl From<DataSourceResponse> for DataSourceDto {
+    fn from(source: DataSourceResponse) -> Self {
+        Self {
+            source_id: source.id.0,
+            voided: source.is_voided(),
+            kind: source.kind.to_string(),
+            name: source.name,
+            metadata: source.metadata,
+            created_at: format_rfc3339_utc(source.created_at),
+            voided_at: source.voided_at.map(format_rfc3339_utc),
+            void_reason: source.void_reason,
+            shared: source.shared,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateDataSourceRequest {
+    /// 新名称（不传表示不修改）
+    pub name: Option<String>,
+
+    /// 新元信息，必须是 JSON 对象（不传表示不修改，`{}` 表示清空）
+    pub metadata: Option<JsonValue>,
+}
+
+impl UpdateDataSourceRequest {
+    pub fn to_internal(self) -> Result<UpdateDataSource> {
+        let metadata = match self.metadata {
+            None => None,
+            Some(JsonValue::Object(obj)) if obj.is_empty() => Some(None),
+            Some(value @ JsonValue::Object(_)) => Some(Some(value)),
+            Some(_) => {
+                return Err(Error::Custom("metadata must be a JSON object".to_string()));
+            }
+        };
+
+        Ok(UpdateDataSource {
+            name: self.name.map(|n| n.trim().to_string()),
+            metadata,
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct VoidDataSourceRequest {
+    /// 作废原因（例如“上传了别人的报告”）
+    pub reason: Option<String>,
+}
+
+impl VoidDataSourceRequest {
+    pub fn to_internal(self) -> Option<String> {
+        self.reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty())
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VoidDataSourceResultDto {
+    pub source: DataSourceDto,
+    /// 本次一并作废的观测数量（重复作废时为 0）
+    pub voided_observations: u64,
+}
+
+impl From<VoidDataSourceResponse> for VoidDataSourceResultDto {
+    fn from(result: VoidDataSourceResponse) -> Self {
+        Self {
+            source: result.source.into(),
+            voided_observations: result.voided_observations,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListSourceObservationsParams {
+    /// 页码（从 1 开始，默认 1）
+    pub page: Option<u64>,
+
+    /// 每页数量（默认 20）
+    pub limit: Option<u64>,
+}
+
+impl ListSourceObservationsParams {
+    pub fn to_internal(self) -> PaginationInput {
+        let default = PaginationInput::default();
+        PaginationInput {
+            page: self.page.unwrap_or(default.page),
+            limit: self.limit.unwrap_or(default.limit),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SourceObservationDto {
+    pub observation_id: i64,
+    pub subject_id: i64,
+    pub metric_id: i64,
+    pub value: String,
+    /// RFC3339 (UTC)
+    pub observed_at: String,
+    /// RFC3339 (UTC)
+    pub recorded_at: String,
+    pub voided: bool,
+}
+
+impl From<Observation> for SourceObservationDto {
+    fn from(obs: Observation) -> Self {
+        Self {
+            observation_id: obs.id.0,
+            subject_id: obs.subject_id.0,
+            metric_id: obs.metric_id.0,
+            value: obs.value.0,
+            observed_at: format_rfc3339_utc(obs.observed_at),
+            recorded_at: format_rfc3339_utc(obs.recorded_at),
+            voided: obs.voided_at.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListSourceObservationsResponse {
+    pub observations: Vec<SourceObservationDto>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total: u64,
+}
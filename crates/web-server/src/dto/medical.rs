@@ -1,5 +1,11 @@
 use demo_db::{
-    CreateDataSource, DataSourceKind, MetricId, ObservationValue, SubjectId, dto::base::Range,
+    BucketSpec, BucketUnit, DataSourceKind, JobStatus, ListJobs, MetricId, ObservationValue,
+    PaginationInput, ResolveDataSource, SubjectId,
+    dto::{base::Range, medical::LatestMetricValue},
+};
+use pg_tables::table::{
+    metric::dto::MetricSummary,
+    observation::dto::{ObservationBucket, ObservationStats},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -8,7 +14,7 @@ use utoipa::{IntoParams, ToSchema};
 
 use crate::error::{Error, Result};
 
-fn parse_rfc3339(input: &str) -> Result<OffsetDateTime> {
+pub fn parse_rfc3339(input: &str) -> Result<OffsetDateTime> {
     let dt = OffsetDateTime::parse(input.trim(), &time::format_description::well_known::Rfc3339)
         .map_err(|_| {
             Error::Custom(
@@ -59,9 +65,64 @@ pub struct QueryObservationParams {
 
     /// 查询结束时间（RFC3339, 例如：2025-12-30T10:02:43.893518Z）
     pub end_at: Option<String>,
+
+    /// 时间桶：minute / hour / day / week / month（不传时返回原始数据点）
+    pub bucket: Option<String>,
+
+    /// 桶步长（仅 minute / hour 支持，默认 1，例如 15 分钟）
+    pub bucket_step: Option<u32>,
+
+    /// 分桶时区（IANA 名称，默认 UTC，例如 Asia/Shanghai）
+    pub tz: Option<String>,
+
+    /// 需要计算的分位数，逗号分隔（例如 0.5,0.9）
+    pub percentiles: Option<String>,
+
+    /// 最多返回的数据点数（LTTB 降采样，仅对折线图指标生效，最少 3）
+    pub max_points: Option<u32>,
+
+    /// 返回值换算到的单位（不传时为指标的规范单位，例如 mg/dL）
+    pub unit: Option<String>,
 }
 
 impl QueryObservationParams {
+    /// 解析分桶参数（未指定 bucket 时返回 None）
+    pub fn bucket_spec(&self) -> Result<Option<BucketSpec>> {
+        let Some(bucket) = self.bucket.as_deref() else {
+            if self.bucket_step.is_some() || self.tz.is_some() || self.percentiles.is_some() {
+                return Err(Error::Custom(
+                    "bucket_step, tz and percentiles require bucket".to_string(),
+                ));
+            }
+            return Ok(None);
+        };
+
+        let unit: BucketUnit = bucket.trim().parse().map_err(Error::Custom)?;
+        let percentiles = match self.percentiles.as_deref() {
+            None => Vec::new(),
+            Some(list) => list
+                .split(',')
+                .map(|p| {
+                    p.trim()
+                        .parse::<f64>()
+                        .map_err(|_| Error::Custom(format!("invalid percentile '{}'", p.trim())))
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+
+        Ok(Some(BucketSpec {
+            unit,
+            step: self.bucket_step.unwrap_or(1),
+            time_zone: self
+                .tz
+                .as_deref()
+                .map(str::trim)
+                .unwrap_or("UTC")
+                .to_string(),
+            percentiles,
+        }))
+    }
+
     pub fn to_internal(
         self,
     ) -> Result<(
@@ -98,6 +159,8 @@ pub struct ObservationPointDto {
     pub value_num: Option<f64>,
     /// RFC3339 (UTC), 例如：2025-12-30T10:02:43.893518Z
     pub observed_at: String,
+    /// 相对参考范围：low / normal / high / critical（没有适用的范围时为 null）
+    pub flag: Option<String>,
 }
 
 // =========================
@@ -118,8 +181,11 @@ pub struct RecordObservationRequest {
     /// 观测发生的时间（RFC3339, 例如：2025-12-30T10:02:43.893518Z）
     pub observed_at: String,
 
-    /// 数据来源信息
-    pub source: SourceInput,
+    /// 观测值的单位（可选，与指标单位不同时写入前换算为指标单位）
+    pub unit: Option<String>,
+
+    /// 数据来源信息（可选，按 kind + name 复用已有来源）
+    pub source: Option<SourceInput>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -130,7 +196,7 @@ pub struct SourceInput {
     /// 来源名称
     pub name: String,
 
-    /// 可选元数据
+    /// 可选元数据（仅首次创建来源时写入）
     pub metadata: Option<JsonValue>,
 }
 
@@ -142,21 +208,23 @@ impl RecordObservationRequest {
         MetricId,
         ObservationValue,
         OffsetDateTime,
-        CreateDataSource,
+        Option<String>,
+        Option<ResolveDataSource>,
     )> {
         let observed_at = parse_rfc3339(&self.observed_at)?;
 
-        let source = CreateDataSource {
-            kind: DataSourceKind::from(self.source.kind.as_str()),
-            name: self.source.name,
-            metadata: self.source.metadata,
-        };
+        let source = self.source.map(|source| ResolveDataSource {
+            kind: DataSourceKind::from(source.kind.as_str()),
+            name: source.name,
+            metadata: source.metadata,
+        });
 
         Ok((
             SubjectId(self.subject_id),
             MetricId(self.metric_id),
             ObservationValue(self.value),
             observed_at,
+            self.unit,
             source,
         ))
     }
@@ -165,14 +233,50 @@ impl RecordObservationRequest {
 #[derive(Debug, Serialize, ToSchema)]
 pub struct RecordObservationResponse {
     pub observation_id: i64,
-    pub source_id: i64,
+    /// 实际关联的来源 ID（未指定来源时为 null）
+    pub source_id: Option<i64>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct QueryRecipeObservationResponse {
     pub subject_id: i64,
     pub metric: MetricSummaryDto,
+    /// 原始数据点；分桶查询时为每个桶的平均值（时间为桶起点）
     pub points: Vec<ObservationPointDto>,
+    /// 分桶查询时的聚合结果
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buckets: Option<Vec<ObservationBucketDto>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ObservationBucketDto {
+    /// 桶起点，RFC3339 (UTC)
+    pub bucket_start: String,
+    pub count: i64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub avg: Option<f64>,
+    /// 桶内最早的值
+    pub first: Option<f64>,
+    /// 桶内最晚的值
+    pub last: Option<f64>,
+    /// 与请求的 percentiles 一一对应
+    pub percentiles: Vec<f64>,
+}
+
+impl From<ObservationBucket> for ObservationBucketDto {
+    fn from(bucket: ObservationBucket) -> Self {
+        Self {
+            bucket_start: format_rfc3339_utc(bucket.bucket_start),
+            count: bucket.count,
+            min: bucket.min,
+            max: bucket.max,
+            avg: bucket.avg,
+            first: bucket.first,
+            last: bucket.last,
+            percentiles: bucket.percentiles,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -183,13 +287,32 @@ pub struct MetricSummaryDto {
     pub unit: Option<String>,
     pub value_type: String,
     pub visualization: String,
+    /// active / deprecated
+    pub status: String,
+    /// 数值展示的小数位数
+    pub display_precision: Option<u8>,
+}
+
+impl From<MetricSummary> for MetricSummaryDto {
+    fn from(metric: MetricSummary) -> Self {
+        Self {
+            id: metric.id,
+            metric_code: metric.metric_code.0,
+            metric_name: metric.metric_name,
+            unit: metric.unit,
+            value_type: metric.value_type.to_string(),
+            visualization: metric.visualization.to_string(),
+            status: metric.status.to_string(),
+            display_precision: metric.display_precision,
+        }
+    }
 }
 
 // =========================
 // Upload Markdown Data Source
 // =========================
 
-#[derive(Debug, Deserialize, ToSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UploadMarkdownRequest {
     /// subject 全局 ID
     pub subject_id: i64,
@@ -204,7 +327,7 @@ pub struct UploadMarkdownRequest {
     pub file_content: String,
 }
 
-#[derive(Debug, Serialize, ToSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UploadMarkdownResponse {
     pub source_id: i64,
     pub source_type: String,
@@ -214,17 +337,284 @@ pub struct UploadMarkdownResponse {
     pub created_at: String,
     /// 插入的观测记录数
     pub records_inserted: usize,
+    /// 单位无法换算而跳过的指标
+    #[serde(default)]
+    pub skipped: Vec<SkippedValueDto>,
+}
+
+/// 报告中因单位无法换算而未写入的指标
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct SkippedValueDto {
+    pub metric_id: i64,
+    /// 报告上的单位
+    pub unit: String,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UploadMarkdownTaskResponse {
-    pub task_id: String,
+    /// 持久化任务 ID
+    pub task_id: i64,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TaskStatusResponse {
-    pub task_id: String,
+    pub task_id: i64,
+    /// 任务所属 subject
+    pub subject_id: Option<i64>,
+    /// pending / running / succeeded / failed / cancelled
     pub status: String,
+    /// 已尝试次数（失败会自动重试）
+    pub attempts: i32,
+    /// 执行进度（执行中持续更新）
+    pub progress: Option<MarkdownTaskProgress>,
     pub result: Option<UploadMarkdownResponse>,
     pub error: Option<String>,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+    /// RFC3339 (UTC)
+    pub updated_at: String,
+}
+
+/// Markdown 入库任务的执行进度
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Default)]
+pub struct MarkdownTaskProgress {
+    /// 当前步骤：parsing / matching_metrics / saving_source / inserting_observations / completed
+    pub step: String,
+
+    /// 在报告中匹配到的指标数
+    pub metrics_matched: usize,
+
+    /// 已插入的观测记录数
+    pub rows_inserted: usize,
+}
+
+// =========================
+// List Tasks
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListTasksParams {
+    /// subject 全局 ID（必填，需要读权限）
+    pub subject_id: i64,
+
+    /// 按状态过滤（pending / running / succeeded / failed / cancelled）
+    pub status: Option<String>,
+
+    /// 页码（从 1 开始，默认 1）
+    pub page: Option<u64>,
+
+    /// 每页数量（默认 20）
+    pub limit: Option<u64>,
+}
+
+impl ListTasksParams {
+    pub fn to_internal(self, queue: &str) -> Result<(ListJobs, PaginationInput)> {
+        let status = match self.status.as_deref() {
+            None => None,
+            Some(s) => Some(parse_task_status(s)?),
+        };
+
+        let default = PaginationInput::default();
+        let pagination = PaginationInput {
+            page: self.page.unwrap_or(default.page),
+            limit: self.limit.unwrap_or(default.limit),
+        };
+
+        let input = ListJobs {
+            queue: Some(queue.to_string()),
+            subject_id: Some(self.subject_id),
+            status,
+        };
+
+        Ok((input, pagination))
+    }
+}
+
+fn parse_task_status(input: &str) -> Result<JobStatus> {
+    let status = JobStatus::from(input.trim());
+    if status.as_str() != input.trim() {
+        return Err(Error::Custom(format!(
+            "invalid task status '{}', expected one of pending, running, succeeded, failed, cancelled",
+            input
+        )));
+    }
+    Ok(status)
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListTasksResponse {
+    pub tasks: Vec<TaskStatusResponse>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total: u64,
+}
+
+// =========================
+// Live Updates (SSE)
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct StreamObservationParams {
+    /// subject 全局 ID
+    pub subject_id: i64,
+
+    /// metric 全局 ID（仅支持 Primitive 指标）
+    pub metric_id: i64,
+
+    /// 从该观测 ID 之后开始推送（不传则只推送连接建立后的新数据）
+    ///
+    /// 断线重连时浏览器会自动携带 `Last-Event-ID`，优先使用该请求头
+    pub after_id: Option<i64>,
+}
+
+impl StreamObservationParams {
+    pub fn to_internal(&self) -> demo_db::dto::medical::QueryObservationRequest {
+        demo_db::dto::medical::QueryObservationRequest {
+            subject_id: SubjectId(self.subject_id),
+            metric_id: MetricId(self.metric_id),
+        }
+    }
+}
+
+/// `observation` 事件的数据（事件 id 为 observation_id）
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ObservationEventDto {
+    pub observation_id: i64,
+    pub subject_id: i64,
+    pub metric_id: i64,
+    pub value: String,
+    pub value_num: Option<f64>,
+    /// RFC3339 (UTC)
+    pub observed_at: String,
+    /// RFC3339 (UTC)
+    pub recorded_at: String,
+}
+
+// =========================
+// Observation Stats
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ObservationStatsParams {
+    /// subject 全局 ID
+    pub subject_id: i64,
+
+    /// metric 全局 ID（仅支持数值类型的 Primitive 指标）
+    pub metric_id: i64,
+
+    /// 统计起始时间（RFC3339，不传则从最早的观测开始）
+    pub start_at: Option<String>,
+
+    /// 统计结束时间（RFC3339，不传则到当前时间）
+    pub end_at: Option<String>,
+}
+
+impl ObservationStatsParams {
+    pub fn to_internal(
+        self,
+    ) -> Result<(
+        demo_db::dto::medical::QueryObservationRequest,
+        Range<OffsetDateTime>,
+    )> {
+        let from = self.start_at.as_deref().map(parse_rfc3339).transpose()?;
+        let to = match self.end_at {
+            Some(ref s) => parse_rfc3339(s)?,
+            None => OffsetDateTime::now_utc(),
+        };
+
+        let query = demo_db::dto::medical::QueryObservationRequest {
+            subject_id: SubjectId(self.subject_id),
+            metric_id: MetricId(self.metric_id),
+        };
+
+        Ok((query, Range { from, to: Some(to) }))
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ObservationStatsDto {
+    /// 参与统计的数值观测数量
+    pub count: i64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    /// 样本标准差（少于 2 个值时为 null）
+    pub stddev: Option<f64>,
+    /// 区间内最早的值
+    pub first: Option<f64>,
+    /// RFC3339 (UTC)
+    pub first_at: Option<String>,
+    /// 区间内最晚的值
+    pub last: Option<f64>,
+    /// RFC3339 (UTC)
+    pub last_at: Option<String>,
+    /// last - first
+    pub change: Option<f64>,
+    /// (last - first) / |first|，first 为 0 时为 null
+    pub change_ratio: Option<f64>,
+}
+
+impl From<ObservationStats> for ObservationStatsDto {
+    fn from(stats: ObservationStats) -> Self {
+        Self {
+            count: stats.count,
+            min: stats.min,
+            max: stats.max,
+            mean: stats.mean,
+            median: stats.median,
+            stddev: stats.stddev,
+            first: stats.first,
+            first_at: stats.first_at.map(format_rfc3339_utc),
+            last: stats.last,
+            last_at: stats.last_at.map(format_rfc3339_utc),
+            change: stats.change,
+            change_ratio: stats.change_ratio,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ObservationStatsResponse {
+    pub subject_id: i64,
+    pub metric: MetricSummaryDto,
+    pub stats: ObservationStatsDto,
+}
+
+// =========================
+// Latest Snapshot
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct LatestSnapshotParams {
+    /// 是否同时计算 Derived 指标（默认 false）
+    #[serde(default)]
+    pub include_derived: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LatestMetricValueDto {
+    pub metric: MetricSummaryDto,
+    pub value: String,
+    pub value_num: Option<f64>,
+    /// RFC3339 (UTC)
+    pub observed_at: String,
+}
+
+impl From<LatestMetricValue> for LatestMetricValueDto {
+    fn from(item: LatestMetricValue) -> Self {
+        Self {
+            metric: MetricSummaryDto::from(item.metric),
+            value: item.point.value.0,
+            value_num: item.point.value_num,
+            observed_at: format_rfc3339_utc(item.point.observed_at),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LatestSnapshotResponse {
+    pub subject_id: i64,
+    /// 每个 metric 一条，按指标名称排序
+    pub items: Vec<LatestMetricValueDto>,
 }
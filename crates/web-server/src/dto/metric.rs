@@ -0,0 +1,416 @@
+use demo_db::{
+    MetricCode, MetricId, MetricKind, MetricStatus, MetricValueType, MetricVisualization,
+    PaginationInput, Sex, UnitConversion,
+    dto::metric::{
+        CreateMetricRequest as CreateMetric, CreateMetricUnitRequest as CreateMetricUnit,
+        CreateReferenceRangeRequest as CreateReferenceRange, ListMetricsRequest, MetricResponse,
+        MetricUnitResponse, ReferenceRangeResponse, UnitFormula,
+        UpdateMetricRequest as UpdateMetric,
+    },
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    dto::medical::format_rfc3339_utc,
+    error::{Error, Result},
+};
+
+// =========================
+// Metric Administration
+// =========================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MetricDto {
+    pub id: i64,
+    pub kind: String,
+    pub metric_code: String,
+    pub metric_name: String,
+    pub unit: Option<String>,
+    pub value_type: String,
+    pub visualization: String,
+    /// active / deprecated
+    pub status: String,
+    pub description: Option<String>,
+    /// 数值展示的小数位数
+    pub display_precision: Option<u8>,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+}
+
+impl From<MetricResponse> for MetricDto {
+    fn from(metric: MetricResponse) -> Self {
+        Self {
+            id: metric.id.0,
+            kind: metric.kind.to_string(),
+            metric_code: metric.code.0,
+            metric_name: metric.name,
+            unit: metric.unit,
+            value_type: metric.value_type.to_string(),
+            visualization: metric.visualization.to_string(),
+            status: metric.status.to_string(),
+            description: metric.description,
+            display_precision: metric.display_precision,
+            created_at: format_rfc3339_utc(metric.created_at),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateMetricRequest {
+    /// primitive / derived
+    pub kind: String,
+
+    /// 稳定的语义代码（例如 lab.biochem.tg，创建后不可修改）
+    pub metric_code: String,
+
+    pub metric_name: String,
+
+    pub unit: Option<String>,
+
+    /// int / float / decimal / bool / text
+    pub value_type: String,
+
+    /// line_chart / bar_chart / value_list / single_value
+    pub visualization: String,
+
+    /// active / deprecated（默认 active）
+    pub status: Option<String>,
+
+    pub description: Option<String>,
+
+    /// 数值展示的小数位数（Float / Decimal 为 0..=6，Integer 仅允许 0）
+    pub display_precision: Option<u8>,
+}
+
+impl CreateMetricRequest {
+    pub fn to_internal(self) -> Result<CreateMetric> {
+        Ok(CreateMetric {
+            kind: parse_metric_kind(&self.kind)?,
+            code: MetricCode(self.metric_code.trim().to_string()),
+            name: self.metric_name.trim().to_string(),
+            unit: non_empty(self.unit),
+            value_type: parse_value_type(&self.value_type)?,
+            visualization: parse_visualization(&self.visualization)?,
+            status: self
+                .status
+                .as_deref()
+                .map(parse_metric_status)
+                .transpose()?
+                .unwrap_or(MetricStatus::Active),
+            description: non_empty(self.description),
+            display_precision: self.display_precision,
+        })
+    }
+}
+
+pub(crate) fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListMetricsParams {
+    /// 按指标类型过滤（primitive / derived）
+    pub kind: Option<String>,
+
+    /// 按状态过滤（active / deprecated），默认全部
+    pub status: Option<String>,
+
+    /// 按值类型过滤（int / float / decimal / bool / text）
+    pub value_type: Option<String>,
+
+    /// 页码（从 1 开始，默认 1）
+    pub page: Option<u64>,
+
+    /// 每页数量（默认 20）
+    pub limit: Option<u64>,
+}
+
+impl ListMetricsParams {
+    pub fn to_internal(self) -> Result<(ListMetricsRequest, PaginationInput)> {
+        let default = PaginationInput::default();
+        let pagination = PaginationInput {
+            page: self.page.unwrap_or(default.page),
+            limit: self.limit.unwrap_or(default.limit),
+        };
+
+        let input = ListMetricsRequest {
+            kind: self.kind.as_deref().map(parse_metric_kind).transpose()?,
+            status: self
+                .status
+                .as_deref()
+                .map(parse_metric_status)
+                .transpose()?,
+            value_type: self
+                .value_type
+                .as_deref()
+                .map(parse_value_type)
+                .transpose()?,
+        };
+
+        Ok((input, pagination))
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListMetricsResponse {
+    pub metrics: Vec<MetricDto>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMetricRequest {
+    /// 新名称（不传表示不修改）
+    pub metric_name: Option<String>,
+
+    /// 新单位（不传表示不修改，空字符串表示清空）
+    pub unit: Option<String>,
+
+    /// 新可视化类型：line_chart / bar_chart / value_list / single_value
+    pub visualization: Option<String>,
+}
+
+impl UpdateMetricRequest {
+    pub fn to_internal(self) -> Result<UpdateMetric> {
+        let unit = self.unit.map(|u| non_empty(Some(u)));
+
+        Ok(UpdateMetric {
+            name: self.metric_name.map(|n| n.trim().to_string()),
+            unit,
+            visualization: self
+                .visualization
+                .as_deref()
+                .map(parse_visualization)
+                .transpose()?,
+        })
+    }
+}
+
+// =========================
+// Reference Ranges
+// =========================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReferenceRangeDto {
+    pub range_id: i64,
+    pub metric_id: i64,
+    /// male / female，null 表示不限
+    pub sex: Option<String>,
+    /// 适用年龄段 [age_min, age_max)，单位：岁
+    pub age_min: Option<u32>,
+    pub age_max: Option<u32>,
+    pub low: Option<f64>,
+    pub high: Option<f64>,
+    pub critical_low: Option<f64>,
+    pub critical_high: Option<f64>,
+    /// null 表示与指标单位一致
+    pub unit: Option<String>,
+    pub source: Option<String>,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+}
+
+impl From<ReferenceRangeResponse> for ReferenceRangeDto {
+    fn from(range: ReferenceRangeResponse) -> Self {
+        Self {
+            range_id: range.id.0,
+            metric_id: range.metric_id.0,
+            sex: range.sex.map(|s| s.to_string()),
+            age_min: range.age_min,
+            age_max: range.age_max,
+            low: range.low,
+            high: range.high,
+            critical_low: range.critical_low,
+            critical_high: range.critical_high,
+            unit: range.unit,
+            source: range.source,
+            created_at: format_rfc3339_utc(range.created_at),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListReferenceRangesResponse {
+    pub ranges: Vec<ReferenceRangeDto>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateReferenceRangeRequest {
+    /// male / female（不传表示不限）
+    pub sex: Option<String>,
+
+    /// 适用年龄下限（含，岁）
+    pub age_min: Option<u32>,
+
+    /// 适用年龄上限（不含，岁）
+    pub age_max: Option<u32>,
+
+    /// 正常范围下限 / 上限（含边界）
+    pub low: Option<f64>,
+    pub high: Option<f64>,
+
+    /// 危急值下限 / 上限（达到即为 critical）
+    pub critical_low: Option<f64>,
+    pub critical_high: Option<f64>,
+
+    /// 范围的单位（不传表示与指标单位一致）
+    pub unit: Option<String>,
+
+    /// 范围出处（化验室 / 指南等）
+    pub source: Option<String>,
+}
+
+impl CreateReferenceRangeRequest {
+    pub fn to_internal(self, metric_id: i64) -> Result<CreateReferenceRange> {
+        Ok(CreateReferenceRange {
+            metric_id: MetricId(metric_id),
+            sex: self.sex.as_deref().map(parse_sex).transpose()?,
+            age_min: self.age_min,
+            age_max: self.age_max,
+            low: self.low,
+            high: self.high,
+            critical_low: self.critical_low,
+            critical_high: self.critical_high,
+            unit: non_empty(self.unit),
+            source: non_empty(self.source),
+        })
+    }
+}
+
+// =========================
+// Units
+// =========================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MetricUnitDto {
+    pub unit_id: i64,
+    pub metric_id: i64,
+    /// 备选单位，例如 mmol/L
+    pub unit: String,
+    /// 指标单位的值 = 值 * factor + offset
+    pub factor: f64,
+    pub offset: f64,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+}
+
+impl From<MetricUnitResponse> for MetricUnitDto {
+    fn from(unit: MetricUnitResponse) -> Self {
+        Self {
+            unit_id: unit.id.0,
+            metric_id: unit.metric_id.0,
+            unit: unit.unit,
+            factor: unit.factor,
+            offset: unit.offset,
+            created_at: format_rfc3339_utc(unit.created_at),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListMetricUnitsResponse {
+    pub units: Vec<MetricUnitDto>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateMetricUnitRequest {
+    /// 备选单位，例如 mmol/L
+    pub unit: String,
+
+    /// 换算到指标单位的系数（与 molar_mass 二选一）
+    pub factor: Option<f64>,
+
+    /// 换算偏移量（仅与 factor 一起使用，默认 0）
+    pub offset: Option<f64>,
+
+    /// 物质的摩尔质量（g/mol），在质量浓度与摩尔浓度之间换算，例如葡萄糖 180.16
+    pub molar_mass: Option<f64>,
+}
+
+impl CreateMetricUnitRequest {
+    pub fn to_internal(self, metric_id: i64) -> Result<CreateMetricUnit> {
+        let formula = match (self.factor, self.molar_mass) {
+            (Some(factor), None) => UnitFormula::Affine(UnitConversion {
+                factor,
+                offset: self.offset.unwrap_or(0.0),
+            }),
+            (None, Some(molar_mass)) if self.offset.is_none() => UnitFormula::MolarMass(molar_mass),
+            (None, Some(_)) => {
+                return Err(Error::Custom(
+                    "offset can only be used with factor".to_string(),
+                ));
+            }
+            _ => {
+                return Err(Error::Custom(
+                    "exactly one of factor and molar_mass is required".to_string(),
+                ));
+            }
+        };
+
+        Ok(CreateMetricUnit {
+            metric_id: MetricId(metric_id),
+            unit: self.unit,
+            formula,
+        })
+    }
+}
+
+// 领域枚举的 From<&str> 会把未知值兜底成默认值（容忍脏数据），
+// 这里是用户输入，必须严格校验
+
+fn parse_metric_kind(input: &str) -> Result<MetricKind> {
+    let kind = MetricKind::from(input.trim());
+    if kind.as_str() != input.trim() {
+        return Err(Error::Custom(format!(
+            "invalid metric kind '{}', expected one of primitive, derived",
+            input
+        )));
+    }
+    Ok(kind)
+}
+
+fn parse_metric_status(input: &str) -> Result<MetricStatus> {
+    let status = MetricStatus::from(input.trim());
+    if status.to_string() != input.trim() {
+        return Err(Error::Custom(format!(
+            "invalid metric status '{}', expected one of active, deprecated",
+            input
+        )));
+    }
+    Ok(status)
+}
+
+fn parse_value_type(input: &str) -> Result<MetricValueType> {
+    let value_type = MetricValueType::from(input.trim());
+    if value_type.to_string() != input.trim() {
+        return Err(Error::Custom(format!(
+            "invalid value type '{}', expected one of int, float, decimal, bool, text",
+            input
+        )));
+    }
+    Ok(value_type)
+}
+
+fn parse_visualization(input: &str) -> Result<MetricVisualization> {
+    let visualization = MetricVisualization::from(input.trim());
+    if visualization.as_str() != input.trim() {
+        return Err(Error::Custom(format!(
+            "invalid visualization '{}', expected one of line_chart, bar_chart, value_list, single_value",
+            input
+        )));
+    }
+    Ok(visualization)
+}
+
+pub(crate) fn parse_sex(input: &str) -> Result<Sex> {
+    Sex::parse(input.trim()).ok_or_else(|| {
+        Error::Custom(format!(
+            "invalid sex '{}', expected one of male, female",
+            input
+        ))
+    })
+}
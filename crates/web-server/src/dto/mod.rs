@@ -1 +1,7 @@
+pub mod access;
+pub mod auth;
+pub mod data_source;
 pub mod medical;
+pub mod metric;
+pub mod recipe;
+pub mod subject;
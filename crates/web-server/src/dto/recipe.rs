@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use demo_db::dto::recipe::{
+    CreateRecipeRequest as CreateRecipe, PublishRecipeRequest as PublishRecipe, RecipeResponse,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+use utoipa::{IntoParams, ToSchema};
+
+use time::OffsetDateTime;
+
+use crate::{
+    dto::medical::{format_rfc3339_utc, parse_rfc3339},
+    error::Result,
+};
+
+// =========================
+// Recipes
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateRecipeRequest {
+    /// 目标指标（必须为 derived）
+    pub metric_id: i64,
+
+    /// 依赖的 primitive 指标 ID
+    pub deps: Vec<i64>,
+
+    /// 已注册的计算，见 `GET /recipes/calcs`
+    pub calc_key: String,
+
+    /// 参数名 -> 指标 ID，例如 {"TG": 16, "GLU": 18}
+    pub arg_map: BTreeMap<String, i64>,
+
+    /// 说明信息（可选，原样保存）
+    #[schema(value_type = Option<Object>)]
+    pub expr: Option<JsonValue>,
+}
+
+impl CreateRecipeRequest {
+    pub fn to_internal(self) -> CreateRecipe {
+        CreateRecipe {
+            metric_id: self.metric_id,
+            deps: json!(self.deps),
+            calc_key: self.calc_key.trim().to_string(),
+            arg_map: json!(self.arg_map),
+            expr: self.expr.unwrap_or_else(|| json!({})),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PublishRecipeRequest {
+    /// 依赖的 primitive 指标 ID
+    pub deps: Vec<i64>,
+
+    pub calc_key: String,
+
+    /// 参数名 -> 指标 ID
+    pub arg_map: BTreeMap<String, i64>,
+
+    #[schema(value_type = Option<Object>)]
+    pub expr: Option<JsonValue>,
+
+    /// 新版本生效起点（RFC3339，默认当前时间），此前的观测仍按旧版本计算
+    pub effective_from: Option<String>,
+}
+
+impl PublishRecipeRequest {
+    pub fn to_internal(self, metric_id: i64) -> Result<PublishRecipe> {
+        let effective_from = match self.effective_from.as_deref() {
+            Some(s) => parse_rfc3339(s)?,
+            None => OffsetDateTime::now_utc(),
+        };
+
+        let recipe = CreateRecipeRequest {
+            metric_id,
+            deps: self.deps,
+            calc_key: self.calc_key,
+            arg_map: self.arg_map,
+            expr: self.expr,
+        }
+        .to_internal();
+
+        Ok(PublishRecipe {
+            recipe,
+            effective_from,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListRecipesParams {
+    /// 按 calc_key 过滤
+    pub calc_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecipeDto {
+    pub id: i64,
+    pub metric_id: i64,
+    #[schema(value_type = Object)]
+    pub deps: JsonValue,
+    pub calc_key: String,
+    #[schema(value_type = Object)]
+    pub arg_map: JsonValue,
+    #[schema(value_type = Object)]
+    pub expr: JsonValue,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+    /// 同一指标下的版本号
+    pub version: i32,
+    /// 生效起点（含），null 表示不限
+    pub effective_from: Option<String>,
+    /// 生效终点（不含），null 表示当前版本
+    pub effective_to: Option<String>,
+}
+
+impl From<RecipeResponse> for RecipeDto {
+    fn from(recipe: RecipeResponse) -> Self {
+        Self {
+            id: recipe.id,
+            metric_id: recipe.metric_id,
+            deps: recipe.deps,
+            calc_key: recipe.calc_key,
+            arg_map: recipe.arg_map,
+            expr: recipe.expr,
+            created_at: format_rfc3339_utc(recipe.created_at),
+            version: recipe.version,
+            effective_from: recipe.effective_from.map(format_rfc3339_utc),
+            effective_to: recipe.effective_to.map(format_rfc3339_utc),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListRecipesResponse {
+    pub recipes: Vec<RecipeDto>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalcDto {
+    pub calc_key: String,
+    /// arg_map 必须绑定的参数名
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListCalcsResponse {
+    pub calcs: Vec<CalcDto>,
+}
@@ -0,0 +1,198 @@
+use demo_db::{
+    PaginationInput, SubjectId, SubjectKind,
+    dto::subject::{
+        CreateSubjectRequest as CreateSubject, ListSubjectsRequest,
+        SaveSubjectProfileRequest as SaveSubjectProfile, SubjectProfileResponse, SubjectResponse,
+    },
+};
+use serde::{Deserialize, Serialize};
+use time::{Date, format_description::BorrowedFormatItem, macros::format_description};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    dto::{
+        medical::format_rfc3339_utc,
+        metric::{non_empty, parse_sex},
+    },
+    error::{Error, Result},
+};
+
+/// 日期格式：YYYY-MM-DD
+const DATE_FORMAT: &[BorrowedFormatItem<'static>] = format_description!("[year]-[month]-[day]");
+
+// =========================
+// Subjects
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSubjectRequest {
+    /// 主体类型：user / member / device
+    pub kind: String,
+}
+
+impl CreateSubjectRequest {
+    pub fn to_internal(&self) -> Result<CreateSubject> {
+        Ok(CreateSubject {
+            kind: parse_subject_kind(&self.kind)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListSubjectsParams {
+    /// 按类型过滤（user / member / device）
+    pub kind: Option<String>,
+
+    /// 是否包含已停用的 Subject（默认 false）
+    pub include_deactivated: Option<bool>,
+
+    /// 页码（从 1 开始，默认 1）
+    pub page: Option<u64>,
+
+    /// 每页数量（默认 20）
+    pub limit: Option<u64>,
+}
+
+impl ListSubjectsParams {
+    pub fn to_internal(self) -> Result<(ListSubjectsRequest, PaginationInput)> {
+        let kind = match self.kind.as_deref() {
+            None => None,
+            Some(k) => Some(parse_subject_kind(k)?),
+        };
+
+        let default = PaginationInput::default();
+        let pagination = PaginationInput {
+            page: self.page.unwrap_or(default.page),
+            limit: self.limit.unwrap_or(default.limit),
+        };
+
+        let input = ListSubjectsRequest {
+            kind,
+            include_deactivated: self.include_deactivated.unwrap_or(false),
+        };
+
+        Ok((input, pagination))
+    }
+}
+
+/// 只接受已知类型，避免拼写错误产生 `Other`
+fn parse_subject_kind(input: &str) -> Result<SubjectKind> {
+    match SubjectKind::from(input.trim()) {
+        SubjectKind::Other(_) => Err(Error::Custom(format!(
+            "invalid subject kind '{}', expected one of user, member, device",
+            input
+        ))),
+        kind => Ok(kind),
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubjectDto {
+    pub subject_id: i64,
+    pub kind: String,
+    pub active: bool,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+    /// RFC3339 (UTC)，未停用时为 null
+    pub deactivated_at: Option<String>,
+}
+
+impl From<SubjectResponse> for SubjectDto {
+    fn from(subject: SubjectResponse) -> Self {
+        Self {
+            subject_id: subject.id.0,
+            active: subject.is_active(),
+            kind: subject.kind.to_string(),
+            created_at: format_rfc3339_utc(subject.created_at),
+            deactivated_at: subject.deactivated_at.map(format_rfc3339_utc),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListSubjectsResponse {
+    pub subjects: Vec<SubjectDto>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total: u64,
+}
+
+// =========================
+// Subject Profile
+// =========================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveSubjectProfileRequest {
+    /// 显示名称（昵称）
+    pub display_name: Option<String>,
+
+    /// 出生日期（YYYY-MM-DD）
+    pub birth_date: Option<String>,
+
+    /// male / female
+    pub sex: Option<String>,
+
+    /// 身高（厘米）
+    pub height_cm: Option<f64>,
+
+    /// IANA 时区名称（例如 Asia/Shanghai）
+    pub time_zone: Option<String>,
+}
+
+impl SaveSubjectProfileRequest {
+    pub fn to_internal(self, subject_id: i64) -> Result<SaveSubjectProfile> {
+        let birth_date = self
+            .birth_date
+            .as_deref()
+            .map(|d| {
+                Date::parse(d.trim(), DATE_FORMAT).map_err(|_| {
+                    Error::Custom(format!("invalid birth_date '{}', expected YYYY-MM-DD", d))
+                })
+            })
+            .transpose()?;
+
+        Ok(SaveSubjectProfile {
+            subject_id: SubjectId(subject_id),
+            display_name: non_empty(self.display_name),
+            birth_date,
+            sex: self.sex.as_deref().map(parse_sex).transpose()?,
+            height_cm: self.height_cm,
+            time_zone: non_empty(self.time_zone),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubjectProfileDto {
+    pub subject_id: i64,
+    pub display_name: Option<String>,
+    /// YYYY-MM-DD
+    pub birth_date: Option<String>,
+    /// 当前周岁年龄（由 birth_date 计算）
+    pub age: Option<u32>,
+    /// male / female
+    pub sex: Option<String>,
+    pub height_cm: Option<f64>,
+    pub time_zone: Option<String>,
+    /// RFC3339 (UTC)
+    pub created_at: String,
+    /// RFC3339 (UTC)
+    pub updated_at: String,
+}
+
+impl SubjectProfileDto {
+    /// `age` 由 demo-db 按 subject 所在时区计算
+    pub fn new(profile: SubjectProfileResponse, age: Option<u32>) -> Self {
+        Self {
+            subject_id: profile.subject_id.0,
+            display_name: profile.display_name,
+            birth_date: profile.birth_date.and_then(|d| d.format(DATE_FORMAT).ok()),
+            age,
+            sex: profile.sex.map(|s| s.to_string()),
+            height_cm: profile.height_cm,
+            time_zone: profile.time_zone,
+            created_at: format_rfc3339_utc(profile.created_at),
+            updated_at: format_rfc3339_utc(profile.updated_at),
+        }
+    }
+}
@@ -20,8 +20,25 @@ pub enum Error {
     #[allow(clippy::enum_variant_names)]
     Core(#[from] pg_core::Error),
 
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
     #[error("custom error: {0}")]
     Custom(String),
+
+    #[error("unavailable: {0}")]
+    Unavailable(String),
+}
+
+impl Error {
+    /// 是否为暂时性错误（数据库连接问题、资源被占用），稍后重试可能成功
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Core(e) => e.is_transient(),
+            Error::Unavailable(_) => true,
+            _ => false,
+        }
+    }
 }
 
 pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -51,6 +68,14 @@ fn error_to_api_error(err: Error) -> ApiError {
             .to_json(),
         ),
         Error::Core(e) => map_core_error(e),
+        Error::Unauthorized(message) => (
+            StatusCode::UNAUTHORIZED,
+            CommonError {
+                code: 401,
+                message,
+            }
+            .to_json(),
+        ),
         Error::Custom(message) => (
             StatusCode::BAD_REQUEST,
             CommonError {
@@ -59,6 +84,14 @@ fn error_to_api_error(err: Error) -> ApiError {
             }
             .to_json(),
         ),
+        Error::Unavailable(message) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            CommonError {
+                code: 503,
+                message,
+            }
+            .to_json(),
+        ),
     }
 }
 
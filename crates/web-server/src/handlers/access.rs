@@ -0,0 +1,99 @@
+use axum::{Json, extract::Path};
+use demo_db::{SubjectId, api::access::AccessApi, dto::access::GrantAccessRequest as GrantAccess};
+use toolcraft_axum_kit::{CommonOk, CommonResponse, Empty, IntoCommonResponse, ResponseResult};
+
+use crate::{
+    dto::access::{GrantAccessRequest, ListSubjectAccessResponse, SubjectAccessDto},
+    error::Error,
+    middleware::auth::AuthUser,
+    statics::db_manager::get_default_ctx,
+};
+
+#[utoipa::path(
+    get,
+    path = "/subjects/{subject_id}/access",
+    tag = "Medical",
+    params(
+        ("subject_id" = i64, Path, description = "Subject ID")
+    ),
+    responses(
+        (status = 200, description = "List access grants of a subject (owner only)", body = CommonResponse<ListSubjectAccessResponse>),
+    )
+)]
+pub async fn list_subject_access(
+    user: AuthUser,
+    Path(subject_id): Path<i64>,
+) -> ResponseResult<ListSubjectAccessResponse> {
+    let api = AccessApi::new(get_default_ctx());
+
+    let grants = api
+        .list(&user.actor(), SubjectId(subject_id))
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListSubjectAccessResponse {
+        grants: grants.into_iter().map(SubjectAccessDto::from).collect(),
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/subjects/{subject_id}/access",
+    tag = "Medical",
+    params(
+        ("subject_id" = i64, Path, description = "Subject ID")
+    ),
+    request_body = GrantAccessRequest,
+    responses(
+        (status = 200, description = "Grant or update access to a subject (owner only)", body = CommonResponse<SubjectAccessDto>),
+    )
+)]
+pub async fn grant_subject_access(
+    user: AuthUser,
+    Path(subject_id): Path<i64>,
+    Json(req): Json<GrantAccessRequest>,
+) -> ResponseResult<SubjectAccessDto> {
+    let api = AccessApi::new(get_default_ctx());
+
+    let internal_req = GrantAccess {
+        subject_id: SubjectId(subject_id),
+        user_id: req.user_id,
+        role: req.parse_role()?,
+    };
+
+    let access = api
+        .grant(&user.actor(), internal_req)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(SubjectAccessDto::from(access)
+        .into_common_response()
+        .to_json())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/subjects/{subject_id}/access/{user_id}",
+    tag = "Medical",
+    params(
+        ("subject_id" = i64, Path, description = "Subject ID"),
+        ("user_id" = i64, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Revoke access to a subject (owner only)", body = CommonOk),
+    )
+)]
+pub async fn revoke_subject_access(
+    user: AuthUser,
+    Path((subject_id, user_id)): Path<(i64, i64)>,
+) -> ResponseResult<Empty> {
+    let api = AccessApi::new(get_default_ctx());
+
+    api.revoke(&user.actor(), SubjectId(subject_id), user_id)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(Empty.into_common_response().to_json())
+}
@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+use demo_db::{
+    AccountId,
+    api::account::AccountApi,
+    dto::account::{LoginRequest as Login, RegisterAccountRequest},
+};
+use time::OffsetDateTime;
+use toolcraft_axum_kit::{CommonOk, CommonResponse, Empty, IntoCommonResponse, ResponseResult};
+use toolcraft_jwt::Jwt;
+
+use crate::{
+    dto::auth::{
+        AccountDto, LoginRequest, LogoutRequest, RefreshRequest, RegisterRequest, TokenResponse,
+    },
+    error::{Error, Result},
+    middleware::auth::{AuthToken, AuthUser},
+    statics::db_manager::get_default_ctx,
+};
+
+const TOKEN_TYPE: &str = "Bearer";
+
+#[utoipa::path(
+    post,
+    path = "/register",
+    tag = "Auth",
+    request_body = RegisterRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Register a local account and its own subject (admin only)", body = CommonResponse<AccountDto>),
+    )
+)]
+pub async fn register(
+    user: AuthUser,
+    Json(req): Json<RegisterRequest>,
+) -> ResponseResult<AccountDto> {
+    let api = AccountApi::new(get_default_ctx());
+
+    let account = api
+        .register(
+            &user.actor(),
+            RegisterAccountRequest {
+                username: req.username,
+                password: req.password,
+            },
+        )
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(AccountDto::from(account).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "Auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Log in with username and password", body = CommonResponse<TokenResponse>),
+        (status = 401, description = "Invalid username or password"),
+    )
+)]
+pub async fn login(
+    Extension(jwt): Extension<Arc<Jwt>>,
+    Json(req): Json<LoginRequest>,
+) -> ResponseResult<TokenResponse> {
+    let api = AccountApi::new(get_default_ctx());
+
+    let account = api
+        .login(Login {
+            username: req.username,
+            password: req.password,
+        })
+        .await
+        .map_err(unauthorized)?;
+
+    let user = AuthUser {
+        user_id: account.id.0,
+        roles: account.roles.clone(),
+    };
+    let (access_token, refresh_token) = jwt
+        .generate_token_pair(user.to_subject())
+        .map_err(|e| Error::Custom(format!("failed to issue token: {e}")))?;
+
+    let resp = TokenResponse {
+        access_token,
+        refresh_token: Some(refresh_token),
+        token_type: TOKEN_TYPE.to_string(),
+        account: AccountDto::from(account),
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/refresh",
+    tag = "Auth",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "Issue a new access token from a refresh token", body = CommonResponse<TokenResponse>),
+        (status = 401, description = "Refresh token is invalid, revoked or the account is disabled"),
+    )
+)]
+pub async fn refresh(
+    Extension(jwt): Extension<Arc<Jwt>>,
+    Json(req): Json<RefreshRequest>,
+) -> ResponseResult<TokenResponse> {
+    let api = AccountApi::new(get_default_ctx());
+
+    let claims = jwt
+        .validate_refresh_token(&req.refresh_token)
+        .map_err(|e| Error::Unauthorized(format!("invalid refresh token: {e}")))?;
+
+    if api
+        .is_token_revoked(&req.refresh_token)
+        .await
+        .map_err(Error::Core)?
+    {
+        return Err(Error::Unauthorized("refresh token has been revoked".to_string()).into());
+    }
+
+    let user = AuthUser::from_subject(&claims.sub)
+        .ok_or_else(|| Error::Unauthorized("invalid token subject".to_string()))?;
+
+    // 重新读取账号，停用的账号不能续期，角色以数据库为准
+    let account = api
+        .get_active(AccountId(user.user_id))
+        .await
+        .map_err(unauthorized)?;
+
+    let user = AuthUser {
+        user_id: account.id.0,
+        roles: account.roles.clone(),
+    };
+    let access_token = jwt
+        .generate_access_token(user.to_subject())
+        .map_err(|e| Error::Custom(format!("failed to issue token: {e}")))?;
+
+    let resp = TokenResponse {
+        access_token,
+        refresh_token: None,
+        token_type: TOKEN_TYPE.to_string(),
+        account: AccountDto::from(account),
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = "Auth",
+    request_body = LogoutRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Revoke the current access token and optionally a refresh token", body = CommonOk),
+    )
+)]
+pub async fn logout(
+    user: AuthUser,
+    Extension(jwt): Extension<Arc<Jwt>>,
+    Extension(token): Extension<AuthToken>,
+    Json(req): Json<LogoutRequest>,
+) -> ResponseResult<Empty> {
+    let api = AccountApi::new(get_default_ctx());
+
+    api.revoke_token(&token.token, expires_at(token.expires_at)?)
+        .await
+        .map_err(Error::Core)?;
+
+    if let Some(refresh_token) = req.refresh_token {
+        let claims = jwt
+            .validate_refresh_token(&refresh_token)
+            .map_err(|e| Error::Unauthorized(format!("invalid refresh token: {e}")))?;
+
+        // 只能注销自己的 refresh token
+        let owner = AuthUser::from_subject(&claims.sub)
+            .ok_or_else(|| Error::Unauthorized("invalid token subject".to_string()))?;
+        if owner.user_id != user.user_id {
+            return Err(Error::Core(pg_core::Error::permission_denied(
+                "refresh token belongs to another user",
+            )))?;
+        }
+
+        api.revoke_token(&refresh_token, expires_at(claims.exp)?)
+            .await
+            .map_err(Error::Core)?;
+    }
+
+    Ok(Empty.into_common_response().to_json())
+}
+
+/// 登录 / 刷新失败统一返回 401，其余错误按原样映射
+fn unauthorized(err: pg_core::Error) -> Error {
+    match err.kind() {
+        pg_core::ErrorKind::Permission => Error::Unauthorized(err.to_string()),
+        _ => Error::Core(err),
+    }
+}
+
+fn expires_at(exp: usize) -> Result<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp(exp as i64)
+        .map_err(|e| Error::Custom(format!("invalid token expiry: {e}")))
+}
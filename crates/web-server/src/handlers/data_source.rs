@@ -0,0 +1,140 @@
+use axum::{
+    Json,
+    extract::{Path, Query},
+};
+use demo_db::{DataSourceId, api::data_source::DataSourceApi};
+use toolcraft_axum_kit::{CommonResponse, IntoCommonResponse, ResponseResult};
+
+use crate::{
+    dto::data_source::{
+        DataSourceDto, ListSourceObservationsParams, ListSourceObservationsResponse,
+        SourceObservationDto, UpdateDataSourceRequest, VoidDataSourceRequest,
+        VoidDataSourceResultDto,
+    },
+    error::Error,
+    middleware::auth::AuthUser,
+    statics::db_manager::get_default_ctx,
+};
+
+#[utoipa::path(
+    get,
+    path = "/data-source/{source_id}",
+    tag = "Medical",
+    params(
+        ("source_id" = i64, Path, description = "Data source ID")
+    ),
+    responses(
+        (status = 200, description = "Get a data source", body = CommonResponse<DataSourceDto>),
+    )
+)]
+pub async fn get_data_source(
+    user: AuthUser,
+    Path(source_id): Path<i64>,
+) -> ResponseResult<DataSourceDto> {
+    let api = DataSourceApi::new(get_default_ctx());
+
+    let source = api
+        .get(&user.actor(), DataSourceId(source_id))
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(DataSourceDto::from(source).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    patch,
+    path = "/data-source/{source_id}",
+    tag = "Medical",
+    params(
+        ("source_id" = i64, Path, description = "Data source ID")
+    ),
+    request_body = UpdateDataSourceRequest,
+    responses(
+        (status = 200, description = "Update name / metadata of a data source", body = CommonResponse<DataSourceDto>),
+    )
+)]
+pub async fn update_data_source(
+    user: AuthUser,
+    Path(source_id): Path<i64>,
+    Json(req): Json<UpdateDataSourceRequest>,
+) -> ResponseResult<DataSourceDto> {
+    let api = DataSourceApi::new(get_default_ctx());
+
+    let source = api
+        .update(&user.actor(), DataSourceId(source_id), req.to_internal()?)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(DataSourceDto::from(source).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/data-source/{source_id}/observations",
+    tag = "Medical",
+    params(
+        ("source_id" = i64, Path, description = "Data source ID"),
+        ListSourceObservationsParams
+    ),
+    responses(
+        (status = 200, description = "List observations created from a data source, including voided ones", body = CommonResponse<ListSourceObservationsResponse>),
+    )
+)]
+pub async fn list_data_source_observations(
+    user: AuthUser,
+    Path(source_id): Path<i64>,
+    Query(params): Query<ListSourceObservationsParams>,
+) -> ResponseResult<ListSourceObservationsResponse> {
+    let api = DataSourceApi::new(get_default_ctx());
+
+    let page = api
+        .list_observations(
+            &user.actor(),
+            DataSourceId(source_id),
+            Some(params.to_internal()),
+        )
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListSourceObservationsResponse {
+        observations: page
+            .items
+            .into_iter()
+            .map(SourceObservationDto::from)
+            .collect(),
+        page: page.page,
+        page_size: page.page_size,
+        total: page.total,
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/data-source/{source_id}/void",
+    tag = "Medical",
+    params(
+        ("source_id" = i64, Path, description = "Data source ID")
+    ),
+    request_body = VoidDataSourceRequest,
+    responses(
+        (status = 200, description = "Void a data source and retract all of its observations", body = CommonResponse<VoidDataSourceResultDto>),
+    )
+)]
+pub async fn void_data_source(
+    user: AuthUser,
+    Path(source_id): Path<i64>,
+    Json(req): Json<VoidDataSourceRequest>,
+) -> ResponseResult<VoidDataSourceResultDto> {
+    let api = DataSourceApi::new(get_default_ctx());
+
+    let result = api
+        .void(&user.actor(), DataSourceId(source_id), req.to_internal())
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(VoidDataSourceResultDto::from(result)
+        .into_common_response()
+        .to_json())
+}
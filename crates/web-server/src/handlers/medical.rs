@@ -1,24 +1,27 @@
 use axum::{Json, extract::{Path, Query}};
 use demo_db::{
-    CreateDataSource, api::medical::HealthApi, dto::medical::RecordObservationWithSourceRequest,
+    AccessLevel, EnqueueJob, Job, JobId, SubjectId,
+    api::{job::JobApi, medical::HealthApi},
+    dto::medical::RecordObservationRequest as RecordObservation,
 };
-use pg_tables::table::metric::dto::{MetricKind, MetricValueType};
-use std::collections::HashSet;
-use time::OffsetDateTime;
 use toolcraft_axum_kit::{CommonResponse, IntoCommonResponse, ResponseResult};
 
 use crate::{
     dto::medical::{
-        ListSelectableMetricsResponse, QueryObservationParams, QueryRecipeObservationResponse,
+        ListSelectableMetricsResponse, ObservationBucketDto, QueryObservationParams,
+        QueryRecipeObservationResponse,
         MetricSummaryDto, RecordObservationRequest, RecordObservationResponse, SelectableMetricDto,
         TaskStatusResponse, UploadMarkdownRequest, UploadMarkdownResponse,
-        UploadMarkdownTaskResponse, ObservationPointDto, format_rfc3339_utc,
+        UploadMarkdownTaskResponse, ObservationPointDto, format_rfc3339_utc, ListTasksParams,
+        ListTasksResponse, MarkdownTaskProgress, LatestMetricValueDto, LatestSnapshotParams,
+        LatestSnapshotResponse, ObservationStatsDto, ObservationStatsParams,
+        ObservationStatsResponse,
     },
-    error::Error,
+    error::{Error, Result},
+    jobs::{self, markdown},
+    middleware::auth::AuthUser,
     statics::db_manager::get_default_ctx,
-    statics::task_store,
 };
-use serde_json::json;
 
 #[utoipa::path(
     get,
@@ -32,41 +35,123 @@ use serde_json::json;
     )
 )]
 pub async fn query_observations(
+    user: AuthUser,
     Query(req): Query<QueryObservationParams>,
 ) -> ResponseResult<QueryRecipeObservationResponse> {
     // 1. 构造 HealthApi（轻量）
     let api = HealthApi::new(get_default_ctx());
 
     let subject_id = req.subject_id;
+    let max_points = req.max_points;
+    let unit = req.unit.clone();
 
     // 2. web request → internal 参数
+    let bucket = req.bucket_spec()?;
     let (query, range) = req.to_internal()?;
 
-    // 3. 调用内部查询
-    let result = api
-        .query_observation(query, range)
+    // 3a. 分桶聚合（在数据库内完成），points 为每个桶的平均值
+    if let Some(spec) = bucket {
+        let mut result = api
+            .aggregate_observation(&user.actor(), query, range, spec)
+            .await
+            .map_err(Error::Core)?;
+        if let Some(unit) = unit.as_deref() {
+            api.convert_buckets(&mut result, unit)
+                .await
+                .map_err(Error::Core)?;
+        }
+
+        let resp = QueryRecipeObservationResponse {
+            subject_id,
+            metric: MetricSummaryDto::from(result.metric),
+            points: result
+                .buckets
+                .iter()
+                .map(|b| ObservationPointDto {
+                    value: b.avg.map(|v| v.to_string()).unwrap_or_default(),
+                    value_num: b.avg,
+                    observed_at: format_rfc3339_utc(b.bucket_start),
+                    flag: None,
+                })
+                .collect(),
+            buckets: Some(
+                result
+                    .buckets
+                    .into_iter()
+                    .map(ObservationBucketDto::from)
+                    .collect(),
+            ),
+        };
+
+        return Ok(resp.into_common_response().to_json());
+    }
+
+    // 3b. 调用内部查询
+    let mut result = api
+        .query_observation(&user.actor(), query, range)
         .await
         .map_err(Error::Core)?;
 
+    // 4. 折线图按需降采样
+    if let Some(max_points) = max_points {
+        result.downsample(max_points as usize);
+    }
+
+    // 5. 按需换算单位
+    if let Some(unit) = unit.as_deref() {
+        api.convert_observation(&mut result, unit)
+            .await
+            .map_err(Error::Core)?;
+    }
+
     let resp = QueryRecipeObservationResponse {
         subject_id,
-        metric: MetricSummaryDto {
-            id: result.metric.id,
-            metric_code: result.metric.metric_code.0,
-            metric_name: result.metric.metric_name,
-            unit: result.metric.unit,
-            value_type: result.metric.value_type.to_string(),
-            visualization: result.metric.visualization.to_string(),
-        },
+        metric: MetricSummaryDto::from(result.metric),
         points: result
             .points
             .into_iter()
             .map(|p| ObservationPointDto {
                 value: p.value.as_str().to_string(),
-                value_num: p.value.as_str().parse::<f64>().ok(),
+                value_num: p.value_num,
                 observed_at: format_rfc3339_utc(p.observed_at),
+                flag: p.flag.map(|f| f.to_string()),
             })
             .collect(),
+        buckets: None,
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/observations/stats",
+    tag = "Medical",
+    params(
+        ObservationStatsParams
+    ),
+    responses(
+        (status = 200, description = "Descriptive statistics of a metric over a time range", body = CommonResponse<ObservationStatsResponse>),
+    )
+)]
+pub async fn get_observation_stats(
+    user: AuthUser,
+    Query(req): Query<ObservationStatsParams>,
+) -> ResponseResult<ObservationStatsResponse> {
+    let api = HealthApi::new(get_default_ctx());
+
+    let subject_id = req.subject_id;
+    let (query, range) = req.to_internal()?;
+
+    let result = api
+        .observation_stats(&user.actor(), query, range)
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ObservationStatsResponse {
+        subject_id,
+        metric: MetricSummaryDto::from(result.metric),
+        stats: ObservationStatsDto::from(result.stats),
     };
 
     Ok(resp.into_common_response().to_json())
@@ -82,31 +167,33 @@ pub async fn query_observations(
     )
 )]
 pub async fn record_observation(
+    user: AuthUser,
     Json(req): Json<RecordObservationRequest>,
 ) -> ResponseResult<RecordObservationResponse> {
     let api = HealthApi::new(get_default_ctx());
 
     // web request → internal 参数
-    let (subject_id, metric_id, value, observed_at, source) = req.to_internal()?;
+    let (subject_id, metric_id, value, observed_at, unit, source) = req.to_internal()?;
 
     // 构造业务请求
-    let internal_req = RecordObservationWithSourceRequest {
+    let internal_req = RecordObservation {
         subject_id,
         metric_id,
         value,
         observed_at,
+        unit,
         source,
     };
 
     // 调用业务 API
     let result = api
-        .record_observation_with_source(internal_req)
+        .record_observation(&user.actor(), internal_req)
         .await
         .map_err(Error::Core)?;
 
     let resp = RecordObservationResponse {
         observation_id: result.observation_id.0,
-        source_id: result.source_id.0,
+        source_id: result.source_id.map(|id| id.0),
     };
 
     Ok(resp.into_common_response().to_json())
@@ -149,98 +236,37 @@ pub async fn list_selectable_metrics() -> ResponseResult<ListSelectableMetricsRe
     )
 )]
 pub async fn upload_markdown_data_source(
+    user: AuthUser,
     Json(req): Json<UploadMarkdownRequest>,
 ) -> ResponseResult<UploadMarkdownTaskResponse> {
-    use demo_db::DataSourceKind;
-    use pg_tables::table::data_source::service::DataSourceService;
-
     if req.file_content.is_empty() {
         return Err(Error::Custom("No file content provided".to_string()))?;
     }
 
-    let task_id = task_store::create_task();
-    let req_task = req.clone();
-    let task_id_clone = task_id.clone();
-
-    tokio::spawn(async move {
-        task_store::set_running(&task_id_clone);
-
-        let result: Result<UploadMarkdownResponse, Error> = async {
-            let ctx = get_default_ctx();
-            let service = DataSourceService::new(ctx.clone());
-
-            let observed_at = parse_report_date(&req_task.file_content)?;
-            let observed_at_str = format_rfc3339_utc(observed_at);
-
-            let api = HealthApi::new(ctx);
-            let metrics = api.list_selectable_metrics().await.map_err(Error::Core)?;
-            let extracted = extract_metric_values(&metrics, &req_task.file_content);
-            let mut records_inserted = 0;
-            let metrics_json = extracted
-                .iter()
-                .map(|(metric_id, value)| {
-                    json!({
-                        "metric_id": metric_id.0,
-                        "value": value,
-                    })
-                })
-                .collect::<Vec<_>>();
-
-            let parsed_data = json!({
-                "observed_at": observed_at_str,
-                "metrics": metrics_json,
-            });
-
-            let input = CreateDataSource {
-                kind: DataSourceKind::from(req_task.source_type.as_str()),
-                name: req_task.source_name.clone(),
-                metadata: Some(parsed_data.clone()),
-            };
-
-            let model = service.create(input).await.map_err(|e: demo_db::Error| {
-                Error::Custom(format!("Failed to insert data source: {}", e))
-            })?;
-
-            for (metric_id, value) in extracted {
-                let result = api
-                    .record_observation_with_source_id(
-                        demo_db::SubjectId(req_task.subject_id),
-                        metric_id,
-                        demo_db::ObservationValue(value),
-                        observed_at,
-                        model.id,
-                    )
-                    .await;
-
-                if result.is_ok() {
-                    records_inserted += 1;
-                }
-            }
-
-            let now = OffsetDateTime::now_utc();
-            let created_at = now
-                .to_offset(time::UtcOffset::UTC)
-                .format(&time::format_description::well_known::Rfc3339)
-                .unwrap_or_else(|_| "invalid datetime".to_string());
-
-            Ok(UploadMarkdownResponse {
-                source_id: model.id.0,
-                source_type: model.kind.to_string(),
-                source_name: model.name,
-                parsed_data,
-                created_at,
-                records_inserted,
-            })
-        }
-        .await;
+    // 入队前先校验写权限，避免无权限的任务进入队列
+    HealthApi::new(get_default_ctx())
+        .authorize(&user.actor(), SubjectId(req.subject_id), AccessLevel::Write)
+        .await
+        .map_err(Error::Core)?;
 
-        match result {
-            Ok(resp) => task_store::set_result(&task_id_clone, resp),
-            Err(err) => task_store::set_error(&task_id_clone, err.to_string()),
-        }
-    });
+    let subject_id = req.subject_id;
+    let payload = serde_json::to_value(markdown::Payload { user, request: req })
+        .map_err(|e| Error::Custom(format!("invalid markdown upload: {e}")))?;
+
+    // 持久化入队，由任意实例的 worker 领取执行
+    let api = JobApi::new(get_default_ctx());
+    let job = api
+        .enqueue(EnqueueJob {
+            queue: markdown::QUEUE.to_string(),
+            payload,
+            max_attempts: markdown::MAX_ATTEMPTS,
+            run_at: None,
+            subject_id: Some(subject_id),
+        })
+        .await
+        .map_err(Error::Core)?;
 
-    Ok(UploadMarkdownTaskResponse { task_id }
+    Ok(UploadMarkdownTaskResponse { task_id: job.id.0 }
         .into_common_response()
         .to_json())
 }
@@ -250,216 +276,194 @@ pub async fn upload_markdown_data_source(
     path = "/data-source/markdown/tasks/{task_id}",
     tag = "Medical",
     params(
-        ("task_id" = String, Path, description = "Task ID")
+        ("task_id" = i64, Path, description = "Task ID")
     ),
     responses(
         (status = 200, description = "Get markdown task status", body = CommonResponse<TaskStatusResponse>),
     )
 )]
 pub async fn get_markdown_task(
-    Path(task_id): Path<String>,
+    user: AuthUser,
+    Path(task_id): Path<i64>,
 ) -> ResponseResult<TaskStatusResponse> {
-    let state = task_store::get_task(&task_id)
-        .ok_or_else(|| Error::Core(pg_core::Error::not_found("task", &task_id)))?;
-
-    let resp = TaskStatusResponse {
-        task_id,
-        status: state.status.as_str().to_string(),
-        result: state.result,
-        error: state.error,
-    };
-
-    Ok(resp.into_common_response().to_json())
+    let job = find_task(&user, task_id, AccessLevel::Read).await?;
+    Ok(to_task_status(job)?.into_common_response().to_json())
 }
 
-fn extract_metric_values(
-    metrics: &[pg_tables::table::metric::dto::Metric],
-    content: &str,
-) -> Vec<(pg_tables::table::metric::dto::MetricId, String)> {
-    let mut results = Vec::new();
-    let mut seen = HashSet::new();
+#[utoipa::path(
+    get,
+    path = "/tasks",
+    tag = "Medical",
+    params(
+        ListTasksParams
+    ),
+    responses(
+        (status = 200, description = "List ingestion tasks", body = CommonResponse<ListTasksResponse>),
+    )
+)]
+pub async fn list_tasks(
+    user: AuthUser,
+    Query(params): Query<ListTasksParams>,
+) -> ResponseResult<ListTasksResponse> {
+    // 任务结果包含化验数据，只列出有读权限的 subject 的任务
+    HealthApi::new(get_default_ctx())
+        .authorize(&user.actor(), SubjectId(params.subject_id), AccessLevel::Read)
+        .await
+        .map_err(Error::Core)?;
 
-    for metric in metrics {
-        if metric.kind != MetricKind::Primitive || seen.contains(&metric.id) {
-            continue;
-        }
+    let (input, pagination) = params.to_internal(markdown::QUEUE)?;
 
-        let keys = build_metric_keys(metric);
-
-        for line in content.lines() {
-            let line_trim = line.trim();
-            if line_trim.is_empty() {
-                continue;
-            }
-            let line_lower = line_trim.to_ascii_lowercase();
-            let mut matched = false;
-            for key in &keys {
-                if let Some(value) = extract_value_from_line(
-                    line_trim,
-                    &line_lower,
-                    key,
-                    &metric.value_type,
-                ) {
-                    if !value.is_empty() {
-                        results.push((metric.id, value));
-                        seen.insert(metric.id);
-                        matched = true;
-                        break;
-                    }
-                }
-            }
-            if matched {
-                break;
-            }
-        }
-    }
+    let api = JobApi::new(get_default_ctx());
+    let page = api
+        .list(input, Some(pagination))
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListTasksResponse {
+        tasks: page
+            .items
+            .into_iter()
+            .map(to_task_status)
+            .collect::<Result<Vec<_>>>()?,
+        page: page.page,
+        page_size: page.page_size,
+        total: page.total,
+    };
 
-    results
+    Ok(resp.into_common_response().to_json())
 }
 
-fn build_metric_keys(metric: &pg_tables::table::metric::dto::Metric) -> Vec<String> {
-    let mut keys = vec![metric.name.to_ascii_lowercase()];
-    match metric.name.as_str() {
-        "尿比重" => keys.push("比重".to_string()),
-        "尿PH值" => keys.push("ph值".to_string()),
-        "尿蛋白" => keys.push("蛋白质".to_string()),
-        "尿糖" => keys.push("葡萄糖".to_string()),
-        "幽门螺杆菌抗体" => {
-            keys.push("幽门螺旋杆菌抗体".to_string());
-            keys.push("幽门螺旋杆菌抗体检测".to_string());
-        }
-        _ => {}
-    }
-    let mut seen = HashSet::new();
-    keys.into_iter()
-        .filter(|key| seen.insert(key.clone()))
-        .collect()
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}",
+    tag = "Medical",
+    params(
+        ("task_id" = i64, Path, description = "Task ID")
+    ),
+    responses(
+        (status = 200, description = "Get task status", body = CommonResponse<TaskStatusResponse>),
+    )
+)]
+pub async fn get_task(
+    user: AuthUser,
+    Path(task_id): Path<i64>,
+) -> ResponseResult<TaskStatusResponse> {
+    let job = find_task(&user, task_id, AccessLevel::Read).await?;
+    Ok(to_task_status(job)?.into_common_response().to_json())
 }
 
-fn extract_value_from_line(
-    line: &str,
-    line_lower: &str,
-    key: &str,
-    value_type: &MetricValueType,
-) -> Option<String> {
-    if key.is_empty() || !line_lower.contains(key) {
-        return None;
-    }
+#[utoipa::path(
+    delete,
+    path = "/tasks/{task_id}",
+    tag = "Medical",
+    params(
+        ("task_id" = i64, Path, description = "Task ID")
+    ),
+    responses(
+        (status = 200, description = "Cancel a pending or running task", body = CommonResponse<TaskStatusResponse>),
+    )
+)]
+pub async fn cancel_task(
+    user: AuthUser,
+    Path(task_id): Path<i64>,
+) -> ResponseResult<TaskStatusResponse> {
+    find_task(&user, task_id, AccessLevel::Write).await?;
+    tracing::info!("task {} cancelled by user {}", task_id, user.user_id);
 
-    let pos = line_lower.find(key)?;
-    let mut after = line[pos + key.len()..].trim();
-    after = after.trim_start_matches(|c: char| {
-        matches!(c, ':' | '：' | '-' | '—' | '–' | '|' | ' ' | '\t')
-    });
+    let api = JobApi::new(get_default_ctx());
+    let job = api.cancel(JobId(task_id)).await.map_err(Error::Core)?;
 
-    let raw_value = match after.split_once('|') {
-        Some((v, _)) => v.trim(),
-        None => after,
-    };
+    // 任务在本实例执行时立即中止；否则由持有它的实例在 heartbeat 时中止
+    jobs::abort_local(job.id);
 
-    match value_type {
-        MetricValueType::Integer | MetricValueType::Float | MetricValueType::Decimal => {
-            return first_number_in_string(raw_value);
-        }
-        MetricValueType::Boolean | MetricValueType::Text => {
-            return extract_text_value(raw_value);
-        }
-    }
+    Ok(to_task_status(job)?.into_common_response().to_json())
 }
 
-fn first_number_in_string(value: &str) -> Option<String> {
-    let mut start: Option<usize> = None;
-    let mut end: usize = 0;
-
-    for (idx, ch) in value.char_indices() {
-        if start.is_none() {
-            if ch.is_ascii_digit() {
-                start = Some(idx);
-                end = idx + ch.len_utf8();
-            }
-            continue;
-        }
-
-        if ch.is_ascii_digit() || ch == '.' {
-            end = idx + ch.len_utf8();
-        } else {
-            break;
-        }
-    }
+#[utoipa::path(
+    get,
+    path = "/subjects/{subject_id}/latest",
+    tag = "Medical",
+    params(
+        ("subject_id" = i64, Path, description = "Subject ID"),
+        LatestSnapshotParams
+    ),
+    responses(
+        (status = 200, description = "Latest value of every metric for a subject", body = CommonResponse<LatestSnapshotResponse>),
+    )
+)]
+pub async fn get_latest_snapshot(
+    user: AuthUser,
+    Path(subject_id): Path<i64>,
+    Query(params): Query<LatestSnapshotParams>,
+) -> ResponseResult<LatestSnapshotResponse> {
+    let api = HealthApi::new(get_default_ctx());
 
-    start.map(|s| value[s..end].to_string())
-}
+    let snapshot = api
+        .latest_snapshot(&user.actor(), SubjectId(subject_id), params.include_derived)
+        .await
+        .map_err(Error::Core)?;
 
-fn extract_text_value(value: &str) -> Option<String> {
-    if let Some(preferred) = preferred_text_value(value) {
-        return Some(preferred);
-    }
+    let resp = LatestSnapshotResponse {
+        subject_id: snapshot.subject_id.0,
+        items: snapshot
+            .items
+            .into_iter()
+            .map(LatestMetricValueDto::from)
+            .collect(),
+    };
 
-    for token in value.split_whitespace() {
-        let token = token.trim();
-        if token.is_empty() {
-            continue;
-        }
-        let stripped = token
-            .trim_matches(|c: char| matches!(c, '(' | ')' | '（' | '）'))
-            .trim();
-        if stripped.is_empty() {
-            continue;
-        }
-        if is_placeholder_value(stripped) {
-            continue;
-        }
-        if !stripped.chars().any(is_cjk_char) {
-            continue;
-        }
-        return Some(stripped.to_string());
-    }
-    None
+    Ok(resp.into_common_response().to_json())
 }
 
-fn is_placeholder_value(value: &str) -> bool {
-    if matches!(value, "-" | "—" | "/" | "NA" | "N/A" | "检测") {
-        return true;
+/// 查找任务，并校验 user 对任务所属 subject 的访问权限
+pub(crate) async fn find_task(user: &AuthUser, task_id: i64, level: AccessLevel) -> Result<Job> {
+    let job = load_task(task_id).await?;
+
+    match job.subject_id {
+        Some(subject_id) => HealthApi::new(get_default_ctx())
+            .authorize(&user.actor(), SubjectId(subject_id), level)
+            .await
+            .map_err(Error::Core)?,
+        // 不属于任何 subject 的任务只有管理员可见
+        None if user.actor().is_admin => {}
+        None => return Err(Error::Core(pg_core::Error::not_found("task", task_id))),
     }
-    value.chars().all(|c| c.is_ascii_uppercase()) && value.len() <= 6
-}
 
-fn is_cjk_char(c: char) -> bool {
-    matches!(c, '\u{4E00}'..='\u{9FFF}')
+    Ok(job)
 }
 
-fn preferred_text_value(value: &str) -> Option<String> {
-    let candidates = ["阴性", "阳性", "弱阳性", "强阳性", "未检出", "未发现", "正常", "异常"];
-    for candidate in candidates {
-        if value.contains(candidate) {
-            return Some(candidate.to_string());
-        }
-    }
-    None
-}
+/// 查找任务（不做权限校验，调用方需先通过 [`find_task`] 校验）
+pub(crate) async fn load_task(task_id: i64) -> Result<Job> {
+    let api = JobApi::new(get_default_ctx());
 
-fn parse_report_date(content: &str) -> Result<OffsetDateTime, Error> {
-    for token in content.split_whitespace() {
-        let trimmed = token.trim_matches(|c: char| !c.is_ascii_digit() && c != '-');
-        if trimmed.len() == 10
-            && trimmed.as_bytes()[4] == b'-'
-            && trimmed.as_bytes()[7] == b'-'
-            && trimmed.chars().all(|c| c.is_ascii_digit() || c == '-')
-        {
-            let year: i32 = trimmed[0..4].parse().unwrap_or(0);
-            let month: u8 = trimmed[5..7].parse().unwrap_or(0);
-            let day: u8 = trimmed[8..10].parse().unwrap_or(0);
-            if let Ok(date) = time::Date::from_calendar_date(
-                year,
-                time::Month::try_from(month).unwrap_or(time::Month::January),
-                day,
-            ) {
-                return Ok(OffsetDateTime::new_utc(date, time::Time::MIDNIGHT));
-            }
-        }
+    let job = api.get(JobId(task_id)).await.map_err(Error::Core)?;
+    if job.queue != markdown::QUEUE {
+        return Err(Error::Core(pg_core::Error::not_found("task", task_id)));
     }
+    Ok(job)
+}
 
-    Err(Error::Custom(
-        "report date not found in markdown content".to_string(),
-    ))
+pub(crate) fn to_task_status(job: Job) -> Result<TaskStatusResponse> {
+    let result = job
+        .result
+        .map(serde_json::from_value::<UploadMarkdownResponse>)
+        .transpose()
+        .map_err(|e| Error::Custom(format!("invalid task result: {e}")))?;
+
+    // 进度仅用于展示，格式不符时忽略
+    let progress = job
+        .progress
+        .and_then(|v| serde_json::from_value::<MarkdownTaskProgress>(v).ok());
+
+    Ok(TaskStatusResponse {
+        task_id: job.id.0,
+        subject_id: job.subject_id,
+        status: job.status.to_string(),
+        attempts: job.attempts,
+        progress,
+        result,
+        error: job.last_error,
+        created_at: format_rfc3339_utc(job.created_at),
+        updated_at: format_rfc3339_utc(job.updated_at),
+    })
 }
@@ -0,0 +1,341 @@
+use axum::{
+    Json,
+    extract::{Path, Query},
+};
+use demo_db::{MetricId, MetricUnitId, ReferenceRangeId, api::metric::MetricApi};
+use toolcraft_axum_kit::{CommonOk, CommonResponse, Empty, IntoCommonResponse, ResponseResult};
+
+use crate::{
+    dto::metric::{
+        CreateMetricRequest, CreateMetricUnitRequest, CreateReferenceRangeRequest,
+        ListMetricUnitsResponse, ListMetricsParams, ListMetricsResponse,
+        ListReferenceRangesResponse, MetricDto, MetricUnitDto, ReferenceRangeDto,
+        UpdateMetricRequest,
+    },
+    error::Error,
+    middleware::auth::AuthUser,
+    statics::db_manager::get_default_ctx,
+};
+
+#[utoipa::path(
+    post,
+    path = "/metrics",
+    tag = "Medical",
+    request_body = CreateMetricRequest,
+    responses(
+        (status = 200, description = "Create a metric (admin only)", body = CommonResponse<MetricDto>),
+    )
+)]
+pub async fn create_metric(
+    user: AuthUser,
+    Json(req): Json<CreateMetricRequest>,
+) -> ResponseResult<MetricDto> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let metric = api
+        .create(&user.actor(), req.to_internal()?)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(MetricDto::from(metric).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "Medical",
+    params(
+        ListMetricsParams
+    ),
+    responses(
+        (status = 200, description = "List all metrics including deprecated ones (admin only)", body = CommonResponse<ListMetricsResponse>),
+    )
+)]
+pub async fn list_metrics(
+    user: AuthUser,
+    Query(params): Query<ListMetricsParams>,
+) -> ResponseResult<ListMetricsResponse> {
+    let (input, pagination) = params.to_internal()?;
+
+    let api = MetricApi::new(get_default_ctx());
+    let page = api
+        .list(&user.actor(), input, Some(pagination))
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListMetricsResponse {
+        metrics: page.items.into_iter().map(MetricDto::from).collect(),
+        page: page.page,
+        page_size: page.page_size,
+        total: page.total,
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/{metric_id}",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID")
+    ),
+    responses(
+        (status = 200, description = "Get a metric (admin only)", body = CommonResponse<MetricDto>),
+    )
+)]
+pub async fn get_metric(user: AuthUser, Path(metric_id): Path<i64>) -> ResponseResult<MetricDto> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let metric = api
+        .get(&user.actor(), MetricId(metric_id))
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(MetricDto::from(metric).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    patch,
+    path = "/metrics/{metric_id}",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID")
+    ),
+    request_body = UpdateMetricRequest,
+    responses(
+        (status = 200, description = "Update name, unit or visualization of a metric (admin only)", body = CommonResponse<MetricDto>),
+    )
+)]
+pub async fn update_metric(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+    Json(req): Json<UpdateMetricRequest>,
+) -> ResponseResult<MetricDto> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let metric = api
+        .update(&user.actor(), MetricId(metric_id), req.to_internal()?)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(MetricDto::from(metric).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/metrics/{metric_id}/deprecate",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID")
+    ),
+    responses(
+        (status = 200, description = "Deprecate a metric; history stays queryable (admin only)", body = CommonResponse<MetricDto>),
+    )
+)]
+pub async fn deprecate_metric(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+) -> ResponseResult<MetricDto> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let metric = api
+        .deprecate(&user.actor(), MetricId(metric_id))
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(MetricDto::from(metric).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/metrics/{metric_id}/reactivate",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID")
+    ),
+    responses(
+        (status = 200, description = "Reactivate a deprecated metric (admin only)", body = CommonResponse<MetricDto>),
+    )
+)]
+pub async fn reactivate_metric(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+) -> ResponseResult<MetricDto> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let metric = api
+        .reactivate(&user.actor(), MetricId(metric_id))
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(MetricDto::from(metric).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/{metric_id}/reference-ranges",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID")
+    ),
+    responses(
+        (status = 200, description = "List reference ranges of a metric (admin only)", body = CommonResponse<ListReferenceRangesResponse>),
+    )
+)]
+pub async fn list_reference_ranges(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+) -> ResponseResult<ListReferenceRangesResponse> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let ranges = api
+        .list_reference_ranges(&user.actor(), MetricId(metric_id))
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListReferenceRangesResponse {
+        ranges: ranges.into_iter().map(ReferenceRangeDto::from).collect(),
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/metrics/{metric_id}/reference-ranges",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID")
+    ),
+    request_body = CreateReferenceRangeRequest,
+    responses(
+        (status = 200, description = "Add a reference range to a numeric metric (admin only)", body = CommonResponse<ReferenceRangeDto>),
+    )
+)]
+pub async fn create_reference_range(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+    Json(req): Json<CreateReferenceRangeRequest>,
+) -> ResponseResult<ReferenceRangeDto> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let range = api
+        .create_reference_range(&user.actor(), req.to_internal(metric_id)?)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(ReferenceRangeDto::from(range)
+        .into_common_response()
+        .to_json())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/metrics/{metric_id}/reference-ranges/{range_id}",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID"),
+        ("range_id" = i64, Path, description = "Reference range ID")
+    ),
+    responses(
+        (status = 200, description = "Delete a reference range (admin only)", body = CommonOk),
+    )
+)]
+pub async fn delete_reference_range(
+    user: AuthUser,
+    Path((metric_id, range_id)): Path<(i64, i64)>,
+) -> ResponseResult<Empty> {
+    let api = MetricApi::new(get_default_ctx());
+
+    api.delete_reference_range(
+        &user.actor(),
+        MetricId(metric_id),
+        ReferenceRangeId(range_id),
+    )
+    .await
+    .map_err(Error::Core)?;
+
+    Ok(Empty.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/{metric_id}/units",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID")
+    ),
+    responses(
+        (status = 200, description = "List alternative units of a metric (admin only)", body = CommonResponse<ListMetricUnitsResponse>),
+    )
+)]
+pub async fn list_metric_units(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+) -> ResponseResult<ListMetricUnitsResponse> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let units = api
+        .list_units(&user.actor(), MetricId(metric_id))
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListMetricUnitsResponse {
+        units: units.into_iter().map(MetricUnitDto::from).collect(),
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/metrics/{metric_id}/units",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID")
+    ),
+    request_body = CreateMetricUnitRequest,
+    responses(
+        (status = 200, description = "Register an alternative unit of a numeric metric (admin only)", body = CommonResponse<MetricUnitDto>),
+    )
+)]
+pub async fn create_metric_unit(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+    Json(req): Json<CreateMetricUnitRequest>,
+) -> ResponseResult<MetricUnitDto> {
+    let api = MetricApi::new(get_default_ctx());
+
+    let unit = api
+        .create_unit(&user.actor(), req.to_internal(metric_id)?)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(MetricUnitDto::from(unit).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/metrics/{metric_id}/units/{unit_id}",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Metric ID"),
+        ("unit_id" = i64, Path, description = "Metric unit ID")
+    ),
+    responses(
+        (status = 200, description = "Delete an alternative unit (admin only)", body = CommonOk),
+    )
+)]
+pub async fn delete_metric_unit(
+    user: AuthUser,
+    Path((metric_id, unit_id)): Path<(i64, i64)>,
+) -> ResponseResult<Empty> {
+    let api = MetricApi::new(get_default_ctx());
+
+    api.delete_unit(&user.actor(), MetricId(metric_id), MetricUnitId(unit_id))
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(Empty.into_common_response().to_json())
+}
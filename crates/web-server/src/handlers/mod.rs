@@ -1,2 +1,9 @@
+pub mod access;
+pub mod auth;
+pub mod data_source;
 pub mod medical;
+pub mod metric;
+pub mod recipe;
+pub mod subject;
 pub mod llm;
+pub mod stream;
@@ -0,0 +1,175 @@
+use axum::{
+    Json,
+    extract::{Path, Query},
+};
+use demo_db::{
+    MetricId, api::recipe::RecipeApi, calc::list_calcs, dto::recipe::QueryRecipeRequest,
+};
+use toolcraft_axum_kit::{CommonResponse, IntoCommonResponse, ResponseResult};
+
+use crate::{
+    dto::recipe::{
+        CalcDto, CreateRecipeRequest, ListCalcsResponse, ListRecipesParams, ListRecipesResponse,
+        PublishRecipeRequest, RecipeDto,
+    },
+    error::Error,
+    middleware::auth::AuthUser,
+    statics::db_manager::get_default_ctx,
+};
+
+#[utoipa::path(
+    post,
+    path = "/recipes",
+    tag = "Medical",
+    request_body = CreateRecipeRequest,
+    responses(
+        (status = 200, description = "Create a recipe for a derived metric (admin only)", body = CommonResponse<RecipeDto>),
+    )
+)]
+pub async fn create_recipe(
+    user: AuthUser,
+    Json(req): Json<CreateRecipeRequest>,
+) -> ResponseResult<RecipeDto> {
+    let api = RecipeApi::new(get_default_ctx());
+
+    let recipe = api
+        .create(&user.actor(), req.to_internal())
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(RecipeDto::from(recipe).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/recipes",
+    tag = "Medical",
+    params(
+        ListRecipesParams
+    ),
+    responses(
+        (status = 200, description = "List recipes (admin only)", body = CommonResponse<ListRecipesResponse>),
+    )
+)]
+pub async fn list_recipes(
+    user: AuthUser,
+    Query(params): Query<ListRecipesParams>,
+) -> ResponseResult<ListRecipesResponse> {
+    let api = RecipeApi::new(get_default_ctx());
+
+    let recipes = api
+        .list(
+            &user.actor(),
+            QueryRecipeRequest {
+                calc_key: params.calc_key,
+            },
+        )
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListRecipesResponse {
+        recipes: recipes.into_iter().map(RecipeDto::from).collect(),
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/recipes/{recipe_id}",
+    tag = "Medical",
+    params(
+        ("recipe_id" = i64, Path, description = "Recipe ID")
+    ),
+    responses(
+        (status = 200, description = "Get a recipe (admin only)", body = CommonResponse<RecipeDto>),
+    )
+)]
+pub async fn get_recipe(user: AuthUser, Path(recipe_id): Path<i64>) -> ResponseResult<RecipeDto> {
+    let api = RecipeApi::new(get_default_ctx());
+
+    let recipe = api
+        .get(&user.actor(), recipe_id)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(RecipeDto::from(recipe).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/recipes/calcs",
+    tag = "Medical",
+    responses(
+        (status = 200, description = "List registered calculations and their arguments", body = CommonResponse<ListCalcsResponse>),
+    )
+)]
+pub async fn list_recipe_calcs() -> ResponseResult<ListCalcsResponse> {
+    let resp = ListCalcsResponse {
+        calcs: list_calcs()
+            .iter()
+            .map(|calc| CalcDto {
+                calc_key: calc.key.to_string(),
+                args: calc.args.iter().map(|a| a.to_string()).collect(),
+            })
+            .collect(),
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/{metric_id}/recipes",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Derived metric ID")
+    ),
+    responses(
+        (status = 200, description = "List all recipe versions of a derived metric (admin only)", body = CommonResponse<ListRecipesResponse>),
+    )
+)]
+pub async fn list_recipe_versions(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+) -> ResponseResult<ListRecipesResponse> {
+    let api = RecipeApi::new(get_default_ctx());
+
+    let recipes = api
+        .list_versions(&user.actor(), MetricId(metric_id))
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListRecipesResponse {
+        recipes: recipes.into_iter().map(RecipeDto::from).collect(),
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    post,
+    path = "/metrics/{metric_id}/recipes",
+    tag = "Medical",
+    params(
+        ("metric_id" = i64, Path, description = "Derived metric ID")
+    ),
+    request_body = PublishRecipeRequest,
+    responses(
+        (status = 200, description = "Publish a new recipe version; older versions keep computing their own period (admin only)", body = CommonResponse<RecipeDto>),
+    )
+)]
+pub async fn publish_recipe(
+    user: AuthUser,
+    Path(metric_id): Path<i64>,
+    Json(req): Json<PublishRecipeRequest>,
+) -> ResponseResult<RecipeDto> {
+    let api = RecipeApi::new(get_default_ctx());
+
+    let recipe = api
+        .publish(&user.actor(), req.to_internal(metric_id)?)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(RecipeDto::from(recipe).into_common_response().to_json())
+}
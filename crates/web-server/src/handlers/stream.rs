@@ -0,0 +1,325 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    convert::Infallible,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{Path, Query},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use demo_db::{
+    AccessLevel, Observation, ObservationId,
+    api::medical::HealthApi,
+    dto::{access::Actor, medical::QueryObservationRequest},
+};
+use futures_util::{Stream, stream};
+use toolcraft_axum_kit::ApiError;
+
+use crate::{
+    dto::medical::{ObservationEventDto, StreamObservationParams, format_rfc3339_utc},
+    error::{Error, Result},
+    handlers::medical::{find_task, load_task, to_task_status},
+    middleware::auth::AuthUser,
+    statics::db_manager::get_default_ctx,
+};
+
+/// 任务状态的轮询间隔
+const TASK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 新观测的轮询间隔
+const OBSERVATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 每次查询最多读取的观测条数
+const OBSERVATION_BATCH_SIZE: u64 = 100;
+
+/// 重新扫描的时间窗口：写入后这么久之内提交的观测都不会漏推
+const OBSERVATION_OVERLAP: Duration = Duration::from_secs(60);
+
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}/events",
+    tag = "Medical",
+    params(
+        ("task_id" = i64, Path, description = "Task ID")
+    ),
+    responses(
+        (status = 200, description = "Stream of `status` events (TaskStatusResponse JSON); closes once the task is finished", content_type = "text/event-stream", body = String),
+    )
+)]
+pub async fn stream_task_events(
+    user: AuthUser,
+    Path(task_id): Path<i64>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    // 先确认任务存在且有读权限，否则直接返回 404 / 403
+    find_task(&user, task_id, AccessLevel::Read).await?;
+
+    struct State {
+        task_id: i64,
+        last: Option<String>,
+        done: bool,
+    }
+
+    let state = State {
+        task_id,
+        last: None,
+        done: false,
+    };
+
+    let events = stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+
+        loop {
+            let snapshot = load_task(state.task_id).await.and_then(|job| {
+                let finished = job.status.is_finished();
+                let data = serde_json::to_string(&to_task_status(job)?)
+                    .map_err(|e| Error::Custom(format!("invalid task status: {e}")))?;
+                Ok((data, finished))
+            });
+
+            match snapshot {
+                // 只在状态 / 进度变化时推送；任务结束后推送最后一次并关闭
+                Ok((data, finished)) => {
+                    if state.last.as_deref() != Some(data.as_str()) {
+                        state.last = Some(data.clone());
+                        state.done = finished;
+                        let event = Event::default().event("status").data(data);
+                        return Some((Ok(event), state));
+                    }
+                }
+                Err(err) => {
+                    state.done = true;
+                    let event = Event::default().event("error").data(err.to_string());
+                    return Some((Ok(event), state));
+                }
+            }
+
+            tokio::time::sleep(TASK_POLL_INTERVAL).await;
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/observations/stream",
+    tag = "Medical",
+    params(
+        StreamObservationParams
+    ),
+    responses(
+        (status = 200, description = "Stream of `observation` events (ObservationEventDto JSON) for newly recorded observations", content_type = "text/event-stream", body = String),
+    )
+)]
+pub async fn stream_observations(
+    user: AuthUser,
+    headers: HeaderMap,
+    Query(params): Query<StreamObservationParams>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let api = HealthApi::new(get_default_ctx());
+    let req = params.to_internal();
+    let actor = user.actor();
+
+    // metric 不存在 / 非 Primitive 时直接返回错误
+    let latest = api
+        .latest_observation_id(&actor, &req)
+        .await
+        .map_err(Error::Core)?;
+
+    // 游标优先级：Last-Event-ID（断线重连）> after_id > 当前最新
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<i64>().ok());
+    let cursor = last_event_id
+        .or(params.after_id)
+        .map(ObservationId)
+        .or(latest);
+
+    struct State {
+        api: HealthApi,
+        actor: Actor,
+        req: QueryObservationRequest,
+        cursor: ObservationCursor,
+        pending: std::vec::IntoIter<ObservationEventDto>,
+        polled: bool,
+        done: bool,
+    }
+
+    let state = State {
+        api,
+        actor,
+        req,
+        cursor: ObservationCursor::new(cursor.map(|id| id.0)),
+        pending: Vec::new().into_iter(),
+        polled: false,
+        done: false,
+    };
+
+    let events = stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+
+        loop {
+            if let Some(item) = state.pending.next() {
+                let event = Event::default()
+                    .event("observation")
+                    .id(item.observation_id.to_string())
+                    .json_data(&item)
+                    .unwrap_or_else(|e| Event::default().event("error").data(e.to_string()));
+                return Some((Ok(event), state));
+            }
+
+            if state.polled {
+                tokio::time::sleep(OBSERVATION_POLL_INTERVAL).await;
+            }
+            state.polled = true;
+
+            match state
+                .cursor
+                .poll(&state.api, &state.actor, &state.req)
+                .await
+            {
+                Ok(batch) => {
+                    state.pending = batch
+                        .into_iter()
+                        .map(|o| ObservationEventDto {
+                            observation_id: o.id.0,
+                            subject_id: o.subject_id.0,
+                            metric_id: o.metric_id.0,
+                            value: o.value.as_str().to_string(),
+                            value_num: o.value_num,
+                            observed_at: format_rfc3339_utc(o.observed_at),
+                            recorded_at: format_rfc3339_utc(o.recorded_at),
+                        })
+                        .collect::<Vec<_>>()
+                        .into_iter();
+                }
+                Err(err) => {
+                    state.done = true;
+                    let event = Event::default().event("error").data(err.to_string());
+                    return Some((Ok(event), state));
+                }
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// 观测流的游标
+///
+/// observation_id 按分配顺序递增，但提交顺序不一定一致：id 较小的行可能晚于
+/// id 较大的行提交。每次轮询都从 [`OBSERVATION_OVERLAP`] 之前的位置重新扫描，
+/// 并按已推送的 id 去重，晚提交的行因此不会被跳过
+struct ObservationCursor {
+    /// 扫描起点（不含）
+    floor: Option<i64>,
+    /// 已推送的最大 id
+    head: Option<i64>,
+    /// 每次轮询时的 head，用来确定 overlap 窗口的起点
+    checkpoints: VecDeque<(Instant, Option<i64>)>,
+    /// floor 之后已推送的 id
+    sent: BTreeSet<i64>,
+}
+
+impl ObservationCursor {
+    fn new(start: Option<i64>) -> Self {
+        Self {
+            floor: start,
+            head: start,
+            checkpoints: VecDeque::new(),
+            sent: BTreeSet::new(),
+        }
+    }
+
+    /// 拉取尚未推送过的观测（按 id 顺序）
+    async fn poll(
+        &mut self,
+        api: &HealthApi,
+        actor: &Actor,
+        req: &QueryObservationRequest,
+    ) -> demo_db::Result<Vec<Observation>> {
+        let mut fresh = Vec::new();
+        let mut after = self.floor;
+
+        // 窗口内的行可能超过一页，翻页直到取完
+        loop {
+            let batch = api
+                .list_new_observations(actor, req, after.map(ObservationId), OBSERVATION_BATCH_SIZE)
+                .await?;
+            let full = batch.len() as u64 >= OBSERVATION_BATCH_SIZE;
+            after = batch.last().map(|o| o.id.0).or(after);
+            fresh.extend(batch.into_iter().filter(|o| self.mark_sent(o.id.0)));
+            if !full {
+                break;
+            }
+        }
+
+        self.advance(Instant::now());
+        Ok(fresh)
+    }
+
+    /// 记录已推送的 id；已推送过时返回 false
+    fn mark_sent(&mut self, id: i64) -> bool {
+        if !self.sent.insert(id) {
+            return false;
+        }
+        self.head = self.head.max(Some(id));
+        true
+    }
+
+    /// 把 floor 推进到 overlap 窗口之前的 head，并丢弃 floor 以下的已推送 id
+    fn advance(&mut self, now: Instant) {
+        self.checkpoints.push_back((now, self.head));
+        while let Some(&(at, head)) = self.checkpoints.front() {
+            if now.duration_since(at) < OBSERVATION_OVERLAP {
+                break;
+            }
+            self.floor = head;
+            self.checkpoints.pop_front();
+        }
+
+        if let Some(floor) = self.floor {
+            self.sent = self.sent.split_off(&(floor + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_dedupes_within_overlap() {
+        let start = Instant::now();
+        let mut cursor = ObservationCursor::new(Some(10));
+
+        assert!(cursor.mark_sent(12));
+        cursor.advance(start);
+        // 窗口内重新扫描到 12 不再推送，晚提交的 11 仍会推送
+        assert!(!cursor.mark_sent(12));
+        assert!(cursor.mark_sent(11));
+        assert_eq!(cursor.floor, Some(10));
+        assert_eq!(cursor.head, Some(12));
+    }
+
+    #[test]
+    fn test_cursor_advances_after_overlap() {
+        let start = Instant::now();
+        let mut cursor = ObservationCursor::new(None);
+
+        assert!(cursor.mark_sent(5));
+        cursor.advance(start);
+        assert_eq!(cursor.floor, None);
+
+        cursor.advance(start + OBSERVATION_OVERLAP);
+        assert_eq!(cursor.floor, Some(5));
+        assert!(cursor.sent.is_empty());
+    }
+}
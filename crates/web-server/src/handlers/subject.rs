@@ -0,0 +1,183 @@
+use axum::{
+    Json,
+    extract::{Path, Query},
+};
+use demo_db::{SubjectId, api::subject::SubjectApi};
+use toolcraft_axum_kit::{CommonResponse, IntoCommonResponse, ResponseResult};
+
+use crate::{
+    dto::subject::{
+        CreateSubjectRequest, ListSubjectsParams, ListSubjectsResponse, SaveSubjectProfileRequest,
+        SubjectDto, SubjectProfileDto,
+    },
+    error::Error,
+    middleware::auth::AuthUser,
+    statics::db_manager::get_default_ctx,
+};
+
+#[utoipa::path(
+    post,
+    path = "/subjects",
+    tag = "Medical",
+    request_body = CreateSubjectRequest,
+    responses(
+        (status = 200, description = "Create a subject; the caller becomes its owner", body = CommonResponse<SubjectDto>),
+    )
+)]
+pub async fn create_subject(
+    user: AuthUser,
+    Json(req): Json<CreateSubjectRequest>,
+) -> ResponseResult<SubjectDto> {
+    let api = SubjectApi::new(get_default_ctx());
+
+    let subject = api
+        .create(&user.actor(), req.to_internal()?)
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(SubjectDto::from(subject).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/subjects",
+    tag = "Medical",
+    params(
+        ListSubjectsParams
+    ),
+    responses(
+        (status = 200, description = "List subjects visible to the caller", body = CommonResponse<ListSubjectsResponse>),
+    )
+)]
+pub async fn list_subjects(
+    user: AuthUser,
+    Query(params): Query<ListSubjectsParams>,
+) -> ResponseResult<ListSubjectsResponse> {
+    let (input, pagination) = params.to_internal()?;
+
+    let api = SubjectApi::new(get_default_ctx());
+    let page = api
+        .list(&user.actor(), input, Some(pagination))
+        .await
+        .map_err(Error::Core)?;
+
+    let resp = ListSubjectsResponse {
+        subjects: page.items.into_iter().map(SubjectDto::from).collect(),
+        page: page.page,
+        page_size: page.page_size,
+        total: page.total,
+    };
+
+    Ok(resp.into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/subjects/{subject_id}",
+    tag = "Medical",
+    params(
+        ("subject_id" = i64, Path, description = "Subject ID")
+    ),
+    responses(
+        (status = 200, description = "Get a subject", body = CommonResponse<SubjectDto>),
+    )
+)]
+pub async fn get_subject(
+    user: AuthUser,
+    Path(subject_id): Path<i64>,
+) -> ResponseResult<SubjectDto> {
+    let api = SubjectApi::new(get_default_ctx());
+
+    let subject = api
+        .get(&user.actor(), SubjectId(subject_id))
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(SubjectDto::from(subject).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/subjects/{subject_id}",
+    tag = "Medical",
+    params(
+        ("subject_id" = i64, Path, description = "Subject ID")
+    ),
+    responses(
+        (status = 200, description = "Deactivate a subject (owner only); history is kept", body = CommonResponse<SubjectDto>),
+    )
+)]
+pub async fn deactivate_subject(
+    user: AuthUser,
+    Path(subject_id): Path<i64>,
+) -> ResponseResult<SubjectDto> {
+    let api = SubjectApi::new(get_default_ctx());
+
+    let subject = api
+        .deactivate(&user.actor(), SubjectId(subject_id))
+        .await
+        .map_err(Error::Core)?;
+
+    Ok(SubjectDto::from(subject).into_common_response().to_json())
+}
+
+#[utoipa::path(
+    get,
+    path = "/subjects/{subject_id}/profile",
+    tag = "Medical",
+    params(
+        ("subject_id" = i64, Path, description = "Subject ID")
+    ),
+    responses(
+        (status = 200, description = "Get the demographics profile of a subject", body = CommonResponse<SubjectProfileDto>),
+    )
+)]
+pub async fn get_subject_profile(
+    user: AuthUser,
+    Path(subject_id): Path<i64>,
+) -> ResponseResult<SubjectProfileDto> {
+    let api = SubjectApi::new(get_default_ctx());
+
+    let profile = api
+        .get_profile(&user.actor(), SubjectId(subject_id))
+        .await
+        .map_err(Error::Core)?
+        .ok_or_else(|| Error::Core(pg_core::Error::not_found("subject_profile", subject_id)))?;
+
+    let age = api.age(&profile).await.map_err(Error::Core)?;
+
+    Ok(SubjectProfileDto::new(profile, age)
+        .into_common_response()
+        .to_json())
+}
+
+#[utoipa::path(
+    put,
+    path = "/subjects/{subject_id}/profile",
+    tag = "Medical",
+    params(
+        ("subject_id" = i64, Path, description = "Subject ID")
+    ),
+    request_body = SaveSubjectProfileRequest,
+    responses(
+        (status = 200, description = "Create or replace the demographics profile of a subject", body = CommonResponse<SubjectProfileDto>),
+    )
+)]
+pub async fn save_subject_profile(
+    user: AuthUser,
+    Path(subject_id): Path<i64>,
+    Json(req): Json<SaveSubjectProfileRequest>,
+) -> ResponseResult<SubjectProfileDto> {
+    let api = SubjectApi::new(get_default_ctx());
+
+    let profile = api
+        .save_profile(&user.actor(), req.to_internal(subject_id)?)
+        .await
+        .map_err(Error::Core)?;
+
+    let age = api.age(&profile).await.map_err(Error::Core)?;
+
+    Ok(SubjectProfileDto::new(profile, age)
+        .into_common_response()
+        .to_json())
+}
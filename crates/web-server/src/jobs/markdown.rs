@@ -0,0 +1,404 @@
+use std::collections::HashSet;
+
+use demo_db::{
+    AccessLevel, CreateDataSource, DataSourceKind,
+    api::medical::HealthApi,
+    dto::{
+        access::Actor,
+        medical::{RecordReportRequest, ReportValue},
+    },
+};
+use pg_core::{advisory_key, lock};
+use pg_tables::table::metric::dto::{MetricKind, MetricValueType};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+use time::OffsetDateTime;
+
+use super::JobContext;
+use crate::{
+    dto::medical::{
+        MarkdownTaskProgress, SkippedValueDto, UploadMarkdownRequest, UploadMarkdownResponse,
+        format_rfc3339_utc,
+    },
+    error::{Error, Result},
+    middleware::auth::AuthUser,
+    statics::db_manager::get_default_ctx,
+};
+
+/// Markdown 报告解析入库的队列名
+pub const QUEUE: &str = "markdown_ingest";
+
+/// 最大尝试次数（含首次）
+pub const MAX_ATTEMPTS: i32 = 3;
+
+/// 任务输入：上传请求 + 提交任务的用户
+///
+/// worker 以提交者的身份写入观测，授权检查与同步接口一致
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Payload {
+    pub user: AuthUser,
+
+    #[serde(flatten)]
+    pub request: UploadMarkdownRequest,
+}
+
+/// 执行一次 markdown 入库任务
+///
+/// payload 为 [`Payload`]，返回值为 `UploadMarkdownResponse`
+pub async fn run(ctx: &JobContext, payload: JsonValue) -> Result<JsonValue> {
+    let Payload { user, request: req } = serde_json::from_value(payload)
+        .map_err(|e| Error::Custom(format!("invalid markdown job payload: {e}")))?;
+    let actor = user.actor();
+
+    // 同一份报告只允许一个实例处理（重复提交时）
+    let lock_name = format!("markdown-report:{}:{}", req.subject_id, req.source_name);
+    let guard = lock::try_lock(&get_default_ctx(), advisory_key(&lock_name))
+        .await?
+        .ok_or_else(|| {
+            Error::Unavailable("report is already being processed by another instance".to_string())
+        })?;
+
+    let result = ingest(ctx, &actor, &req).await;
+
+    if let Err(err) = guard.release().await {
+        tracing::warn!("failed to release report lock {}: {}", lock_name, err);
+    }
+
+    serde_json::to_value(result?)
+        .map_err(|e| Error::Custom(format!("failed to serialize markdown job result: {e}")))
+}
+
+async fn ingest(
+    job: &JobContext,
+    actor: &Actor,
+    req: &UploadMarkdownRequest,
+) -> Result<UploadMarkdownResponse> {
+    let mut progress = MarkdownTaskProgress {
+        step: "parsing".to_string(),
+        ..Default::default()
+    };
+    job.report_progress(&progress).await;
+
+    let ctx = get_default_ctx();
+
+    let observed_at = parse_report_date(&req.file_content)?;
+    let observed_at_str = format_rfc3339_utc(observed_at);
+
+    progress.step = "matching_metrics".to_string();
+    job.report_progress(&progress).await;
+
+    let api = HealthApi::new(ctx);
+
+    // 授权可能在排队期间被撤销，执行前再校验一次
+    api.authorize(
+        actor,
+        demo_db::SubjectId(req.subject_id),
+        AccessLevel::Write,
+    )
+    .await
+    .map_err(Error::Core)?;
+
+    let metrics = api.list_selectable_metrics().await.map_err(Error::Core)?;
+    let extracted = extract_metric_values(&metrics, &req.file_content);
+
+    progress.metrics_matched = extracted.len();
+    progress.step = "saving_source".to_string();
+    job.report_progress(&progress).await;
+    let metrics_json = extracted
+        .iter()
+        .map(|(metric_id, value, unit)| {
+            json!({
+                "metric_id": metric_id.0,
+                "value": value,
+                "unit": unit,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let parsed_data = json!({
+        "observed_at": observed_at_str,
+        "metrics": metrics_json,
+    });
+
+    let input = CreateDataSource {
+        kind: DataSourceKind::from(req.source_type.as_str()),
+        name: req.source_name.clone(),
+        metadata: Some(parsed_data.clone()),
+    };
+
+    progress.step = "inserting_observations".to_string();
+    job.report_progress(&progress).await;
+
+    // 来源与观测在同一事务内写入：任意一项失败时整体回滚，任务走失败 / 重试流程，
+    // 重试不会产生重复的来源或观测；单位无法换算的值跳过，在结果中列出
+    let report = RecordReportRequest {
+        subject_id: demo_db::SubjectId(req.subject_id),
+        observed_at,
+        source: input,
+        values: extracted
+            .into_iter()
+            .map(|(metric_id, value, unit)| ReportValue {
+                metric_id,
+                value: demo_db::ObservationValue(value),
+                unit,
+            })
+            .collect(),
+    };
+    let result = api
+        .record_report(actor, report)
+        .await
+        .map_err(Error::Core)?;
+    let model = result.source;
+    let records_inserted = result.records_inserted;
+    let skipped = result
+        .skipped
+        .into_iter()
+        .map(|v| SkippedValueDto {
+            metric_id: v.metric_id.0,
+            unit: v.unit,
+        })
+        .collect();
+    progress.rows_inserted = records_inserted;
+
+    progress.step = "completed".to_string();
+    job.report_progress(&progress).await;
+
+    let now = OffsetDateTime::now_utc();
+    let created_at = now
+        .to_offset(time::UtcOffset::UTC)
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| "invalid datetime".to_string());
+
+    Ok(UploadMarkdownResponse {
+        source_id: model.id.0,
+        source_type: model.kind.to_string(),
+        source_name: model.name,
+        parsed_data,
+        created_at,
+        records_inserted,
+        skipped,
+    })
+}
+
+/// 提取 (metric, 值, 报告上的单位)
+fn extract_metric_values(
+    metrics: &[pg_tables::table::metric::dto::Metric],
+    content: &str,
+) -> Vec<(
+    pg_tables::table::metric::dto::MetricId,
+    String,
+    Option<String>,
+)> {
+    let mut results = Vec::new();
+    let mut seen = HashSet::new();
+
+    for metric in metrics {
+        if metric.kind != MetricKind::Primitive || seen.contains(&metric.id) {
+            continue;
+        }
+
+        let keys = build_metric_keys(metric);
+
+        for line in content.lines() {
+            let line_trim = line.trim();
+            if line_trim.is_empty() {
+                continue;
+            }
+            let line_lower = line_trim.to_ascii_lowercase();
+            let mut matched = false;
+            for key in &keys {
+                if let Some((value, unit)) =
+                    extract_value_from_line(line_trim, &line_lower, key, &metric.value_type)
+                {
+                    if !value.is_empty() {
+                        results.push((metric.id, value, unit));
+                        seen.insert(metric.id);
+                        matched = true;
+                        break;
+                    }
+                }
+            }
+            if matched {
+                break;
+            }
+        }
+    }
+
+    results
+}
+
+fn build_metric_keys(metric: &pg_tables::table::metric::dto::Metric) -> Vec<String> {
+    let mut keys = vec![metric.name.to_ascii_lowercase()];
+    match metric.name.as_str() {
+        "尿比重" => keys.push("比重".to_string()),
+        "尿PH值" => keys.push("ph值".to_string()),
+        "尿蛋白" => keys.push("蛋白质".to_string()),
+        "尿糖" => keys.push("葡萄糖".to_string()),
+        "幽门螺杆菌抗体" => {
+            keys.push("幽门螺旋杆菌抗体".to_string());
+            keys.push("幽门螺旋杆菌抗体检测".to_string());
+        }
+        _ => {}
+    }
+    let mut seen = HashSet::new();
+    keys.into_iter()
+        .filter(|key| seen.insert(key.clone()))
+        .collect()
+}
+
+fn extract_value_from_line(
+    line: &str,
+    line_lower: &str,
+    key: &str,
+    value_type: &MetricValueType,
+) -> Option<(String, Option<String>)> {
+    if key.is_empty() || !line_lower.contains(key) {
+        return None;
+    }
+
+    let pos = line_lower.find(key)?;
+    let mut after = line[pos + key.len()..].trim();
+    after = after
+        .trim_start_matches(|c: char| matches!(c, ':' | '：' | '-' | '—' | '–' | '|' | ' ' | '\t'));
+
+    let (raw_value, rest) = match after.split_once('|') {
+        Some((v, rest)) => (v.trim(), rest),
+        None => (after, ""),
+    };
+
+    match value_type {
+        MetricValueType::Integer | MetricValueType::Float | MetricValueType::Decimal => {
+            let (value, end) = first_number_in_string(raw_value)?;
+            // 单位紧跟在数值后面（"5.6 mmol/L"），或在表格的下一列
+            let next_cell = rest.split('|').next().unwrap_or_default();
+            let unit = raw_value[end..]
+                .split_whitespace()
+                .next()
+                .filter(|u| looks_like_unit(u))
+                .or_else(|| Some(next_cell.trim()).filter(|u| looks_like_unit(u)))
+                .map(str::to_string);
+            Some((value, unit))
+        }
+        MetricValueType::Boolean | MetricValueType::Text => {
+            extract_text_value(raw_value).map(|v| (v, None))
+        }
+    }
+}
+
+/// 是否像一个单位（mmol/L、%、°C 等）；含中文的写法（例如 "个/HP"）不识别，按指标单位入库
+fn looks_like_unit(token: &str) -> bool {
+    !token.is_empty()
+        && !token.contains(char::is_whitespace)
+        && !matches!(token, "H" | "L" | "↑" | "↓")
+        && !token.chars().any(is_cjk_char)
+        && token
+            .chars()
+            .any(|c| c.is_alphabetic() || matches!(c, '%' | '/' | '°'))
+}
+
+/// 第一个数字及其结束位置
+fn first_number_in_string(value: &str) -> Option<(String, usize)> {
+    let mut start: Option<usize> = None;
+    let mut end: usize = 0;
+
+    for (idx, ch) in value.char_indices() {
+        if start.is_none() {
+            if ch.is_ascii_digit() {
+                start = Some(idx);
+                end = idx + ch.len_utf8();
+            }
+            continue;
+        }
+
+        if ch.is_ascii_digit() || ch == '.' {
+            end = idx + ch.len_utf8();
+        } else {
+            break;
+        }
+    }
+
+    start.map(|s| (value[s..end].to_string(), end))
+}
+
+fn extract_text_value(value: &str) -> Option<String> {
+    if let Some(preferred) = preferred_text_value(value) {
+        return Some(preferred);
+    }
+
+    for token in value.split_whitespace() {
+        let token = token.trim();
+        if token.is_empty() {
+            continue;
+        }
+        let stripped = token
+            .trim_matches(|c: char| matches!(c, '(' | ')' | '（' | '）'))
+            .trim();
+        if stripped.is_empty() {
+            continue;
+        }
+        if is_placeholder_value(stripped) {
+            continue;
+        }
+        if !stripped.chars().any(is_cjk_char) {
+            continue;
+        }
+        return Some(stripped.to_string());
+    }
+    None
+}
+
+fn is_placeholder_value(value: &str) -> bool {
+    if matches!(value, "-" | "—" | "/" | "NA" | "N/A" | "检测") {
+        return true;
+    }
+    value.chars().all(|c| c.is_ascii_uppercase()) && value.len() <= 6
+}
+
+fn is_cjk_char(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}')
+}
+
+fn preferred_text_value(value: &str) -> Option<String> {
+    let candidates = [
+        "阴性",
+        "阳性",
+        "弱阳性",
+        "强阳性",
+        "未检出",
+        "未发现",
+        "正常",
+        "异常",
+    ];
+    for candidate in candidates {
+        if value.contains(candidate) {
+            return Some(candidate.to_string());
+        }
+    }
+    None
+}
+
+fn parse_report_date(content: &str) -> Result<OffsetDateTime> {
+    for token in content.split_whitespace() {
+        let trimmed = token.trim_matches(|c: char| !c.is_ascii_digit() && c != '-');
+        if trimmed.len() == 10
+            && trimmed.as_bytes()[4] == b'-'
+            && trimmed.as_bytes()[7] == b'-'
+            && trimmed.chars().all(|c| c.is_ascii_digit() || c == '-')
+        {
+            let year: i32 = trimmed[0..4].parse().unwrap_or(0);
+            let month: u8 = trimmed[5..7].parse().unwrap_or(0);
+            let day: u8 = trimmed[8..10].parse().unwrap_or(0);
+            if let Ok(date) = time::Date::from_calendar_date(
+                year,
+                time::Month::try_from(month).unwrap_or(time::Month::January),
+                day,
+            ) {
+                return Ok(OffsetDateTime::new_utc(date, time::Time::MIDNIGHT));
+            }
+        }
+    }
+
+    Err(Error::Custom(
+        "report date not found in markdown content".to_string(),
+    ))
+}
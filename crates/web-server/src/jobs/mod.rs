@@ -0,0 +1,231 @@
+pub mod markdown;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration as StdDuration,
+};
+
+use demo_db::{
+    ClaimJob, Job, JobId,
+    api::{account::AccountApi, job::JobApi},
+};
+use pg_core::LeaderElection;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use time::Duration;
+use tokio::sync::Notify;
+
+use crate::{
+    error::{Error, Result},
+    statics::db_manager::get_default_ctx,
+};
+
+/// 队列为空时的轮询间隔
+const POLL_INTERVAL: StdDuration = StdDuration::from_secs(2);
+
+/// 可见性超时：worker 崩溃后任务多久可被重新领取
+const VISIBILITY_TIMEOUT: Duration = Duration::minutes(5);
+
+/// 心跳间隔（必须小于可见性超时）
+///
+/// 也决定了其它实例发起的取消最迟多久生效
+const HEARTBEAT_INTERVAL: StdDuration = StdDuration::from_secs(15);
+
+/// 暂时性失败（数据库连接、锁被占用）后的重试退避；其它失败不重试
+const RETRY_DELAY: Duration = Duration::seconds(30);
+
+/// TTL 清理的执行间隔
+const CLEANUP_INTERVAL: StdDuration = StdDuration::from_secs(600);
+
+/// 结束的任务保留多久
+const JOB_TTL: Duration = Duration::days(7);
+
+/// 本实例正在执行的任务 → 取消信号
+static RUNNING: OnceLock<Mutex<HashMap<i64, Arc<Notify>>>> = OnceLock::new();
+
+fn running() -> &'static Mutex<HashMap<i64, Arc<Notify>>> {
+    RUNNING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 立即中止本实例上正在执行的任务
+///
+/// 任务状态需先在数据库中置为 cancelled；任务不在本实例执行时返回 false，
+/// 持有它的实例会在下一次 heartbeat 时发现并中止
+pub fn abort_local(id: JobId) -> bool {
+    let signal = running().lock().unwrap().get(&id.0).cloned();
+    match signal {
+        Some(signal) => {
+            signal.notify_one();
+            true
+        }
+        None => false,
+    }
+}
+
+/// 正在执行的任务的上下文（供任务上报进度）
+pub struct JobContext {
+    id: JobId,
+    worker_id: String,
+}
+
+impl JobContext {
+    /// 上报进度（尽力而为，失败只记录日志）
+    pub async fn report_progress(&self, progress: &impl Serialize) {
+        let value = match serde_json::to_value(progress) {
+            Ok(value) => value,
+            Err(err) => {
+                tracing::warn!("invalid progress for job {}: {}", self.id.0, err);
+                return;
+            }
+        };
+
+        let api = JobApi::new(get_default_ctx());
+        match api.update_progress(self.id, &self.worker_id, value).await {
+            Ok(true) => {}
+            // 任务已被取消或被其它 worker 接管：中止当前执行
+            Ok(false) => {
+                abort_local(self.id);
+            }
+            Err(err) => tracing::warn!("failed to report progress of job {}: {}", self.id.0, err),
+        }
+    }
+}
+
+/// 启动后台 worker（每个实例都启动，靠数据库抢占任务）
+pub fn spawn_workers() {
+    tokio::spawn(run_worker(markdown::QUEUE));
+    tokio::spawn(run_cleanup());
+}
+
+async fn run_worker(queue: &'static str) {
+    let worker_id = worker_id();
+    tracing::info!("job worker {} started for queue {}", worker_id, queue);
+
+    loop {
+        let api = JobApi::new(get_default_ctx());
+        let claim = ClaimJob {
+            queue: queue.to_string(),
+            worker_id: worker_id.clone(),
+            visibility_timeout: VISIBILITY_TIMEOUT,
+        };
+
+        match api.claim(claim).await {
+            Ok(Some(job)) => run_job(&api, &worker_id, job).await,
+            Ok(None) => tokio::time::sleep(POLL_INTERVAL).await,
+            Err(err) => {
+                tracing::warn!("failed to claim job from {}: {}", queue, err);
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+async fn run_job(api: &JobApi, worker_id: &str, job: Job) {
+    let job_id = job.id;
+    tracing::info!(
+        "running job {} from {} (attempt {}/{})",
+        job_id.0,
+        job.queue,
+        job.attempts,
+        job.max_attempts
+    );
+
+    let abort = Arc::new(Notify::new());
+    running().lock().unwrap().insert(job_id.0, abort.clone());
+
+    let ctx = JobContext {
+        id: job_id,
+        worker_id: worker_id.to_string(),
+    };
+    let work = dispatch(&ctx, job);
+    tokio::pin!(work);
+
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    heartbeat.tick().await;
+
+    // None 表示任务被取消（或不再由本 worker 持有），执行中的 future 被直接丢弃
+    let outcome = loop {
+        tokio::select! {
+            res = &mut work => break Some(res),
+            _ = abort.notified() => break None,
+            _ = heartbeat.tick() => {
+                match api.heartbeat(job_id, worker_id, VISIBILITY_TIMEOUT).await {
+                    Ok(true) => {}
+                    Ok(false) => break None,
+                    Err(err) => tracing::warn!("heartbeat failed for job {}: {}", job_id.0, err),
+                }
+            }
+        }
+    };
+
+    running().lock().unwrap().remove(&job_id.0);
+
+    let finished = match outcome {
+        Some(Ok(result)) => api.complete(job_id, worker_id, result).await.map(|_| ()),
+        Some(Err(err)) => {
+            // 校验失败、未知指标/单位、报告格式错误等重试也不会成功，直接置为 failed
+            let retry_delay = err.is_transient().then_some(RETRY_DELAY);
+            tracing::warn!(
+                "job {} failed (retryable: {}): {}",
+                job_id.0,
+                retry_delay.is_some(),
+                err
+            );
+            api.fail(job_id, worker_id, err.to_string(), retry_delay)
+                .await
+                .map(|_| ())
+        }
+        None => {
+            tracing::info!("job {} aborted: no longer held by {}", job_id.0, worker_id);
+            Ok(())
+        }
+    };
+
+    if let Err(err) = finished {
+        tracing::warn!("failed to store outcome of job {}: {}", job_id.0, err);
+    }
+}
+
+async fn dispatch(ctx: &JobContext, job: Job) -> Result<JsonValue> {
+    match job.queue.as_str() {
+        markdown::QUEUE => markdown::run(ctx, job.payload).await,
+        other => Err(Error::Custom(format!("unknown job queue: {other}"))),
+    }
+}
+
+/// 定期清理过期任务与过期的 token 注销记录，只由 leader 实例执行
+async fn run_cleanup() {
+    let election = LeaderElection::new(get_default_ctx(), "jobs:cleanup");
+    let mut ticker = tokio::time::interval(CLEANUP_INTERVAL);
+
+    loop {
+        ticker.tick().await;
+
+        let outcome = election
+            .run_if_leader(|| async {
+                let (reaped, purged) = JobApi::new(get_default_ctx()).cleanup(JOB_TTL).await?;
+                let tokens = AccountApi::new(get_default_ctx())
+                    .purge_revoked_tokens()
+                    .await?;
+                Ok::<_, demo_db::Error>((reaped, purged, tokens))
+            })
+            .await;
+
+        match outcome {
+            Ok(Some(Ok((reaped, purged, tokens)))) => tracing::info!(
+                "job cleanup: {} reaped, {} purged, {} revoked tokens purged",
+                reaped,
+                purged,
+                tokens
+            ),
+            Ok(Some(Err(err))) | Err(err) => tracing::warn!("job cleanup failed: {}", err),
+            Ok(None) => {}
+        }
+    }
+}
+
+fn worker_id() -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "web-server".to_string());
+    format!("{}-{}", host, std::process::id())
+}
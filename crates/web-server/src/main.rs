@@ -1,7 +1,9 @@
 mod dto;
 mod error;
 mod handlers;
+mod jobs;
 mod logging;
+mod middleware;
 mod routes;
 mod settings;
 mod statics;
@@ -9,12 +11,15 @@ mod utils;
 
 use std::sync::Arc;
 
-use settings::Settings;
+use demo_db::{api::account::AccountApi, dto::account::RegisterAccountRequest};
+use settings::{AdminCfg, Settings};
 use toolcraft_axum_kit::http_server;
 use toolcraft_jwt::Jwt;
 
 use crate::{
-    logging::init_tracing_to_file, statics::db_manager::init_db,
+    logging::init_tracing_to_file,
+    middleware::auth::ADMIN_ROLE,
+    statics::db_manager::{get_default_ctx, init_db},
     statics::llm_client::init_llm,
 };
 
@@ -26,11 +31,35 @@ async fn main() {
         .await
         .expect("DatabaseManager initialization failed");
 
+    if let Some(admin) = settings.admin.clone().or_else(AdminCfg::from_env) {
+        bootstrap_admin(admin)
+            .await
+            .expect("Admin bootstrap failed");
+    }
+
     init_llm(settings.llm).expect("LLM Client initialization failed");
 
+    jobs::spawn_workers();
+
     let jwt = Arc::new(Jwt::new(settings.jwt));
     let router = routes::create_routes(jwt);
     let http_task = http_server::start(settings.http.port, router);
 
     let _ = tokio::join!(http_task);
 }
+
+/// 还没有管理员时创建初始管理员（register 只对管理员开放）
+async fn bootstrap_admin(admin: AdminCfg) -> demo_db::Result<()> {
+    let req = RegisterAccountRequest {
+        username: admin.username,
+        password: admin.password,
+    };
+    match AccountApi::new(get_default_ctx())
+        .bootstrap_admin(req, ADMIN_ROLE)
+        .await?
+    {
+        Some(account) => tracing::info!("created initial admin account {}", account.username),
+        None => tracing::debug!("admin account already exists, skipping bootstrap"),
+    }
+    Ok(())
+}
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{FromRequestParts, Request},
+    http::{HeaderMap, header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use demo_db::{api::account::AccountApi, dto::access::Actor};
+use serde::{Deserialize, Serialize};
+use toolcraft_axum_kit::ApiError;
+use toolcraft_jwt::Jwt;
+use utoipa::{
+    Modify,
+    openapi::{
+        OpenApi,
+        security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme},
+    },
+};
+
+use crate::{error::Error, statics::db_manager::get_default_ctx};
+
+/// OpenAPI 中 bearer 鉴权方案的名称
+pub const BEARER_SCHEME: &str = "bearer_auth";
+
+/// 管理员角色
+pub const ADMIN_ROLE: &str = "admin";
+
+/// 已通过鉴权的用户（由 [`require_auth`] 写入请求扩展）
+///
+/// token 的 `sub` 格式为 `<user_id>` 或 `<user_id>:<role>,<role>`，
+/// 刷新 access token 时 `sub` 原样保留，角色随之保留
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthUser {
+    pub user_id: i64,
+    pub roles: Vec<String>,
+}
+
+impl AuthUser {
+    /// 编码为 token 的 `sub`
+    pub fn to_subject(&self) -> String {
+        if self.roles.is_empty() {
+            self.user_id.to_string()
+        } else {
+            format!("{}:{}", self.user_id, self.roles.join(","))
+        }
+    }
+
+    /// 从 token 的 `sub` 解析
+    pub fn from_subject(sub: &str) -> Option<Self> {
+        let (user_id, roles) = match sub.split_once(':') {
+            Some((user_id, roles)) => (user_id, roles),
+            None => (sub, ""),
+        };
+
+        let user_id = user_id.trim().parse::<i64>().ok()?;
+        let roles = roles
+            .split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        Some(Self { user_id, roles })
+    }
+
+    /// 业务层使用的操作者（`admin` 角色不受 Subject 授权限制）
+    pub fn actor(&self) -> Actor {
+        if self.has_role(ADMIN_ROLE) {
+            Actor::admin(self.user_id)
+        } else {
+            Actor::user(self.user_id)
+        }
+    }
+
+    /// 是否拥有指定角色
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+}
+
+/// 当前请求携带的 access token（由 [`require_auth`] 写入请求扩展，供注销使用）
+#[derive(Debug, Clone)]
+pub struct AuthToken {
+    pub token: String,
+    /// 过期时间（unix 秒）
+    pub expires_at: usize,
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthUser>()
+            .cloned()
+            .ok_or_else(|| Error::Unauthorized("missing credentials".to_string()).into())
+    }
+}
+
+/// 鉴权中间件：校验 `Authorization: Bearer <token>`（含注销列表），并把 [`AuthUser`] 写入请求扩展
+pub async fn require_auth(req: Request, next: Next) -> Result<Response, ApiError> {
+    let token = parse_token(req.headers())
+        .ok_or_else(|| Error::Unauthorized("missing bearer token".to_string()))?;
+    authenticate(req, next, token).await
+}
+
+/// SSE 接口的鉴权中间件
+///
+/// EventSource 无法设置请求头，除 bearer 头外还接受 `?access_token=<token>`；
+/// 查询参数会进入访问日志与 Referer，只挂在 SSE 路由上
+pub async fn require_stream_auth(req: Request, next: Next) -> Result<Response, ApiError> {
+    let token = parse_token(req.headers())
+        .or_else(|| query_token(req.uri().query()))
+        .ok_or_else(|| Error::Unauthorized("missing bearer token".to_string()))?;
+    authenticate(req, next, token).await
+}
+
+async fn authenticate(mut req: Request, next: Next, token: String) -> Result<Response, ApiError> {
+    let jwt = req
+        .extensions()
+        .get::<Arc<Jwt>>()
+        .cloned()
+        .ok_or_else(|| Error::Custom("jwt is not configured".to_string()))?;
+
+    let claims = jwt
+        .validate_access_token(&token)
+        .map_err(|e| Error::Unauthorized(format!("invalid access token: {e}")))?;
+
+    let revoked = AccountApi::new(get_default_ctx())
+        .is_token_revoked(&token)
+        .await
+        .map_err(Error::Core)?;
+    if revoked {
+        return Err(Error::Unauthorized("access token has been revoked".to_string()).into());
+    }
+
+    let user = AuthUser::from_subject(&claims.sub)
+        .ok_or_else(|| Error::Unauthorized("invalid token subject".to_string()))?;
+    req.extensions_mut().insert(user);
+    req.extensions_mut().insert(AuthToken {
+        token,
+        expires_at: claims.exp,
+    });
+
+    Ok(next.run(req).await)
+}
+
+fn parse_token(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("bearer") || token.trim().is_empty() {
+        return None;
+    }
+    Some(token.trim().to_string())
+}
+
+fn query_token(query: Option<&str>) -> Option<String> {
+    query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == "access_token")
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// 在 OpenAPI 中声明 bearer 鉴权，并应用到文档内的所有接口
+pub struct BearerSecurity;
+
+impl Modify for BearerSecurity {
+    fn modify(&self, openapi: &mut OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            BEARER_SCHEME,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+
+        let requirement = SecurityRequirement::new(BEARER_SCHEME, Vec::<String>::new());
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.post,
+                &mut item.put,
+                &mut item.patch,
+                &mut item.delete,
+            ];
+            for operation in operations.into_iter().flatten() {
+                operation.security = Some(vec![requirement.clone()]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_round_trip() {
+        let user = AuthUser {
+            user_id: 42,
+            roles: vec!["admin".to_string(), "clinician".to_string()],
+        };
+        assert_eq!(user.to_subject(), "42:admin,clinician");
+        assert_eq!(AuthUser::from_subject(&user.to_subject()), Some(user));
+    }
+
+    #[test]
+    fn test_subject_without_roles() {
+        let user = AuthUser::from_subject("7").unwrap();
+        assert_eq!(user.user_id, 7);
+        assert!(user.roles.is_empty());
+        assert_eq!(user.to_subject(), "7");
+    }
+
+    #[test]
+    fn test_invalid_subject() {
+        assert_eq!(AuthUser::from_subject("alice"), None);
+        assert_eq!(AuthUser::from_subject(""), None);
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_token(&headers), None);
+
+        headers.insert(header::AUTHORIZATION, "Bearer abc.def".parse().unwrap());
+        assert_eq!(parse_token(&headers), Some("abc.def".to_string()));
+
+        headers.insert(header::AUTHORIZATION, "Basic abc".parse().unwrap());
+        assert_eq!(parse_token(&headers), None);
+    }
+
+    #[test]
+    fn test_query_token() {
+        assert_eq!(
+            query_token(Some("subject_id=1&access_token=abc")),
+            Some("abc".to_string())
+        );
+        assert_eq!(query_token(Some("subject_id=1")), None);
+        assert_eq!(query_token(None), None);
+    }
+}
@@ -0,0 +1,52 @@
+use axum::{Router, middleware, routing::post};
+use toolcraft_axum_kit::{CommonError, CommonResponse};
+use utoipa::OpenApi;
+
+use crate::{
+    dto::auth::{
+        AccountDto, LoginRequest, LogoutRequest, RefreshRequest, RegisterRequest, TokenResponse,
+    },
+    handlers::auth::{login, logout, refresh, register},
+    middleware::auth::require_auth,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        crate::handlers::auth::register,
+        crate::handlers::auth::login,
+        crate::handlers::auth::refresh,
+        crate::handlers::auth::logout,
+    ),
+    components(
+        schemas(
+            RegisterRequest,
+            LoginRequest,
+            RefreshRequest,
+            LogoutRequest,
+            AccountDto,
+            TokenResponse,
+            CommonResponse<AccountDto>,
+            CommonResponse<TokenResponse>,
+            CommonError
+        )
+    ),
+    tags(
+        (name = "Auth", description = "Authentication endpoints")
+    )
+)]
+pub struct AuthApi;
+
+pub fn auth_routes() -> Router {
+    Router::new()
+        .route(
+            "/logout",
+            post(logout).route_layer(middleware::from_fn(require_auth)),
+        )
+        .route(
+            "/register",
+            post(register).route_layer(middleware::from_fn(require_auth)),
+        )
+        .route("/login", post(login))
+        .route("/refresh", post(refresh))
+}
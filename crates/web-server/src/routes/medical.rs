@@ -1,21 +1,69 @@
 use axum::{
-    Router,
-    routing::{get, post},
+    Router, middleware,
+    routing::{delete, get, post},
 };
 use toolcraft_axum_kit::{CommonError, CommonResponse};
 use utoipa::OpenApi;
 
 use crate::{
-    dto::medical::{
-        ListSelectableMetricsResponse, MetricSummaryDto, ObservationPointDto,
-        QueryObservationParams, QueryRecipeObservationResponse, RecordObservationRequest,
-        RecordObservationResponse, SelectableMetricDto, SourceInput, TaskStatusResponse,
-        UploadMarkdownRequest, UploadMarkdownResponse, UploadMarkdownTaskResponse,
+    dto::{
+        access::{GrantAccessRequest, ListSubjectAccessResponse, SubjectAccessDto},
+        data_source::{
+            DataSourceDto, ListSourceObservationsParams, ListSourceObservationsResponse,
+            SourceObservationDto, UpdateDataSourceRequest, VoidDataSourceRequest,
+            VoidDataSourceResultDto,
+        },
+        medical::{
+            LatestMetricValueDto, LatestSnapshotParams, LatestSnapshotResponse,
+            ListSelectableMetricsResponse, ListTasksParams, ListTasksResponse,
+            MarkdownTaskProgress, MetricSummaryDto, ObservationBucketDto, ObservationEventDto,
+            ObservationPointDto, ObservationStatsDto, ObservationStatsParams,
+            ObservationStatsResponse, QueryObservationParams, QueryRecipeObservationResponse,
+            RecordObservationRequest, RecordObservationResponse, SelectableMetricDto,
+            SkippedValueDto, SourceInput, StreamObservationParams, TaskStatusResponse,
+            UploadMarkdownRequest, UploadMarkdownResponse, UploadMarkdownTaskResponse,
+        },
+        metric::{
+            CreateMetricRequest, CreateMetricUnitRequest, CreateReferenceRangeRequest,
+            ListMetricUnitsResponse, ListMetricsParams, ListMetricsResponse,
+            ListReferenceRangesResponse, MetricDto, MetricUnitDto, ReferenceRangeDto,
+            UpdateMetricRequest,
+        },
+        recipe::{
+            CalcDto, CreateRecipeRequest, ListCalcsResponse, ListRecipesParams,
+            ListRecipesResponse, PublishRecipeRequest, RecipeDto,
+        },
+        subject::{
+            CreateSubjectRequest, ListSubjectsParams, ListSubjectsResponse,
+            SaveSubjectProfileRequest, SubjectDto, SubjectProfileDto,
+        },
     },
-    handlers::medical::{
-        get_markdown_task, list_selectable_metrics, query_observations, record_observation,
-        upload_markdown_data_source,
+    handlers::{
+        access::{grant_subject_access, list_subject_access, revoke_subject_access},
+        data_source::{
+            get_data_source, list_data_source_observations, update_data_source, void_data_source,
+        },
+        medical::{
+            cancel_task, get_latest_snapshot, get_markdown_task, get_observation_stats, get_task,
+            list_selectable_metrics, list_tasks, query_observations, record_observation,
+            upload_markdown_data_source,
+        },
+        metric::{
+            create_metric, create_metric_unit, create_reference_range, delete_metric_unit,
+            delete_reference_range, deprecate_metric, get_metric, list_metric_units, list_metrics,
+            list_reference_ranges, reactivate_metric, update_metric,
+        },
+        recipe::{
+            create_recipe, get_recipe, list_recipe_calcs, list_recipe_versions, list_recipes,
+            publish_recipe,
+        },
+        stream::{stream_observations, stream_task_events},
+        subject::{
+            create_subject, deactivate_subject, get_subject, get_subject_profile, list_subjects,
+            save_subject_profile,
+        },
     },
+    middleware::auth::{BearerSecurity, require_auth, require_stream_auth},
 };
 
 #[derive(OpenApi)]
@@ -24,36 +72,142 @@ use crate::{
         crate::handlers::medical::query_observations,
         crate::handlers::medical::record_observation,
         crate::handlers::medical::list_selectable_metrics,
+        crate::handlers::metric::create_metric,
+        crate::handlers::metric::list_metrics,
+        crate::handlers::metric::get_metric,
+        crate::handlers::metric::update_metric,
+        crate::handlers::metric::deprecate_metric,
+        crate::handlers::metric::reactivate_metric,
+        crate::handlers::recipe::create_recipe,
+        crate::handlers::recipe::list_recipes,
+        crate::handlers::recipe::get_recipe,
+        crate::handlers::recipe::list_recipe_calcs,
+        crate::handlers::recipe::list_recipe_versions,
+        crate::handlers::recipe::publish_recipe,
         crate::handlers::medical::upload_markdown_data_source,
         crate::handlers::medical::get_markdown_task,
+        crate::handlers::data_source::get_data_source,
+        crate::handlers::data_source::update_data_source,
+        crate::handlers::data_source::list_data_source_observations,
+        crate::handlers::data_source::void_data_source,
+        crate::handlers::medical::list_tasks,
+        crate::handlers::medical::get_task,
+        crate::handlers::medical::cancel_task,
+        crate::handlers::stream::stream_task_events,
+        crate::handlers::stream::stream_observations,
+        crate::handlers::subject::create_subject,
+        crate::handlers::subject::list_subjects,
+        crate::handlers::subject::get_subject,
+        crate::handlers::subject::deactivate_subject,
+        crate::handlers::access::list_subject_access,
+        crate::handlers::access::grant_subject_access,
+        crate::handlers::access::revoke_subject_access,
+        crate::handlers::medical::get_latest_snapshot,
+        crate::handlers::medical::get_observation_stats,
+        crate::handlers::metric::list_reference_ranges,
+        crate::handlers::metric::create_reference_range,
+        crate::handlers::metric::delete_reference_range,
+        crate::handlers::metric::list_metric_units,
+        crate::handlers::metric::create_metric_unit,
+        crate::handlers::metric::delete_metric_unit,
+        crate::handlers::subject::get_subject_profile,
+        crate::handlers::subject::save_subject_profile,
     ),
     components(
         schemas(
             QueryObservationParams,
             QueryRecipeObservationResponse,
             ObservationPointDto,
+            ObservationBucketDto,
             MetricSummaryDto,
             RecordObservationRequest,
             SourceInput,
             RecordObservationResponse,
             ListSelectableMetricsResponse,
             SelectableMetricDto,
+            MetricDto,
+            CreateMetricRequest,
+            ListMetricsParams,
+            ListMetricsResponse,
+            UpdateMetricRequest,
+            CreateRecipeRequest,
+            PublishRecipeRequest,
+            ListRecipesParams,
+            RecipeDto,
+            ListRecipesResponse,
+            CalcDto,
+            ListCalcsResponse,
             UploadMarkdownRequest,
             UploadMarkdownResponse,
+            SkippedValueDto,
             UploadMarkdownTaskResponse,
+            DataSourceDto,
+            UpdateDataSourceRequest,
+            VoidDataSourceRequest,
+            VoidDataSourceResultDto,
+            ListSourceObservationsParams,
+            SourceObservationDto,
+            ListSourceObservationsResponse,
             TaskStatusResponse,
+            MarkdownTaskProgress,
+            ListTasksParams,
+            ListTasksResponse,
+            StreamObservationParams,
+            ObservationEventDto,
+            CreateSubjectRequest,
+            ListSubjectsParams,
+            SubjectDto,
+            ListSubjectsResponse,
+            GrantAccessRequest,
+            SubjectAccessDto,
+            ListSubjectAccessResponse,
+            LatestSnapshotParams,
+            LatestMetricValueDto,
+            LatestSnapshotResponse,
+            ObservationStatsParams,
+            ObservationStatsDto,
+            ObservationStatsResponse,
+            ReferenceRangeDto,
+            CreateReferenceRangeRequest,
+            ListReferenceRangesResponse,
+            MetricUnitDto,
+            CreateMetricUnitRequest,
+            ListMetricUnitsResponse,
+            SaveSubjectProfileRequest,
+            SubjectProfileDto,
             CommonResponse<QueryRecipeObservationResponse>,
             CommonResponse<RecordObservationResponse>,
             CommonResponse<ListSelectableMetricsResponse>,
+            CommonResponse<MetricDto>,
+            CommonResponse<ListMetricsResponse>,
+            CommonResponse<RecipeDto>,
+            CommonResponse<ListRecipesResponse>,
+            CommonResponse<ListCalcsResponse>,
             CommonResponse<UploadMarkdownResponse>,
             CommonResponse<UploadMarkdownTaskResponse>,
+            CommonResponse<DataSourceDto>,
+            CommonResponse<VoidDataSourceResultDto>,
+            CommonResponse<ListSourceObservationsResponse>,
             CommonResponse<TaskStatusResponse>,
+            CommonResponse<ListTasksResponse>,
+            CommonResponse<SubjectDto>,
+            CommonResponse<ListSubjectsResponse>,
+            CommonResponse<SubjectAccessDto>,
+            CommonResponse<ListSubjectAccessResponse>,
+            CommonResponse<LatestSnapshotResponse>,
+            CommonResponse<ObservationStatsResponse>,
+            CommonResponse<ReferenceRangeDto>,
+            CommonResponse<ListReferenceRangesResponse>,
+            CommonResponse<MetricUnitDto>,
+            CommonResponse<ListMetricUnitsResponse>,
+            CommonResponse<SubjectProfileDto>,
             CommonError
         )
     ),
     tags(
         (name = "Medical", description = "Medical API endpoints")
-    )
+    ),
+    modifiers(&BearerSecurity)
 )]
 pub struct MedicalApi;
 
@@ -63,10 +217,77 @@ pub fn medical_routes() -> Router {
             "/observations",
             get(query_observations).post(record_observation),
         )
+        .route("/metrics", get(list_metrics).post(create_metric))
         .route("/metrics/selectable", get(list_selectable_metrics))
+        .route("/metrics/{metric_id}", get(get_metric).patch(update_metric))
+        .route("/metrics/{metric_id}/deprecate", post(deprecate_metric))
+        .route("/metrics/{metric_id}/reactivate", post(reactivate_metric))
+        .route(
+            "/metrics/{metric_id}/reference-ranges",
+            get(list_reference_ranges).post(create_reference_range),
+        )
+        .route(
+            "/metrics/{metric_id}/reference-ranges/{range_id}",
+            delete(delete_reference_range),
+        )
+        .route(
+            "/metrics/{metric_id}/units",
+            get(list_metric_units).post(create_metric_unit),
+        )
+        .route(
+            "/metrics/{metric_id}/units/{unit_id}",
+            delete(delete_metric_unit),
+        )
+        .route("/recipes", get(list_recipes).post(create_recipe))
+        .route("/recipes/calcs", get(list_recipe_calcs))
+        .route("/recipes/{recipe_id}", get(get_recipe))
+        .route(
+            "/metrics/{metric_id}/recipes",
+            get(list_recipe_versions).post(publish_recipe),
+        )
         .route("/data-source/markdown", post(upload_markdown_data_source))
         .route(
             "/data-source/markdown/tasks/{task_id}",
             get(get_markdown_task),
         )
+        .route(
+            "/data-source/{source_id}",
+            get(get_data_source).patch(update_data_source),
+        )
+        .route(
+            "/data-source/{source_id}/observations",
+            get(list_data_source_observations),
+        )
+        .route("/data-source/{source_id}/void", post(void_data_source))
+        .route("/tasks", get(list_tasks))
+        .route("/tasks/{task_id}", get(get_task).delete(cancel_task))
+        .route("/observations/stats", get(get_observation_stats))
+        .route("/subjects", get(list_subjects).post(create_subject))
+        .route(
+            "/subjects/{subject_id}",
+            get(get_subject).delete(deactivate_subject),
+        )
+        .route(
+            "/subjects/{subject_id}/access",
+            get(list_subject_access).post(grant_subject_access),
+        )
+        .route(
+            "/subjects/{subject_id}/access/{user_id}",
+            delete(revoke_subject_access),
+        )
+        .route("/subjects/{subject_id}/latest", get(get_latest_snapshot))
+        .route(
+            "/subjects/{subject_id}/profile",
+            get(get_subject_profile).put(save_subject_profile),
+        )
+        .route_layer(middleware::from_fn(require_auth))
+        .merge(stream_routes())
+}
+
+/// SSE 路由：EventSource 无法带请求头，允许用 `?access_token=` 传 token
+fn stream_routes() -> Router {
+    Router::new()
+        .route("/tasks/{task_id}/events", get(stream_task_events))
+        .route("/observations/stream", get(stream_observations))
+        .route_layer(middleware::from_fn(require_stream_auth))
 }
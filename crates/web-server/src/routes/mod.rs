@@ -1,3 +1,4 @@
+mod auth;
 mod medical;
 mod llm;
 
@@ -9,11 +10,12 @@ use toolcraft_jwt::Jwt;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use crate::routes::medical::MedicalApi;
+use crate::routes::{auth::AuthApi, medical::MedicalApi};
 
 #[derive(OpenApi)]
 #[openapi(
         nest(
+            (path = "/auth", api = AuthApi),
             (path = "/medical", api = MedicalApi),
         ),
     )]
@@ -24,6 +26,7 @@ pub fn create_routes(jwt: Arc<Jwt>) -> Router {
     let doc = ApiDoc::openapi();
 
     Router::new()
+        .nest("/auth", auth::auth_routes())
         .nest("/medical", medical::medical_routes())
         .nest("/llm", llm::create_llm_routes())
         .layer(Extension(jwt))
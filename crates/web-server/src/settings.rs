@@ -13,6 +13,9 @@ pub struct Settings {
     pub db: Vec<DatabaseConfig>,
     #[serde(default)]
     pub llm: LlmConfig,
+    /// 初始管理员（没有任何管理员账号时在启动时创建）
+    #[serde(default)]
+    pub admin: Option<AdminCfg>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,6 +23,22 @@ pub struct HttpCfg {
     pub port: u16,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdminCfg {
+    pub username: String,
+    pub password: String,
+}
+
+impl AdminCfg {
+    /// 从环境变量 `ADMIN_USERNAME` / `ADMIN_PASSWORD` 读取（配置文件中没有 `[admin]` 时使用）
+    pub fn from_env() -> Option<Self> {
+        Some(Self {
+            username: std::env::var("ADMIN_USERNAME").ok()?,
+            password: std::env::var("ADMIN_PASSWORD").ok()?,
+        })
+    }
+}
+
 impl Settings {
     pub fn load(config_path: &str) -> Result<Self> {
         let r = load_settings(config_path)?;
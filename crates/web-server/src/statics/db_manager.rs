@@ -1,5 +1,6 @@
 use std::sync::OnceLock;
 
+use migration::Migrator;
 use pg_core::{DatabaseConfig, DatabaseManager, DbContext};
 
 use crate::error::{Error, Result};
@@ -11,6 +12,12 @@ pub async fn init_db(configs: Vec<DatabaseConfig>) -> Result<()> {
         .await
         .map_err(|e| Error::Custom(format!("DatabaseManager init failed: {e}")))?;
 
+    // auto_migrate 的库直接升级，其余的库 schema 落后时拒绝启动
+    manager
+        .run_migrations::<Migrator>()
+        .await
+        .map_err(|e| Error::Custom(format!("Database schema check failed: {e}")))?;
+
     DB_MANAGER
         .set(manager)
         .map_err(|_| Error::Custom("DatabaseManager already initialized".to_owned()))?;
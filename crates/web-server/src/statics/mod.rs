@@ -1,3 +1,2 @@
 pub mod db_manager;
 pub mod llm_client;
-pub mod task_store;